@@ -0,0 +1,46 @@
+/// Saved `docker compose` command lines, per project — stored in
+/// ~/.config/docker-cli/bookmarks.json
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    /// Arguments after `docker compose`, as typed in the `:` prompt.
+    pub command: String,
+}
+
+fn bookmarks_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("~/.config"))
+        .join("docker-cli")
+        .join("bookmarks.json")
+}
+
+fn load_all() -> BTreeMap<String, Vec<Bookmark>> {
+    std::fs::read_to_string(bookmarks_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+pub fn load_bookmarks(project: &Path) -> Vec<Bookmark> {
+    load_all().remove(&project.display().to_string()).unwrap_or_default()
+}
+
+pub fn save_bookmarks(project: &Path, bookmarks: &[Bookmark]) {
+    let p = bookmarks_path();
+    if let Some(parent) = p.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let mut all = load_all();
+    if bookmarks.is_empty() {
+        all.remove(&project.display().to_string());
+    } else {
+        all.insert(project.display().to_string(), bookmarks.to_vec());
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&all) {
+        let _ = std::fs::write(&p, json);
+    }
+}
@@ -1,12 +1,43 @@
-use crate::env::{get_profile_value, parse_post_up_tasks};
+use crate::env::{get_profile_value, parse_container_actions, parse_post_up_tasks, read_file_if_exists};
 use std::fs::{self, File};
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 
+/// Tasks file looked up in the project root when `POST_UP_TASKS_FILE` is unset.
+pub const DEFAULT_TASKS_FILE: &str = ".docker-cli-tasks";
+
 #[derive(Debug, Clone)]
 pub struct TaskSpec {
     pub name: String,
     pub cmd: String,
+    /// Re-run interval, declared as `name (every: 30s)::cmd`.
+    pub every: Option<std::time::Duration>,
+    /// Display output without its `[OUT]`/`[ERR]` tags: `name (plain)::cmd`.
+    pub plain: bool,
+    /// Container the task works on, by compose service or container name:
+    /// `name (service: db)::cmd`. `l` jumps between the two.
+    pub service: Option<String>,
+}
+
+/// Templated command run against the selected container, declared in
+/// `CONTAINER_ACTIONS` as `name (key: b)::cmd`.
+#[derive(Debug, Clone)]
+pub struct ContainerAction {
+    pub name: String,
+    pub key: char,
+    pub cmd: String,
+}
+
+impl ContainerAction {
+    /// Substitute `{{id}}`, `{{name}}` and `{{port}}`. `None` when the
+    /// template needs a port and the container publishes none.
+    pub fn render(&self, id: &str, name: &str, port: Option<u16>) -> Option<String> {
+        let cmd = self.cmd.replace("{{id}}", id).replace("{{name}}", name);
+        if !cmd.contains("{{port}}") {
+            return Some(cmd);
+        }
+        port.map(|p| cmd.replace("{{port}}", &p.to_string()))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -17,32 +48,341 @@ pub struct Config {
     pub docker_bin: String,
 
     pub auto_compose_up: bool,
+    /// Ask to bring the stack up (or restart it) at startup
+    /// (`AUTO_COMPOSE_UP`, default on).
+    pub compose_prompt: bool,
     pub compose_profile: String,
+    /// `COMPOSE_FILES`, resolved against `cwd`, in the order given, else the
+    /// files of a `docker/<profile>/` layout. Files that do not exist are
+    /// kept here so they can be reported.
+    pub compose_files: Vec<PathBuf>,
 
     pub db_container: String,
     pub storage_container: String,
 
     pub post_up_tasks: Vec<TaskSpec>,
+    /// Where task definitions are read from first, whether or not it exists.
+    pub tasks_file: PathBuf,
+
+    /// Project-specific commands bound to a key on container items.
+    pub container_actions: Vec<ContainerAction>,
+    /// Keys of the main sidebar actions (`KEY_RESTART`, `KEY_STOP`…).
+    pub keys: crate::keybindings::Keybindings,
 
     pub max_log_lines: usize,
+    /// Longer log lines are cut with a marker (`MAX_LINE_BYTES`), 0 for no limit.
+    pub max_line_bytes: usize,
+    /// Byte cap of the log pane and of each task buffer, on top of
+    /// `max_log_lines` (`MAX_LOG_BYTES`), 0 for no limit.
+    pub max_log_bytes: usize,
+    /// Byte budget for all log buffers together (`LOG_MEM_BUDGET_MB`), 0 for none.
+    pub log_mem_budget: usize,
+    /// Prune and reset ask for a typed word after `y` (`TYPED_CONFIRM`).
+    pub typed_confirm: bool,
+    /// Toast when a task fails out of view (`NOTIFY_TASK_FAILURES`, on by default).
+    pub notify_task_failures: bool,
+    /// Start with mouse capture on (`MOUSE_CAPTURE`); `Ctrl+T` flips it.
+    pub mouse_capture: bool,
     pub refresh_ms: u64,
+    /// `REFRESH_MODE` (default `events`): refresh the containers on
+    /// `docker events` instead of every `refresh_ms`, with a safety poll every
+    /// `events_poll_secs` (`EVENTS_POLL_SECS`). `poll` opts out.
+    pub refresh_on_events: bool,
+    pub events_poll_secs: u64,
+
+    /// Shown in front of the sidebar title: `APP_TITLE`, else the compose
+    /// project name, else the project directory name.
+    pub title: String,
+
+    /// `F1`..`F12` open these `(container or service, port)` pairs in the browser.
+    pub quick_open: Vec<(String, u16)>,
+
+    /// Ports tried first by open-in-browser; empty means built-in defaults.
+    pub open_preferred_ports: Vec<u16>,
+
+    /// `PROFILE_COLORS` as `(profile, colour)`; `*` matches any profile.
+    pub profile_colors: Vec<(String, String)>,
+
+    /// What Enter does on each kind of sidebar item.
+    pub default_action: DefaultActions,
+
+    /// Rewrite BuildKit / pull progress lines in place when streaming
+    /// compose commands, instead of appending every update.
+    pub collapse_progress: bool,
+
+    /// Confirm popups left unanswered this long take their safe choice
+    /// (cancel / keep). `None` waits forever.
+    pub confirm_timeout: Option<std::time::Duration>,
+
+    /// Fixed width of the name column in the sidebar (`NAME_WIDTH`); `None`
+    /// sizes it from the pane width and the longest name.
+    pub name_width: Option<usize>,
+
+    /// Start with the container age column (`2h ago`) visible; `a` toggles it.
+    pub show_age: bool,
+
+    /// CPU/memory of every running container in the sidebar (`LIST_STATS`,
+    /// on by default).
+    pub list_stats: bool,
+
+    /// What the terminal can display, from `NO_COLOR`, `COLORTERM` and `TERM`.
+    pub color_support: ColorSupport,
+
+    /// Shells `e` tries in a container, in order (`EXEC_SHELL`, default `sh`).
+    pub exec_shell: Vec<String>,
+    /// `EXEC_MODE=pane`: `e` opens the built-in split-pane shell instead of
+    /// handing the terminal to `docker exec -it`.
+    pub exec_in_pane: bool,
+}
+
+/// Colours the terminal can show. The UI is styled for true colour and
+/// every frame is brought down to this before it is flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// `NO_COLOR` is set or `TERM=dumb`: no colours, highlights are reversed.
+    None,
+    /// The 16 ANSI colours.
+    Basic,
+    /// The xterm 256-colour palette (`TERM=*-256color`).
+    Ansi256,
+    /// 24-bit colour (`COLORTERM=truecolor`/`24bit`).
+    TrueColor,
+}
+
+impl ColorSupport {
+    pub fn detect() -> Self {
+        // https://no-color.org: any non-empty value disables colours.
+        if std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty()) {
+            return Self::None;
+        }
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return Self::TrueColor;
+        }
+        let term = std::env::var("TERM").unwrap_or_default().to_lowercase();
+        if term == "dumb" {
+            Self::None
+        } else if term.contains("256color") || term.contains("truecolor") || term.contains("direct") {
+            Self::Ansi256
+        } else {
+            Self::Basic
+        }
+    }
+}
+
+/// Action bound to Enter. `Logs` (just select) keeps the historical behaviour.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EnterAction {
+    #[default]
+    Logs,
+    Shell,
+    Inspect,
+    Restart,
+    Open,
+}
+
+impl EnterAction {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "logs" => Some(Self::Logs),
+            "shell" => Some(Self::Shell),
+            "inspect" => Some(Self::Inspect),
+            "restart" | "run" => Some(Self::Restart),
+            "open" => Some(Self::Open),
+            _ => None,
+        }
+    }
+
+    /// Action key run for this choice; `None` means plain selection.
+    pub fn key(self) -> Option<char> {
+        match self {
+            Self::Logs => None,
+            Self::Shell => Some('e'),
+            Self::Inspect => Some('i'),
+            Self::Restart => Some('r'),
+            Self::Open => Some('o'),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultActions {
+    pub container: EnterAction,
+    pub service: EnterAction,
+    pub task: EnterAction,
+}
+
+/// Parse `DEFAULT_ACTION`: either one action for every kind (`shell`) or
+/// per-kind pairs (`container=shell, service=inspect, task=run`).
+/// Unknown kinds or actions are ignored.
+pub fn parse_default_actions(raw: &str) -> DefaultActions {
+    let mut actions = DefaultActions::default();
+    for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part.split_once('=') {
+            Some((kind, action)) => {
+                let Some(action) = EnterAction::parse(action) else { continue };
+                match kind.trim().to_lowercase().as_str() {
+                    "container" => actions.container = action,
+                    "service" => actions.service = action,
+                    "task" => actions.task = action,
+                    _ => {}
+                }
+            }
+            None => {
+                if let Some(action) = EnterAction::parse(part) {
+                    actions = DefaultActions { container: action, service: action, task: action };
+                }
+            }
+        }
+    }
+    actions
+}
+
+/// `DOCKER_BIN` for `profile` (`DOCKER_BIN_<PROFILE>` first). The value may
+/// include arguments, e.g. `docker --context staging`.
+pub fn resolve_docker_binary(profile: &str) -> String {
+    match get_profile_value("DOCKER_BIN", profile).trim() {
+        "" => "docker".to_string(),
+        bin => bin.to_string(),
+    }
+}
+
+/// Resolve `bin` against `PATH` the way the shell would. Paths containing a
+/// separator are returned as-is when they exist.
+pub fn locate_binary(bin: &str) -> Option<PathBuf> {
+    let direct = Path::new(bin);
+    if direct.components().count() > 1 {
+        return direct.is_file().then(|| direct.to_path_buf());
+    }
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(bin))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Profile to use when neither DOCKER_PROFILE nor COMPOSE_PROFILE is set:
+/// `local` when the compose file declares it (or declares no profiles at all),
+/// otherwise the first declared profile.
+pub fn default_profile(available: &[String]) -> String {
+    if available.is_empty() || available.iter().any(|p| p == "local") {
+        "local".to_string()
+    } else {
+        available[0].clone()
+    }
+}
+
+/// Profiles offered at startup with where each was found: those the compose
+/// file declares, then those named by `.env.<profile>` and
+/// `docker-compose.<profile>.yml` files in `root`.
+pub fn discover_profiles(root: &Path, declared: &[String]) -> Vec<(String, Vec<String>)> {
+    let mut found: Vec<(String, Vec<String>)> = declared.iter()
+        .map(|p| (p.clone(), vec!["compose profile".to_string()]))
+        .collect();
+    let mut names: Vec<String> = fs::read_dir(root)
+        .map(|rd| rd.flatten().filter_map(|e| e.file_name().into_string().ok()).collect())
+        .unwrap_or_default();
+    names.sort();
+    for name in names {
+        let Some(profile) = crate::env::profile_from_file_name(&name) else { continue };
+        match found.iter_mut().find(|(p, _)| p == profile) {
+            Some((_, sources)) => sources.push(name.clone()),
+            None => found.push((profile.to_string(), vec![name.clone()])),
+        }
+    }
+    found
+}
+
+/// Parse a comma-separated port list such as `4200, 8080`. Invalid entries are skipped.
+pub fn parse_port_list(raw: &str) -> Vec<u16> {
+    raw.split(',')
+        .filter_map(|p| p.trim().parse::<u16>().ok())
+        .collect()
+}
+
+const COMPOSE_FILE_NAMES: [&str; 4] = ["docker-compose.yml", "docker-compose.yaml", "compose.yml", "compose.yaml"];
+
+pub fn has_compose_file(dir: &Path) -> bool {
+    COMPOSE_FILE_NAMES.iter().any(|f| dir.join(f).exists())
+}
+
+/// `QUICK_OPEN` entries, `name:port` separated by commas, spaces or
+/// newlines, in F-key order. Malformed entries are skipped.
+pub fn parse_quick_open(raw: &str) -> Vec<(String, u16)> {
+    raw.split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|entry| {
+            let (name, port) = entry.trim().rsplit_once(':')?;
+            let port = port.parse::<u16>().ok()?;
+            (!name.is_empty()).then(|| (name.to_string(), port))
+        })
+        .take(12)
+        .collect()
+}
+
+/// `PROFILE_COLORS` entries, `profile:colour` separated by commas, e.g.
+/// `local:green, staging:yellow, *:red`. Malformed entries are skipped.
+pub fn parse_profile_colors(raw: &str) -> Vec<(String, String)> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (profile, color) = entry.trim().split_once(':')?;
+            let (profile, color) = (profile.trim(), color.trim());
+            (!profile.is_empty() && !color.is_empty()).then(|| (profile.to_lowercase(), color.to_string()))
+        })
+        .collect()
 }
 
-pub fn resolve_docker_binary() -> String {
-    std::env::var("DOCKER_BIN").unwrap_or_else(|_| "docker".to_string())
+/// Split `COMPOSE_FILES` like compose's own `COMPOSE_FILE`: entries are
+/// separated by the platform path separator (`:`, `;` on Windows), and commas
+/// are accepted too.
+pub fn split_compose_files(raw: &str) -> Vec<String> {
+    let path_sep = if cfg!(windows) { ';' } else { ':' };
+    raw.split([',', path_sep])
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
+/// Project root to operate on. `COMPOSE_ROOT` (relative to `start_dir` or
+/// absolute) wins; otherwise the nearest ancestor with a compose file.
 pub fn find_project_root(start_dir: &Path) -> PathBuf {
-    // Walk up until we find docker-compose.yml (preferred). If we only find package.json,
-    // keep it as fallback but continue searching for docker-compose.yml.
+    match std::env::var("COMPOSE_ROOT") {
+        Ok(root) if !root.trim().is_empty() => start_dir.join(root.trim()),
+        _ => walk_up_project_root(start_dir),
+    }
+}
+
+/// Compose projects reachable from `start_dir`: the ancestor root, then any
+/// direct subdirectory with its own compose file (monorepos). More than one
+/// entry means the choice is ambiguous.
+pub fn compose_root_candidates(start_dir: &Path) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    let base = walk_up_project_root(start_dir);
+    if has_compose_file(&base) {
+        roots.push(base);
+    }
+    let mut subdirs: Vec<PathBuf> = fs::read_dir(start_dir)
+        .map(|rd| rd.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    subdirs.sort();
+    for dir in subdirs {
+        let hidden = dir.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'));
+        if dir.is_dir() && !hidden && has_compose_file(&dir) && !roots.contains(&dir) {
+            roots.push(dir);
+        }
+    }
+    roots
+}
+
+fn walk_up_project_root(start_dir: &Path) -> PathBuf {
+    // Walk up until we find a compose file (preferred). If we only find package.json,
+    // keep it as fallback but continue searching for a compose file.
     let mut dir = start_dir.to_path_buf();
     let mut fallback: Option<PathBuf> = None;
 
     for _ in 0..12 {
-        let compose = dir.join("docker-compose.yml");
         let pkg = dir.join("package.json");
 
-        if compose.exists() {
+        if has_compose_file(&dir) {
             return dir;
         }
         if pkg.exists() && fallback.is_none() {
@@ -87,6 +427,9 @@ fn load_tasks_from_package_json(root: &Path) -> Vec<TaskSpec> {
                 tasks.push(TaskSpec {
                     name: name.clone(),
                     cmd: cmd_str.to_string(),
+                    every: None,
+                    plain: false,
+                    service: None,
                 });
             }
         }
@@ -103,74 +446,263 @@ pub fn get_config(profile: &str) -> Config {
         profile.trim().to_string()
     };
 
-    let tasks_raw = get_profile_value("POST_UP_TASKS", &prof);
-    let tasks = parse_post_up_tasks(&tasks_raw);
+    let cwd = find_project_root(&std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+    // Task sources, first definition of a name wins:
+    // 1. the tasks file (POST_UP_TASKS_FILE, else .docker-cli-tasks)
+    let tasks_file = match get_profile_value("POST_UP_TASKS_FILE", &prof).trim() {
+        "" => cwd.join(DEFAULT_TASKS_FILE),
+        path => cwd.join(path),
+    };
+    let mut post_up_tasks = read_file_if_exists(&tasks_file)
+        .map(|raw| parse_post_up_tasks(&raw))
+        .unwrap_or_default();
 
-    // Back-compat: old single command
+    // 2. POST_UP_TASKS, or the old single POST_UP_CMD
+    let mut env_tasks = parse_post_up_tasks(&get_profile_value("POST_UP_TASKS", &prof));
     let single = get_profile_value("POST_UP_CMD", &prof);
-    let mut post_up_tasks = if !tasks.is_empty() {
-        tasks
-    } else if !single.trim().is_empty() {
-        vec![TaskSpec {
+    if env_tasks.is_empty() && !single.trim().is_empty() {
+        env_tasks.push(TaskSpec {
             name: "postup".to_string(),
             cmd: single,
-        }]
-    } else {
-        vec![]
-    };
-
-    let cwd = find_project_root(&std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+            every: None,
+            plain: false,
+            service: None,
+        });
+    }
 
-    // Merge tasks from package.json
-    let pkg_tasks = load_tasks_from_package_json(&cwd);
-    // Avoid duplicates, prefer env/profile tasks
-    for pt in pkg_tasks {
-        if !post_up_tasks.iter().any(|t| t.name == pt.name) {
-            post_up_tasks.push(pt);
+    // 3. package.json scripts
+    for t in env_tasks.into_iter().chain(load_tasks_from_package_json(&cwd)) {
+        if !post_up_tasks.iter().any(|existing| existing.name == t.name) {
+            post_up_tasks.push(t);
         }
     }
+
+    let mut container_actions = parse_container_actions(&get_profile_value("CONTAINER_ACTIONS", &prof));
+    let mut keys = crate::keybindings::Keybindings::from_env(&prof);
+    keys.check_container_actions(&mut container_actions);
+
     // Sort combined tasks
     post_up_tasks.sort_by(|a, b| a.name.cmp(&b.name));
 
+    // PLAIN_TASKS also covers tasks that cannot carry `(plain)`, e.g. package.json scripts.
+    let plain_tasks = get_profile_value("PLAIN_TASKS", &prof);
+    let plain_tasks: Vec<&str> = plain_tasks.split(',').map(str::trim).filter(|n| !n.is_empty()).collect();
+    for t in &mut post_up_tasks {
+        t.plain |= plain_tasks.iter().any(|n| *n == "*" || *n == t.name);
+    }
+
     let max_log_lines = std::env::var("MAX_LOG_LINES")
         .ok()
         .and_then(|s| s.parse::<usize>().ok())
         .unwrap_or(1200);
 
+    let max_line_bytes = std::env::var("MAX_LINE_BYTES")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .unwrap_or(16 * 1024);
+
+    let max_log_bytes = std::env::var("MAX_LOG_BYTES")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .unwrap_or(4 * 1024 * 1024);
+
+    let log_mem_budget = std::env::var("LOG_MEM_BUDGET_MB")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .unwrap_or(64)
+        .saturating_mul(1024 * 1024);
+
     let refresh_ms = std::env::var("REFRESH_MS")
         .ok()
         .and_then(|s| s.parse::<u64>().ok())
         .unwrap_or(1000);
 
-    let auto_compose_up = cwd.join("docker-compose.yml").exists()
-        || cwd.join("compose.yml").exists()
-        || cwd.join("docker-compose.yaml").exists()
-        || cwd.join("compose.yaml").exists();
+    let refresh_on_events = !get_profile_value("REFRESH_MODE", &prof).trim().eq_ignore_ascii_case("poll");
+    let events_poll_secs = std::env::var("EVENTS_POLL_SECS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .filter(|s| *s > 0)
+        .unwrap_or(30);
+
+    let collapse_progress = !matches!(
+        std::env::var("COLLAPSE_PROGRESS").unwrap_or_default().trim().to_lowercase().as_str(),
+        "0" | "false" | "no" | "off"
+    );
+
+    let typed_confirm = matches!(
+        std::env::var("TYPED_CONFIRM").unwrap_or_default().trim().to_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    );
+
+    let notify_task_failures = !matches!(
+        std::env::var("NOTIFY_TASK_FAILURES").unwrap_or_default().trim().to_lowercase().as_str(),
+        "0" | "false" | "no" | "off"
+    );
+
+    let list_stats = !matches!(
+        get_profile_value("LIST_STATS", &prof).trim().to_lowercase().as_str(),
+        "0" | "false" | "no" | "off"
+    );
+
+    let exec_shell: Vec<String> = get_profile_value("EXEC_SHELL", &prof)
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let exec_in_pane = get_profile_value("EXEC_MODE", &prof).trim().eq_ignore_ascii_case("pane");
+
+    let mouse_capture = !matches!(
+        std::env::var("MOUSE_CAPTURE").unwrap_or_default().trim().to_lowercase().as_str(),
+        "0" | "false" | "no" | "off"
+    );
+
+    let name_width = std::env::var("NAME_WIDTH")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .filter(|&w| w > 0);
+
+    let show_age = matches!(
+        std::env::var("SHOW_AGE").unwrap_or_default().trim().to_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    );
+
+    let confirm_timeout = std::env::var("CONFIRM_TIMEOUT")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+        .map(std::time::Duration::from_secs);
+
+    let title = [get_profile_value("APP_TITLE", &prof), std::env::var("COMPOSE_PROJECT_NAME").unwrap_or_default()]
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .find(|t| !t.is_empty())
+        .or_else(|| cwd.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+
+    let quick_open = parse_quick_open(&get_profile_value("QUICK_OPEN", &prof));
+    let open_preferred_ports = parse_port_list(&get_profile_value("OPEN_PREFERRED_PORTS", &prof));
+    let profile_colors = parse_profile_colors(&std::env::var("PROFILE_COLORS").unwrap_or_default());
+    let default_action = parse_default_actions(&get_profile_value("DEFAULT_ACTION", &prof));
+
+    let mut compose_files: Vec<PathBuf> = split_compose_files(&get_profile_value("COMPOSE_FILES", &prof))
+        .into_iter()
+        .map(|f| cwd.join(f))
+        .collect();
+    if compose_files.is_empty() {
+        compose_files = profile_compose_files(&cwd, &prof);
+    }
+    let auto_compose_up = has_compose_file(&cwd) || compose_files.iter().any(|f| f.exists());
+    let compose_prompt = !matches!(
+        get_profile_value("AUTO_COMPOSE_UP", &prof).trim().to_lowercase().as_str(),
+        "0" | "false" | "no" | "off"
+    );
 
     Config {
         cwd,
         profile: prof.clone(),
-        docker_bin: resolve_docker_binary(),
+        docker_bin: resolve_docker_binary(&prof),
         auto_compose_up,
+        compose_prompt,
         compose_profile: prof,
+        compose_files,
         db_container: std::env::var("DB_CONTAINER").unwrap_or_else(|_| "supabase-db".to_string()),
         storage_container: std::env::var("STORAGE_CONTAINER")
             .unwrap_or_else(|_| "supabase-storage".to_string()),
         post_up_tasks,
+        tasks_file,
+        container_actions,
+        keys,
         max_log_lines,
+        max_line_bytes,
+        max_log_bytes,
+        log_mem_budget,
+        typed_confirm,
+        notify_task_failures,
+        mouse_capture,
         refresh_ms,
+        refresh_on_events,
+        color_support: ColorSupport::detect(),
+        list_stats,
+        exec_shell,
+        exec_in_pane,
+        events_poll_secs,
+        title,
+        quick_open,
+        open_preferred_ports,
+        profile_colors,
+        default_action,
+        collapse_progress,
+        confirm_timeout,
+        show_age,
+        name_width,
     }
 }
 
-#[allow(dead_code)]
-fn _compose_files_for_profile(cwd: &Path, profile: &str) -> Vec<PathBuf> {
-    // Kept for parity with JS code; the current implementation does not pass -f flags.
-    let mut files = vec![cwd.join("docker-compose.yml")];
+/// The `local` profile with built-in defaults rooted at `cwd`, without
+/// reading the environment or any file, for tests.
+#[cfg(test)]
+pub fn offline_config(cwd: PathBuf) -> Config {
+    Config {
+        tasks_file: cwd.join(DEFAULT_TASKS_FILE),
+        cwd,
+        profile: "local".to_string(),
+        docker_bin: "docker".to_string(),
+        auto_compose_up: false,
+        compose_prompt: false,
+        compose_profile: "local".to_string(),
+        compose_files: Vec::new(),
+        db_container: String::new(),
+        storage_container: String::new(),
+        post_up_tasks: Vec::new(),
+        container_actions: Vec::new(),
+        keys: Default::default(),
+        max_log_lines: 1200,
+        max_line_bytes: 16 * 1024,
+        max_log_bytes: 4 * 1024 * 1024,
+        log_mem_budget: 64 * 1024 * 1024,
+        typed_confirm: false,
+        notify_task_failures: true,
+        mouse_capture: true,
+        refresh_ms: 1000,
+        refresh_on_events: false,
+        color_support: ColorSupport::TrueColor,
+        list_stats: true,
+        exec_shell: vec!["sh".to_string()],
+        exec_in_pane: false,
+        events_poll_secs: 30,
+        title: String::new(),
+        quick_open: Vec::new(),
+        open_preferred_ports: Vec::new(),
+        profile_colors: Vec::new(),
+        default_action: Default::default(),
+        collapse_progress: true,
+        confirm_timeout: None,
+        show_age: false,
+        name_width: None,
+    }
+}
+
+/// Compose files for a profile with its own `docker/<profile>/docker-compose.yml`:
+/// the base file, its override file if any (`-f` turns off compose's own
+/// lookup of it), then the profile file, so later files win. Empty when the
+/// profile has no file of its own, leaving compose to find its files.
+fn profile_compose_files(cwd: &Path, profile: &str) -> Vec<PathBuf> {
     let prof = if profile.trim().is_empty() { "local" } else { profile.trim() };
-    let candidate = PathBuf::from("docker").join(prof).join("docker-compose.yml");
-    if cwd.join(&candidate).exists() {
-        files.push(cwd.join(candidate));
+    let profile_file = cwd.join("docker").join(prof).join("docker-compose.yml");
+    if !profile_file.exists() {
+        return Vec::new();
+    }
+    let mut files = Vec::new();
+    if let Some(base) = COMPOSE_FILE_NAMES.iter().find(|f| cwd.join(f).exists()) {
+        files.push(cwd.join(base));
+        let (stem, ext) = base.rsplit_once('.').expect("compose file names have an extension");
+        let override_file = cwd.join(format!("{stem}.override.{ext}"));
+        if override_file.exists() {
+            files.push(override_file);
+        }
     }
+    files.push(profile_file);
     files
 }
 
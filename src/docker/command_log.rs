@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::{ExitStatus, Output, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+
+const MAX_RECORDS: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct CommandRecord {
+    pub at: chrono::DateTime<chrono::Local>,
+    pub command: String,
+    /// `None` for long-running streams (logs, shells) that outlive the call.
+    pub exit_code: Option<i32>,
+    pub duration: Duration,
+}
+
+/// Ring buffer of the docker invocations this session has made.
+/// Cloning shares the buffer, so background refresh tasks record into the
+/// same history as the UI.
+#[derive(Debug, Clone, Default)]
+pub struct CommandHistory(Arc<Mutex<VecDeque<CommandRecord>>>);
+
+impl CommandHistory {
+    pub fn record(&self, command: String, exit_code: Option<i32>, duration: Duration) {
+        if let Ok(mut h) = self.0.lock() {
+            h.push_back(CommandRecord {
+                at: chrono::Local::now(),
+                command,
+                exit_code,
+                duration,
+            });
+            while h.len() > MAX_RECORDS {
+                h.pop_front();
+            }
+        }
+    }
+
+    /// Record a call made through the Engine API, shown as its CLI equivalent.
+    pub fn record_api<T, E>(&self, equivalent: &str, started: Instant, res: &Result<T, E>) {
+        let code = if res.is_ok() { 0 } else { 1 };
+        self.record(format!("(api) {equivalent}"), Some(code), started.elapsed());
+    }
+
+    pub fn snapshot(&self) -> Vec<CommandRecord> {
+        self.0.lock().map(|h| h.iter().cloned().collect()).unwrap_or_default()
+    }
+}
+
+/// Thin wrapper around `tokio::process::Command` that records every
+/// invocation into a [`CommandHistory`].
+pub struct DockerCommand {
+    inner: Command,
+    display: Vec<String>,
+    history: CommandHistory,
+}
+
+impl DockerCommand {
+    /// `bin` may carry leading arguments (`docker --context staging`, a
+    /// wrapper script and its flags); they are split off the way
+    /// [`super::split_args`] splits a typed command.
+    pub fn new(bin: &str, history: CommandHistory) -> Self {
+        let mut words = super::split_args(bin);
+        if words.is_empty() {
+            words.push(bin.to_string());
+        }
+        let mut inner = Command::new(&words[0]);
+        inner.args(&words[1..]);
+        Self { inner, display: words, history }
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.display.push(arg.as_ref().to_string_lossy().to_string());
+        self.inner.arg(arg);
+        self
+    }
+
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        for a in args {
+            self.arg(a);
+        }
+        self
+    }
+
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.inner.current_dir(dir);
+        self
+    }
+
+    pub fn envs<I, K, V>(&mut self, vars: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.inner.envs(vars);
+        self
+    }
+
+    pub fn stdin<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.inner.stdin(cfg);
+        self
+    }
+
+    pub fn stdout<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.inner.stdout(cfg);
+        self
+    }
+
+    pub fn stderr<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.inner.stderr(cfg);
+        self
+    }
+
+    pub fn display(&self) -> String {
+        self.display.join(" ")
+    }
+
+    pub async fn status(&mut self) -> std::io::Result<ExitStatus> {
+        let started = Instant::now();
+        let res = self.inner.status().await;
+        let code = res.as_ref().ok().and_then(|s| s.code());
+        self.history.record(self.display(), code, started.elapsed());
+        res
+    }
+
+    pub async fn output(&mut self) -> std::io::Result<Output> {
+        let started = Instant::now();
+        let res = self.inner.output().await;
+        let code = res.as_ref().ok().and_then(|o| o.status.code());
+        self.history.record(self.display(), code, started.elapsed());
+        res
+    }
+
+    pub fn spawn(&mut self) -> std::io::Result<Child> {
+        self.history.record(self.display(), None, Duration::ZERO);
+        self.inner.spawn()
+    }
+}
@@ -1,16 +1,18 @@
 use anyhow::Result;
 use std::path::Path;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
 use std::process::Stdio;
 
-use crate::docker::{DockerMeta, LogStream};
+use crate::docker::{compose_out, DockerCommand, DockerMeta, LogStream};
 
+/// Run `docker compose --profile <p> <args>` attached to the terminal and
+/// return its exit code; the caller must have released the TUI first.
+/// [`compose_raw`] streams the output instead.
 pub async fn docker_compose(meta: &DockerMeta, cfg_cwd: &Path, profile: &str, args: &[&str]) -> Result<i32> {
-    let mut full: Vec<&str> = vec!["compose", "--profile", profile];
+    let mut full: Vec<&str> = vec!["--profile", profile];
     full.extend_from_slice(args);
 
-    let status = Command::new(&meta.docker_bin)
+    let status = meta.compose_command()
         .current_dir(cfg_cwd)
         .args(full)
         .envs(std::env::vars())
@@ -19,50 +21,126 @@ pub async fn docker_compose(meta: &DockerMeta, cfg_cwd: &Path, profile: &str, ar
     Ok(status.code().unwrap_or(if status.success() { 0 } else { 1 }))
 }
 
+/// Profiles declared in the project's compose file(s), as reported by
+/// `docker compose config --profiles`. Empty when there is no compose file.
+pub async fn list_compose_profiles(meta: &DockerMeta, cwd: &Path) -> Vec<String> {
+    compose_out(meta, cwd, &["config", "--profiles"])
+        .await
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
 pub async fn compose_group_restart(meta: &DockerMeta, cwd: &Path, project: &str) -> Result<Vec<String>> {
-    let output = Command::new(&meta.docker_bin)
-        .current_dir(cwd)
-        .args(["compose", "-p", project, "restart"])
-        .output()
-        .await?;
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    let mut lines: Vec<String> = stdout.lines().map(|l| l.to_string()).collect();
-    lines.extend(stderr.lines().map(|l| l.to_string()));
-    Ok(lines)
+    let mut cmd = meta.compose_command();
+    cmd.current_dir(cwd).args(["-p", project, "restart"]);
+    compose_capture(cmd).await
 }
 
 pub async fn compose_group_up(meta: &DockerMeta, cwd: &Path, project: &str) -> Result<Vec<String>> {
-    let output = Command::new(&meta.docker_bin)
-        .current_dir(cwd)
-        .args(["compose", "-p", project, "up", "-d"])
-        .output()
-        .await?;
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    let mut lines: Vec<String> = stdout.lines().map(|l| l.to_string()).collect();
-    lines.extend(stderr.lines().map(|l| l.to_string()));
-    Ok(lines)
+    let mut cmd = meta.compose_command();
+    cmd.current_dir(cwd).args(["-p", project, "up", "-d"]);
+    compose_capture(cmd).await
 }
 
-pub async fn compose_group_down(meta: &DockerMeta, cwd: &Path, project: &str) -> Result<Vec<String>> {
-    let output = Command::new(&meta.docker_bin)
-        .current_dir(cwd)
-        .args(["compose", "-p", project, "down"])
-        .output()
-        .await?;
+/// `docker compose -p <project> up -d <service>`: brings one service back,
+/// e.g. right after a reset removed its container and volumes.
+pub async fn compose_service_up(meta: &DockerMeta, cwd: &Path, project: &str, service: &str) -> Result<Vec<String>> {
+    let mut cmd = meta.compose_command();
+    cmd.current_dir(cwd).args(["-p", project, "up", "-d", service]);
+    compose_capture(cmd).await
+}
+
+/// Run a compose command to completion: stdout then stderr, line by line.
+/// Fails with the exit code and the last line when the command fails.
+async fn compose_capture(mut cmd: DockerCommand) -> Result<Vec<String>> {
+    let output = cmd.output().await?;
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
     let mut lines: Vec<String> = stdout.lines().map(|l| l.to_string()).collect();
     lines.extend(stderr.lines().map(|l| l.to_string()));
+    if !output.status.success() {
+        anyhow::bail!("exit {}: {}", output.status.code().unwrap_or(1), lines.last().cloned().unwrap_or_default());
+    }
     Ok(lines)
 }
 
+/// Project name and services enabled for `profile`, from
+/// `docker compose --profile <p> config --format json`.
+pub async fn list_compose_services(meta: &DockerMeta, cwd: &Path, profile: &str) -> Result<(String, Vec<String>)> {
+    let out = compose_out(meta, cwd, &["--profile", profile, "config", "--format", "json"]).await?;
+    let v: serde_json::Value = serde_json::from_str(&out)?;
+    let name = v["name"].as_str().unwrap_or_default().to_string();
+    let mut services: Vec<String> = v["services"]
+        .as_object()
+        .map(|m| m.keys().cloned().collect())
+        .unwrap_or_default();
+    services.sort();
+    Ok((name, services))
+}
+
+/// One service as compose sees it. `name` is empty when the service is
+/// defined but has no container (never created, or removed by `down`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposePsRow {
+    pub service: String,
+    pub name: String,
+    pub state: String,
+    /// `healthy`, `unhealthy`, `starting`, or empty without a healthcheck.
+    pub health: String,
+    pub status: String,
+}
+
+/// Parse `docker compose ps --format json`: a JSON array with older compose
+/// releases, one object per line with newer ones.
+pub fn parse_compose_ps(out: &str) -> Vec<ComposePsRow> {
+    let values: Vec<serde_json::Value> = match serde_json::from_str::<serde_json::Value>(out.trim()) {
+        Ok(serde_json::Value::Array(items)) => items,
+        _ => out.lines().filter_map(|l| serde_json::from_str(l.trim()).ok()).collect(),
+    };
+    let text = |v: &serde_json::Value, key: &str| v[key].as_str().unwrap_or_default().to_string();
+    values
+        .iter()
+        .filter(|v| v.is_object())
+        .map(|v| ComposePsRow {
+            service: text(v, "Service"),
+            name: text(v, "Name"),
+            state: text(v, "State"),
+            health: text(v, "Health"),
+            status: text(v, "Status"),
+        })
+        .collect()
+}
+
+/// Compose's view of the profile: every container of the project (stopped
+/// ones included), plus a row with no container for each defined service
+/// that has none, which `docker ps` cannot show.
+pub async fn compose_ps(meta: &DockerMeta, cwd: &Path, profile: &str) -> Result<Vec<ComposePsRow>> {
+    let out = compose_out(meta, cwd, &["--profile", profile, "ps", "--all", "--format", "json"]).await?;
+    let mut rows = parse_compose_ps(&out);
+    let (_, services) = list_compose_services(meta, cwd, profile).await?;
+    for service in services {
+        if !rows.iter().any(|r| r.service == service) {
+            rows.push(ComposePsRow { service, name: String::new(), state: String::new(), health: String::new(), status: String::new() });
+        }
+    }
+    rows.sort_by(|a, b| a.service.cmp(&b.service).then_with(|| a.name.cmp(&b.name)));
+    Ok(rows)
+}
+
+/// `docker compose --profile <p> up -d <services…>`, output captured.
+pub async fn compose_up_services(meta: &DockerMeta, cwd: &Path, profile: &str, services: &[String]) -> Result<Vec<String>> {
+    let mut cmd = meta.compose_command();
+    cmd.current_dir(cwd).args(["--profile", profile, "up", "-d"]).args(services).envs(std::env::vars());
+    compose_capture(cmd).await
+}
 
 pub fn spawn_compose_logs(meta: &DockerMeta, cwd: &Path, project: &str, tail: usize) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
-    let mut child = Command::new(&meta.docker_bin)
+    let mut child = meta.compose_command()
         .current_dir(cwd)
-        .args(["compose", "-p", project, "logs", "-f", "--tail", &tail.to_string()])
+        .args(["-p", project, "logs", "-f", "--tail", &tail.to_string()])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()?;
@@ -87,3 +165,358 @@ pub fn spawn_compose_logs(meta: &DockerMeta, cwd: &Path, project: &str, tail: us
 
     Ok((LogStream::Child(child), rx))
 }
+
+/// Follow `docker compose logs` for one service, which interleaves all of its
+/// replicas. Lines are sent newline-terminated.
+pub fn spawn_service_logs_follow(meta: &DockerMeta, cwd: &Path, profile: &str, project: &str, service: &str, tail: usize) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    follow_compose_logs(meta, cwd, &["-p", project, "--profile", profile, "logs", "-f", "--tail", &tail.to_string(), service])
+}
+
+/// Follow `docker compose logs` for every service of the profile, each line
+/// prefixed with its container name.
+pub fn spawn_stack_logs_follow(meta: &DockerMeta, cwd: &Path, profile: &str, tail: usize) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    follow_compose_logs(meta, cwd, &["--profile", profile, "logs", "-f", "--tail", &tail.to_string()])
+}
+
+fn follow_compose_logs(meta: &DockerMeta, cwd: &Path, args: &[&str]) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    let mut child = meta.compose_command()
+        .current_dir(cwd)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let stdout = child.stdout.take().unwrap();
+    let stderr = child.stderr.take().unwrap();
+
+    let tx_out = tx.clone();
+    tokio::spawn(async move {
+        let mut r = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = r.next_line().await {
+            let _ = tx_out.send(format!("{line}\n"));
+        }
+    });
+    tokio::spawn(async move {
+        let mut r = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = r.next_line().await {
+            let _ = tx.send(format!("[ERR] {line}\n"));
+        }
+    });
+
+    Ok((LogStream::Child(child), rx))
+}
+
+/// Split a typed command line into arguments, honouring single and double
+/// quotes and backslash escapes (`exec db psql -c "select 1"`).
+pub fn split_args(raw: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut cur = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(n) = chars.next() {
+                    cur.push(n);
+                }
+                in_arg = true;
+            }
+            (Some(_), c) => cur.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut cur));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                cur.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(cur);
+    }
+    args
+}
+
+/// Whether `docker compose <args>` wants the terminal: `exec`/`run`/`attach`
+/// allocate a TTY unless `-T` is given, and explicit `-it`/`--tty` do too.
+/// (A lone `-t` is left out: for `logs` it means timestamps.)
+pub fn compose_is_interactive(args: &[String]) -> bool {
+    let tty_flag = args.iter().any(|a| matches!(a.as_str(), "-it" | "-ti" | "--interactive" | "--tty"));
+    let no_tty = args.iter().any(|a| a == "-T" || a == "--no-TTY");
+    let tty_cmd = matches!(args.first().map(String::as_str), Some("exec" | "run" | "attach"));
+    tty_flag || (tty_cmd && !no_tty)
+}
+
+/// `docker compose --profile <p> stop`, streamed: halts the whole stack but
+/// keeps its containers, networks and volumes, unlike `down`.
+pub fn compose_stop_all(meta: &DockerMeta, cwd: &Path, profile: &str) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    compose_raw(meta, cwd, profile, &["stop".to_string()])
+}
+
+/// `docker compose --profile <p> start`, streamed: resumes the containers a
+/// [`compose_stop_all`] left behind without recreating them.
+pub fn compose_start_all(meta: &DockerMeta, cwd: &Path, profile: &str) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    compose_raw(meta, cwd, profile, &["start".to_string()])
+}
+
+/// `docker compose --profile <p> restart <service>`, streamed: restarts one
+/// service instead of everything the profile enables.
+pub fn compose_restart_service(meta: &DockerMeta, cwd: &Path, profile: &str, service: &str) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    compose_raw(meta, cwd, profile, &["restart".to_string(), service.to_string()])
+}
+
+/// Run `docker compose --profile <p> <args>` in the background and stream its
+/// output. Interactive subcommands go through [`docker_compose`].
+pub fn compose_raw(meta: &DockerMeta, cwd: &Path, profile: &str, args: &[String]) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    let mut child = meta.compose_command()
+        .current_dir(cwd)
+        .args(["--profile", profile])
+        .args(args)
+        .envs(std::env::vars())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let stdout = child.stdout.take().unwrap();
+    let stderr = child.stderr.take().unwrap();
+
+    let tx_err = tx.clone();
+    let errors = tokio::spawn(async move {
+        let mut r = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = r.next_line().await {
+            let _ = tx_err.send(format!("{line}\n"));
+        }
+    });
+    let waiter = tokio::spawn(async move {
+        let mut r = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = r.next_line().await {
+            let _ = tx.send(format!("{line}\n"));
+        }
+        let code = child.wait().await.ok().and_then(|s| s.code());
+        // Compose reports most progress on stderr: let it drain so the
+        // verdict is the last line.
+        let _ = errors.await;
+        let _ = tx.send(match code {
+            Some(0) => "==> OK\n".to_string(),
+            Some(c) => format!("==> FAIL (exit {c})\n"),
+            None => "==> terminated\n".to_string(),
+        });
+    });
+
+    Ok((LogStream::Task(waiter), rx))
+}
+
+/// Container prefix of a `docker compose logs` line (`web-1  | listening`,
+/// optionally after an `[ERR] ` tag): the name and the byte offset just past
+/// the `|`. `None` for lines without one.
+pub fn log_prefix(line: &str) -> Option<(&str, usize)> {
+    let start = if line.starts_with("[ERR] ") { 6 } else { 0 };
+    let bar = start + line[start..].find('|')?;
+    let name = line[start..bar].trim();
+    let well_formed = !name.is_empty()
+        && name.len() <= 64
+        && !name.contains(char::is_whitespace)
+        && matches!(line[bar + 1..].chars().next(), None | Some(' '));
+    well_formed.then_some((name, bar + 1))
+}
+
+/// Identity of a progress line that later lines with the same key supersede:
+/// BuildKit plain output (`#7 sha256:<digest> 1.2MB / 30MB 0.4s`,
+/// `#7 extracting sha256:<digest> 0.5s`) and compose pull layers
+/// (`a2abf6c4d29d Downloading [==>  ] 1.2MB/31MB`). `None` for regular output.
+pub fn progress_key(line: &str) -> Option<String> {
+    let mut parts = line.split_whitespace();
+    let first = parts.next()?;
+    if let Some(step) = first.strip_prefix('#').filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())) {
+        return match parts.next()? {
+            "extracting" => parts.next().filter(|d| d.starts_with("sha256:")).map(|d| format!("#{step} extracting {d}")),
+            digest if digest.starts_with("sha256:") => Some(format!("#{step} {digest}")),
+            _ => None,
+        };
+    }
+    let layer = first.len() == 12 && first.bytes().all(|b| b.is_ascii_hexdigit());
+    (layer && parts.next().is_some()).then(|| first.to_string())
+}
+
+/// Render one `docker events --format {{json .}}` line as
+/// `HH:MM:SS  container  start  web-1`.
+pub fn format_event(line: &str) -> Option<String> {
+    let v: serde_json::Value = serde_json::from_str(line).ok()?;
+    let kind = v.get("Type").and_then(|x| x.as_str()).unwrap_or("?");
+    let action = v.get("Action").and_then(|x| x.as_str()).unwrap_or("?");
+    let attrs = v.get("Actor").and_then(|a| a.get("Attributes"));
+    let name = attrs
+        .and_then(|a| a.get("name"))
+        .and_then(|x| x.as_str())
+        .unwrap_or_else(|| v.get("id").and_then(|x| x.as_str()).unwrap_or("-"));
+    let time = v
+        .get("time")
+        .and_then(|x| x.as_i64())
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".to_string());
+    Some(format!("{time}  {kind:<9}  {action:<20}  {name}"))
+}
+
+/// Follow daemon events for one compose project until the stream is killed.
+pub fn stream_events(meta: &DockerMeta, cwd: &Path, project: &str) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    let mut child = meta.command()
+        .current_dir(cwd)
+        .args([
+            "events",
+            "--format",
+            "{{json .}}",
+            "--filter",
+            &format!("label=com.docker.compose.project={project}"),
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let stdout = child.stdout.take().unwrap();
+    let stderr = child.stderr.take().unwrap();
+
+    let tx_out = tx.clone();
+    tokio::spawn(async move {
+        let mut r = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = r.next_line().await {
+            let text = format_event(&line).unwrap_or(line);
+            let _ = tx_out.send(format!("{text}\n"));
+        }
+    });
+    tokio::spawn(async move {
+        let mut r = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = r.next_line().await {
+            let _ = tx.send(format!("[ERR] {line}\n"));
+        }
+    });
+
+    Ok((LogStream::Child(child), rx))
+}
+
+/// Whether a `docker events --format {{json .}}` line changes what the
+/// sidebar shows (a container appearing, stopping, changing health…).
+pub fn is_refresh_event(line: &str) -> bool {
+    const ACTIONS: [&str; 10] = ["create", "start", "stop", "die", "destroy", "pause", "unpause", "rename", "health_status", "update"];
+    let Ok(v) = serde_json::from_str::<serde_json::Value>(line) else { return false };
+    let kind = v.get("Type").and_then(|x| x.as_str()).unwrap_or_default();
+    let action = v.get("Action").and_then(|x| x.as_str()).unwrap_or_default();
+    matches!(kind, "container" | "service") && ACTIONS.iter().any(|a| action.starts_with(a))
+}
+
+/// Follow container and service events that call for a sidebar refresh. Every
+/// relevant event sends its action; the channel closes when `docker events`
+/// exits (or cannot run), which means going back to polling.
+pub fn stream_refresh_events(meta: &DockerMeta, cwd: &Path) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
+    let mut child = meta.command()
+        .current_dir(cwd)
+        .args(["events", "--format", "{{json .}}", "--filter", "type=container", "--filter", "type=service"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let stdout = child.stdout.take().unwrap();
+    tokio::spawn(async move {
+        let mut r = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = r.next_line().await {
+            if is_refresh_event(&line) && tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok((LogStream::Child(child), rx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_refresh_event() {
+        assert!(is_refresh_event(r#"{"Type":"container","Action":"start","Actor":{"ID":"abc"}}"#));
+        assert!(is_refresh_event(r#"{"Type":"container","Action":"health_status: unhealthy"}"#));
+        assert!(is_refresh_event(r#"{"Type":"service","Action":"update"}"#));
+        assert!(is_refresh_event(r#"{"Type":"container","Action":"stop"}"#));
+        assert!(!is_refresh_event(r#"{"Type":"container","Action":"exec_start: sh"}"#));
+        assert!(!is_refresh_event(r#"{"Type":"network","Action":"connect"}"#));
+        assert!(!is_refresh_event("not json"));
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("exec db psql -U postgres"), ["exec", "db", "psql", "-U", "postgres"]);
+        assert_eq!(split_args(r#"exec db psql -c "select 1;"  "#), ["exec", "db", "psql", "-c", "select 1;"]);
+        assert_eq!(split_args(r#"run app sh -c 'echo $HOME' """#), ["run", "app", "sh", "-c", "echo $HOME", ""]);
+        assert_eq!(split_args(r"logs my\ svc"), ["logs", "my svc"]);
+        let args = |s: &str| split_args(s);
+        assert!(compose_is_interactive(&args("exec db psql")));
+        assert!(!compose_is_interactive(&args("exec -T db pg_dump")));
+        assert!(!compose_is_interactive(&args("ps -a")));
+        assert!(!compose_is_interactive(&args("logs -t db")));
+    }
+
+    #[test]
+    fn test_progress_key() {
+        let dl = "#7 sha256:9b1d2f 3.15MB / 29.13MB 0.6s";
+        assert_eq!(progress_key(dl).as_deref(), Some("#7 sha256:9b1d2f"));
+        assert_eq!(progress_key("#7 sha256:9b1d2f 29.13MB / 29.13MB 1.9s done"), progress_key(dl));
+        assert_eq!(progress_key("#7 extracting sha256:9b1d2f 0.5s").as_deref(), Some("#7 extracting sha256:9b1d2f"));
+        assert_eq!(progress_key(" a2abf6c4d29d Downloading [=>    ]  1.2MB/31.37MB").as_deref(), Some("a2abf6c4d29d"));
+        assert_eq!(progress_key("a2abf6c4d29d Pull complete"), progress_key("a2abf6c4d29d Waiting"));
+        assert!(progress_key("#7 [2/4] RUN apt-get update").is_none());
+        assert!(progress_key("#7 0.512 Reading package lists...").is_none());
+        assert!(progress_key("#7 DONE 1.9s").is_none());
+        assert!(progress_key(" web Pulling").is_none());
+        assert!(progress_key("").is_none());
+    }
+
+    #[test]
+    fn test_log_prefix() {
+        assert_eq!(log_prefix("web-1  | listening on :80"), Some(("web-1", 8)));
+        assert_eq!(log_prefix("db-1  |"), Some(("db-1", 7)));
+        assert_eq!(log_prefix("[ERR] worker-2  | boom"), Some(("worker-2", 17)));
+        assert_eq!(log_prefix("a | b | c").map(|(n, _)| n), Some("a"));
+        assert!(log_prefix("no prefix here").is_none());
+        assert!(log_prefix("two words | x").is_none());
+        assert!(log_prefix("cat x|grep y").is_none());
+    }
+
+    #[test]
+    fn test_format_event() {
+        let line = r#"{"Type":"container","Action":"health_status: healthy","Actor":{"ID":"abc","Attributes":{"name":"web-1"}},"time":0}"#;
+        let out = format_event(line).unwrap();
+        assert!(out.contains("container"));
+        assert!(out.contains("health_status: healthy"));
+        assert!(out.ends_with("web-1"));
+        assert!(format_event("not json").is_none());
+    }
+
+    #[test]
+    fn test_parse_compose_ps() {
+        let ndjson = r#"{"Name":"app-db-1","Service":"db","State":"running","Health":"healthy","Status":"Up 2 hours (healthy)"}
+{"Name":"app-web-1","Service":"web","State":"exited","Health":"","Status":"Exited (1) 3 minutes ago"}"#;
+        let rows = parse_compose_ps(ndjson);
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].service.as_str(), rows[0].health.as_str()), ("db", "healthy"));
+        assert_eq!((rows[1].name.as_str(), rows[1].state.as_str()), ("app-web-1", "exited"));
+
+        let array = r#"[{"Name":"app-db-1","Service":"db","State":"running","Health":"","Status":"Up"}]"#;
+        assert_eq!(parse_compose_ps(array).len(), 1);
+        assert!(parse_compose_ps("").is_empty());
+    }
+}
@@ -7,8 +7,7 @@ use futures_util::stream::StreamExt;
 use crate::docker::{DockerMeta, Port, LogStream};
 use bollard::query_parameters::{ListContainersOptions, StatsOptions, LogsOptions, StartContainerOptions, KillContainerOptions, RemoveContainerOptions, RemoveVolumeOptions};
 use bollard::exec::CreateExecOptions;
-use tokio::io::AsyncReadExt;
-use tokio::process::Command;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use std::process::Stdio;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +24,26 @@ pub struct ContainerSummary {
     pub ports: String,
     #[serde(skip)]
     pub compose_project: Option<String>,
+    #[serde(skip)]
+    pub compose_service: Option<String>,
+    /// `RestartCount` from inspect; filled by [`list_containers_all`].
+    #[serde(skip)]
+    pub restart_count: i64,
+    /// `HostConfig.RestartPolicy.Name` from inspect (`no`, `always`,
+    /// `unless-stopped`, `on-failure`); empty when unknown.
+    #[serde(skip)]
+    pub restart_policy: String,
+    /// `State.Health.Status` from inspect (`healthy`, `unhealthy`,
+    /// `starting`); empty without a healthcheck.
+    #[serde(skip)]
+    pub health: String,
+    /// Creation time as unix seconds, `None` when the daemon did not say.
+    #[serde(skip)]
+    pub created: Option<i64>,
+    /// Named volumes mounted in the container. The CLI listing may cut long
+    /// names with `…`.
+    #[serde(skip)]
+    pub mounts: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,18 +68,46 @@ impl ContainerStats {
             _       => '█',
         }
     }
+}
 
-    pub fn sidebar_label(&self) -> String {
-        let cpu_pct = self.cpu_percent.min(100.0);
-        let gauge: String = (0..4)
-            .map(|i| if cpu_pct >= (i as f64 + 1.0) * 25.0 { '█' } else { '░' })
-            .collect();
-        let mem = if self.mem_usage_mb >= 1024.0 {
-            format!("{:.1}G", self.mem_usage_mb / 1024.0)
-        } else {
-            format!("{:.0}M", self.mem_usage_mb)
-        };
-        format!(" {gauge} {cpu_pct:.0}% {mem}")
+/// Which stat columns the sidebar and the stats bar show; cycled with `M`
+/// because there is rarely room for all of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatsView {
+    #[default]
+    CpuMem,
+    Net,
+    Io,
+    All,
+}
+
+impl StatsView {
+    pub fn next(self) -> Self {
+        match self {
+            StatsView::CpuMem => StatsView::Net,
+            StatsView::Net => StatsView::Io,
+            StatsView::Io => StatsView::All,
+            StatsView::All => StatsView::CpuMem,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatsView::CpuMem => "CPU/Mem",
+            StatsView::Net => "Net I/O",
+            StatsView::Io => "Block I/O",
+            StatsView::All => "All",
+        }
+    }
+}
+
+fn short_mb(mb: f64) -> String {
+    if mb >= 1024.0 {
+        format!("{:.1}G", mb / 1024.0)
+    } else if mb >= 10.0 {
+        format!("{mb:.0}M")
+    } else {
+        format!("{mb:.1}M")
     }
 }
 
@@ -76,7 +123,7 @@ pub fn container_name(raw_names: &str) -> String {
 
 fn parse_size(s: &str) -> f64 {
     let s = s.to_lowercase();
-    let val = s.chars().take_while(|c| c.is_digit(10) || *c == '.').collect::<String>().parse::<f64>().unwrap_or(0.0);
+    let val = s.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect::<String>().parse::<f64>().unwrap_or(0.0);
     if s.contains("gib") || s.contains("gb") { val * 1024.0 }
     else if s.contains("mib") || s.contains("mb") { val }
     else if s.contains("kib") || s.contains("kb") { val / 1024.0 }
@@ -84,7 +131,14 @@ fn parse_size(s: &str) -> f64 {
     else { val }
 }
 
-pub async fn list_containers_all(meta: &DockerMeta, cwd: &Path) -> Result<Vec<(ContainerSummary, Vec<Port>)>> {
+/// List every container. `previous` is the last listing: containers whose
+/// `State` and `Status` did not change keep their inspected restart count
+/// and policy instead of being inspected again.
+pub async fn list_containers_all(
+    meta: &DockerMeta,
+    cwd: &Path,
+    previous: &[(ContainerSummary, Vec<Port>)],
+) -> Result<Vec<(ContainerSummary, Vec<Port>)>> {
     if let Some(client) = &meta.client {
         let options = Some(ListContainersOptions {
             all: true,
@@ -99,14 +153,19 @@ pub async fn list_containers_all(meta: &DockerMeta, cwd: &Path) -> Result<Vec<(C
             let status = c.status.unwrap_or_default();
             
             let compose_project = c.labels.as_mut().and_then(|l| l.remove("com.docker.compose.project"));
+            let compose_service = c.labels.as_mut().and_then(|l| l.remove("com.docker.compose.service"));
+            let mounts = c.mounts.take().unwrap_or_default().into_iter()
+                .filter(|m| matches!(m.typ, Some(bollard::models::MountPointTypeEnum::VOLUME)))
+                .filter_map(|m| m.name)
+                .collect();
 
             let mut parsed_ports = Vec::new();
             if let Some(cports) = c.ports {
                 for p in cports {
                     parsed_ports.push(Port {
                         ip: p.ip.clone(),
-                        private_port: if p.private_port == 0 { None } else { Some(p.private_port as u16) },
-                        public_port: p.public_port.map(|x| x as u16),
+                        private_port: if p.private_port == 0 { None } else { Some(p.private_port) },
+                        public_port: p.public_port,
                         port_type: p.typ.map(|t| t.to_string()),
                     });
                 }
@@ -119,16 +178,23 @@ pub async fn list_containers_all(meta: &DockerMeta, cwd: &Path) -> Result<Vec<(C
                 status,
                 ports: String::new(),
                 compose_project,
+                compose_service,
+                restart_count: 0,
+                restart_policy: String::new(),
+                health: String::new(),
+                created: c.created,
+                mounts,
             };
             res.push((summary, parsed_ports));
         }
         res.sort_by_key(|(c, _)| container_name(&c.names).to_lowercase());
+        fill_restart_counts(meta, cwd, &mut res, previous).await;
         return Ok(res);
     }
 
     // Fallback for SSH / no-bollard contexts: use docker binary
     let out = crate::docker::cmd_out(
-        &meta.docker_bin,
+        meta,
         cwd,
         &["ps", "--all", "--format", "{{json .}}"],
     )
@@ -145,10 +211,12 @@ pub async fn list_containers_all(meta: &DockerMeta, cwd: &Path) -> Result<Vec<(C
             // Labels for compose project
             let labels_raw = v.get("Labels").and_then(|x| x.as_str()).unwrap_or("");
             let mut compose_project = None;
+            let mut compose_service = None;
             for part in labels_raw.split(',') {
-                if part.starts_with("com.docker.compose.project=") {
-                    compose_project = Some(part.replace("com.docker.compose.project=", ""));
-                    break;
+                if let Some(v) = part.strip_prefix("com.docker.compose.project=") {
+                    compose_project = Some(v.to_string());
+                } else if let Some(v) = part.strip_prefix("com.docker.compose.service=") {
+                    compose_service = Some(v.to_string());
                 }
             }
 
@@ -165,28 +233,161 @@ pub async fn list_containers_all(meta: &DockerMeta, cwd: &Path) -> Result<Vec<(C
                 status,
                 ports: String::new(),
                 compose_project,
+                compose_service,
+                restart_count: 0,
+                restart_policy: String::new(),
+                health: String::new(),
+                created: v.get("CreatedAt").and_then(|x| x.as_str()).and_then(parse_created_at),
+                mounts: v.get("Mounts").and_then(|x| x.as_str()).unwrap_or("")
+                    .split(',')
+                    .map(|m| m.trim().to_string())
+                    .filter(|m| !m.is_empty())
+                    .collect(),
             };
             res.push((summary, parsed_ports));
         }
     }
     res.sort_by_key(|(c, _)| container_name(&c.names).to_lowercase());
+    fill_restart_counts(meta, cwd, &mut res, previous).await;
     Ok(res)
 }
 
+/// Copy the inspected fields of containers unchanged since `previous` (same
+/// id, `State` and `Status`) and return the indexes left to inspect.
+fn reuse_inspected(res: &mut [(ContainerSummary, Vec<Port>)], previous: &[(ContainerSummary, Vec<Port>)]) -> Vec<usize> {
+    let mut stale = Vec::new();
+    for (i, (c, _)) in res.iter_mut().enumerate() {
+        match previous.iter().find(|(p, _)| p.id == c.id && p.state == c.state && p.status == c.status) {
+            Some((p, _)) => {
+                c.restart_count = p.restart_count;
+                c.restart_policy = p.restart_policy.clone();
+                c.health = p.health.clone();
+            }
+            None => stale.push(i),
+        }
+    }
+    stale
+}
+
+/// Batch-inspect `RestartCount` so crash loops show up even while a
+/// container reads "running", along with the restart policy and the
+/// healthcheck status. Only containers new or changed since `previous` are
+/// inspected. If the batch fails (a container removed between `ps` and
+/// `inspect` fails the whole command), each id is inspected on its own so
+/// only the missing ones keep zero counts and empty policies and health.
+async fn fill_restart_counts(
+    meta: &DockerMeta,
+    cwd: &Path,
+    res: &mut [(ContainerSummary, Vec<Port>)],
+    previous: &[(ContainerSummary, Vec<Port>)],
+) {
+    let stale = reuse_inspected(res, previous);
+    if stale.is_empty() {
+        return;
+    }
+    if let Some(client) = &meta.client {
+        let counts = futures_util::future::join_all(
+            stale.iter().map(|&i| client.inspect_container(&res[i].0.id, None)),
+        )
+        .await;
+        for (&i, info) in stale.iter().zip(counts) {
+            let Ok(info) = info else { continue };
+            let c = &mut res[i].0;
+            c.restart_count = info.restart_count.unwrap_or(0);
+            c.health = info.state.as_ref()
+                .and_then(|s| s.health.as_ref())
+                .and_then(|h| h.status)
+                .map(|s| s.to_string())
+                .filter(|s| s != "none")
+                .unwrap_or_default();
+            c.restart_policy = info.host_config
+                .and_then(|h| h.restart_policy)
+                .and_then(|p| p.name)
+                .map(|n| n.to_string())
+                .unwrap_or_default();
+        }
+        return;
+    }
+
+    // Health comes before the policy name, which may be empty.
+    const FORMAT: &str = "{{.Id}} {{.RestartCount}} {{if .State.Health}}{{.State.Health.Status}}{{else}}-{{end}} {{.HostConfig.RestartPolicy.Name}}";
+    let ids: Vec<&str> = stale.iter().map(|&i| res[i].0.id.as_str()).collect();
+    let mut args = vec!["inspect", "--format", FORMAT];
+    args.extend(ids.iter().copied());
+    let out = match crate::docker::cmd_out(meta, cwd, &args).await {
+        Ok(out) => out,
+        Err(_) => {
+            let each = futures_util::future::join_all(
+                ids.iter().map(|id| async move {
+                    crate::docker::cmd_out(meta, cwd, &["inspect", "--format", FORMAT, id]).await
+                }),
+            )
+            .await;
+            each.into_iter().filter_map(Result::ok).collect::<Vec<_>>().join("\n")
+        }
+    };
+    for line in out.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(full_id), Some(count)) = (fields.next(), fields.next()) else { continue };
+        // `ps` reports short ids, inspect the full ones.
+        if let Some((c, _)) = res.iter_mut().find(|(c, _)| full_id.starts_with(&c.id)) {
+            c.restart_count = count.parse().unwrap_or(0);
+            c.health = fields.next().filter(|h| !matches!(*h, "-" | "none")).unwrap_or_default().to_string();
+            c.restart_policy = fields.next().unwrap_or_default().to_string();
+        }
+    }
+}
+
+/// `docker exec` arguments opening a shell in `id`. With several candidate
+/// shells (`EXEC_SHELL=bash,sh`), the first one present in the container
+/// is picked by a small `sh -c` probe.
+pub fn exec_shell_args(id: &str, shells: &[String]) -> Vec<String> {
+    let mut args = vec!["exec".to_string(), "-it".to_string(), id.to_string()];
+    match shells {
+        [] => args.push("sh".to_string()),
+        [only] => args.extend(crate::docker::split_args(only)),
+        _ => {
+            let probe = shells.iter()
+                .map(|s| {
+                    let program = s.split_whitespace().next().unwrap_or("sh");
+                    format!("command -v {program} >/dev/null 2>&1 && exec {s}")
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            args.extend(["sh".to_string(), "-c".to_string(), probe]);
+        }
+    }
+    args
+}
+
+/// Run `docker <args>` attached to the terminal and return its exit code.
+/// The caller must have released the TUI first.
+pub async fn docker_foreground(meta: &DockerMeta, cwd: &Path, args: &[String]) -> Result<i32> {
+    let status = meta.command()
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .await?;
+    Ok(status.code().unwrap_or(if status.success() { 0 } else { 1 }))
+}
+
 pub async fn container_action(meta: &DockerMeta, _cwd: &Path, verb: &str, id: &str) -> Result<()> {
     if let Some(client) = &meta.client {
-        match verb {
-            "start" => client.start_container(id, None::<StartContainerOptions>).await?,
-            "stop" => client.stop_container(id, None).await?,
-            "restart" => client.restart_container(id, None).await?,
-            "pause" => client.pause_container(id).await?,
-            "unpause" => client.unpause_container(id).await?,
-            "kill" => client.kill_container(id, None::<KillContainerOptions>).await?,
+        let started = std::time::Instant::now();
+        let res = match verb {
+            "start" => client.start_container(id, None::<StartContainerOptions>).await,
+            "stop" => client.stop_container(id, None).await,
+            "restart" => client.restart_container(id, None).await,
+            "pause" => client.pause_container(id).await,
+            "unpause" => client.unpause_container(id).await,
+            "kill" => client.kill_container(id, None::<KillContainerOptions>).await,
             _ => return Err(anyhow!("Unsupported verb: {}", verb)),
-        }
+        };
+        meta.history.record_api(&format!("docker {verb} {id}"), started, &res);
+        res?;
         Ok(())
     } else {
-        let status = tokio::process::Command::new(&meta.docker_bin)
+        let status = meta.command()
             .current_dir(_cwd)
             .args([verb, id])
             .status()
@@ -206,10 +407,13 @@ pub async fn container_rm_force(meta: &DockerMeta, _cwd: &Path, id: &str) -> Res
             v: false,
             link: false,
         });
-        client.remove_container(id, options).await?;
+        let started = std::time::Instant::now();
+        let res = client.remove_container(id, options).await;
+        meta.history.record_api(&format!("docker rm -f {id}"), started, &res);
+        res?;
         Ok(())
     } else {
-        let status = tokio::process::Command::new(&meta.docker_bin)
+        let status = meta.command()
             .current_dir(_cwd)
             .args(["rm", "-f", id])
             .status()
@@ -227,7 +431,7 @@ pub async fn container_inspect(meta: &DockerMeta, _cwd: &Path, id: &str) -> Resu
         let info = client.inspect_container(id, None).await?;
         Ok(serde_json::to_value(info)?)
     } else {
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["inspect", id]).await?;
+        let out = crate::docker::cmd_out(meta, _cwd, &["inspect", id]).await?;
         let v: Value = serde_json::from_str(&out)?;
         // Inspect outputs an array, take the first element
         if let Some(first) = v.get(0) {
@@ -238,6 +442,77 @@ pub async fn container_inspect(meta: &DockerMeta, _cwd: &Path, id: &str) -> Resu
     }
 }
 
+/// Rebuild a `docker run` command from a container's inspect JSON (a small
+/// "runlike"): name, restart policy, network, published ports, env, mounts,
+/// image and command. Env includes what the image itself sets. One option per
+/// line, joined with `\` continuations so it pastes as-is into a shell.
+pub fn docker_run_command(json: &Value) -> String {
+    let text = |v: &Value| v.as_str().unwrap_or_default().to_string();
+    let mut parts = vec!["docker run -d".to_string()];
+
+    let name = text(&json["Name"]);
+    let name = name.trim_start_matches('/');
+    if !name.is_empty() {
+        parts.push(format!("--name {}", shell_quote(name)));
+    }
+    let policy = text(&json["HostConfig"]["RestartPolicy"]["Name"]);
+    let retries = json["HostConfig"]["RestartPolicy"]["MaximumRetryCount"].as_i64().unwrap_or(0);
+    match policy.as_str() {
+        "" | "no" => {}
+        "on-failure" if retries > 0 => parts.push(format!("--restart on-failure:{retries}")),
+        p => parts.push(format!("--restart {p}")),
+    }
+    let network = text(&json["HostConfig"]["NetworkMode"]);
+    if !matches!(network.as_str(), "" | "default" | "bridge") {
+        parts.push(format!("--network {}", shell_quote(&network)));
+    }
+
+    let mut ports: Vec<(&String, &Value)> = json["HostConfig"]["PortBindings"].as_object().into_iter().flatten().collect();
+    ports.sort_by_key(|(port, _)| port.split('/').next().and_then(|p| p.parse::<u16>().ok()).unwrap_or(0));
+    for (port, bindings) in ports {
+        let container_port = port.strip_suffix("/tcp").unwrap_or(port);
+        for b in bindings.as_array().into_iter().flatten() {
+            let host_port = text(&b["HostPort"]);
+            let host_ip = text(&b["HostIp"]);
+            let host = match (host_ip.as_str(), host_port.as_str()) {
+                (_, "") => String::new(),
+                ("" | "0.0.0.0" | "::", hp) => format!("{hp}:"),
+                (ip, hp) => format!("{ip}:{hp}:"),
+            };
+            parts.push(format!("-p {host}{container_port}"));
+        }
+    }
+    for e in json["Config"]["Env"].as_array().into_iter().flatten().filter_map(|v| v.as_str()) {
+        parts.push(format!("-e {}", shell_quote(e)));
+    }
+    for m in json["Mounts"].as_array().into_iter().flatten() {
+        let source = match text(&m["Type"]).as_str() {
+            "volume" => text(&m["Name"]),
+            _ => text(&m["Source"]),
+        };
+        let ro = if m["RW"].as_bool() == Some(false) { ":ro" } else { "" };
+        parts.push(format!("-v {}", shell_quote(&format!("{source}:{}{ro}", text(&m["Destination"])))));
+    }
+
+    let mut tail = shell_quote(&text(&json["Config"]["Image"]));
+    for arg in json["Config"]["Cmd"].as_array().into_iter().flatten().filter_map(|v| v.as_str()) {
+        tail.push(' ');
+        tail.push_str(&shell_quote(arg));
+    }
+    parts.push(tail);
+    parts.join(" \\\n  ")
+}
+
+/// Quote `s` for a POSIX shell, leaving plain words untouched.
+fn shell_quote(s: &str) -> String {
+    let plain = !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || "_-./:=@%+,".contains(c));
+    if plain {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
 pub async fn list_container_files(meta: &DockerMeta, id: &str, path: &str) -> Result<Vec<(String, bool)>> {
     if let Some(client) = &meta.client {
         let exec = client.create_exec(id, CreateExecOptions {
@@ -264,7 +539,7 @@ pub async fn list_container_files(meta: &DockerMeta, id: &str, path: &str) -> Re
         files.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0))); // Dirs first, then alpha
         Ok(files)
     } else {
-        let out = crate::docker::cmd_out(&meta.docker_bin, Path::new("."), &["exec", id, "ls", "-a", "-p", path]).await?;
+        let out = crate::docker::cmd_out(meta, Path::new("."), &["exec", id, "ls", "-a", "-p", path]).await?;
         let mut files = Vec::new();
         for line in out.lines() {
             let name = line.trim();
@@ -309,7 +584,7 @@ pub async fn reset_container(meta: &DockerMeta, _cwd: &Path, id: &str) -> Result
 
     } else {
         log.push(format!("Inspecting {} via CLI...", id));
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["inspect", id]).await?;
+        let out = crate::docker::cmd_out(meta, _cwd, &["inspect", id]).await?;
         let v: Value = serde_json::from_str(&out)?;
         let info = v.get(0).unwrap_or(&v);
         
@@ -325,14 +600,14 @@ pub async fn reset_container(meta: &DockerMeta, _cwd: &Path, id: &str) -> Result
         }
 
         log.push(format!("Stopping container {}...", id));
-        let _ = tokio::process::Command::new(&meta.docker_bin).current_dir(_cwd).args(["stop", id]).status().await;
+        let _ = meta.command().current_dir(_cwd).args(["stop", id]).status().await;
         
         log.push(format!("Removing container {}...", id));
-        let _ = tokio::process::Command::new(&meta.docker_bin).current_dir(_cwd).args(["rm", "-f", id]).status().await;
+        let _ = meta.command().current_dir(_cwd).args(["rm", "-f", id]).status().await;
 
         for v in volumes {
             log.push(format!("Removing volume {}...", v));
-            let _ = tokio::process::Command::new(&meta.docker_bin).current_dir(_cwd).args(["volume", "rm", &v]).status().await;
+            let _ = meta.command().current_dir(_cwd).args(["volume", "rm", &v]).status().await;
         }
         log.push(format!("✅ Reset complete for {}", id));
     }
@@ -406,25 +681,25 @@ pub async fn fetch_stats(meta: &DockerMeta, _cwd: &Path, id: &str) -> Result<Con
         }
         Err(anyhow!("No stats returned from bollard"))
     } else {
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["stats", "--no-stream", "--format", "{{json .}}", id]).await.unwrap_or_default();
+        let out = crate::docker::cmd_out(meta, _cwd, &["stats", "--no-stream", "--format", "{{json .}}", id]).await.unwrap_or_default();
         if let Ok(v) = serde_json::from_str::<serde_json::Value>(&out) {
             let cpu_str = v.get("CPUPerc").and_then(|x| x.as_str()).unwrap_or("0%");
             let cpu_percent = cpu_str.trim_end_matches('%').parse::<f64>().unwrap_or(0.0);
             
             let mem_str = v.get("MemUsage").and_then(|x| x.as_str()).unwrap_or("0 / 0");
             let mem_parts: Vec<&str> = mem_str.split('/').collect();
-            let mem_usage_mb = if let Some(p) = mem_parts.get(0) { parse_size(p.trim()) } else { 0.0 };
+            let mem_usage_mb = if let Some(p) = mem_parts.first() { parse_size(p.trim()) } else { 0.0 };
             let mem_limit_mb = if let Some(p) = mem_parts.get(1) { parse_size(p.trim()) } else { 0.0 };
             let mem_percent = v.get("MemPerc").and_then(|x| x.as_str()).unwrap_or("0%").trim_end_matches('%').parse::<f64>().unwrap_or(0.0);
 
             let net_str = v.get("NetIO").and_then(|x| x.as_str()).unwrap_or("0 / 0");
             let net_parts: Vec<&str> = net_str.split('/').collect();
-            let net_rx_mb = if let Some(p) = net_parts.get(0) { parse_size(p.trim()) } else { 0.0 };
+            let net_rx_mb = if let Some(p) = net_parts.first() { parse_size(p.trim()) } else { 0.0 };
             let net_tx_mb = if let Some(p) = net_parts.get(1) { parse_size(p.trim()) } else { 0.0 };
 
             let block_str = v.get("BlockIO").and_then(|x| x.as_str()).unwrap_or("0 / 0");
             let block_parts: Vec<&str> = block_str.split('/').collect();
-            let block_read_mb = if let Some(p) = block_parts.get(0) { parse_size(p.trim()) } else { 0.0 };
+            let block_read_mb = if let Some(p) = block_parts.first() { parse_size(p.trim()) } else { 0.0 };
             let block_write_mb = if let Some(p) = block_parts.get(1) { parse_size(p.trim()) } else { 0.0 };
 
             Ok(ContainerStats {
@@ -443,11 +718,211 @@ pub async fn fetch_stats(meta: &DockerMeta, _cwd: &Path, id: &str) -> Result<Con
     }
 }
 
+/// Resource figures of one container, for the sidebar resource column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatsSample {
+    pub cpu_percent: f64,
+    pub mem_usage_mb: f64,
+    pub net_rx_mb: f64,
+    pub net_tx_mb: f64,
+    pub block_read_mb: f64,
+    pub block_write_mb: f64,
+}
+
+impl From<&ContainerStats> for StatsSample {
+    fn from(s: &ContainerStats) -> Self {
+        StatsSample {
+            cpu_percent: s.cpu_percent,
+            mem_usage_mb: s.mem_usage_mb,
+            net_rx_mb: s.net_rx_mb,
+            net_tx_mb: s.net_tx_mb,
+            block_read_mb: s.block_read_mb,
+            block_write_mb: s.block_write_mb,
+        }
+    }
+}
+
+/// `12kB / 3.4MB` as two MiB figures; missing halves count as zero.
+fn parse_io_pair(v: &Value, key: &str) -> (f64, f64) {
+    let text = v.get(key).and_then(|x| x.as_str()).unwrap_or("");
+    let mut parts = text.split('/').map(str::trim).filter(|p| p.starts_with(|c: char| c.is_ascii_digit()));
+    (parts.next().map(parse_size).unwrap_or(0.0), parts.next().map(parse_size).unwrap_or(0.0))
+}
+
+/// Parse `docker stats --no-stream --format {{json .}}` into samples keyed by
+/// the short container id. Entries a backend leaves blank (`--`) are skipped.
+pub fn parse_stats_lines(text: &str) -> std::collections::HashMap<String, StatsSample> {
+    text.lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|v| {
+            let id = v.get("ID").and_then(|x| x.as_str())?.to_string();
+            let cpu_percent = v.get("CPUPerc").and_then(|x| x.as_str())?.trim_end_matches('%').parse::<f64>().ok()?;
+            let mem = v.get("MemUsage").and_then(|x| x.as_str())?.split('/').next()?.trim().to_string();
+            if !mem.starts_with(|c: char| c.is_ascii_digit()) {
+                return None;
+            }
+            let (net_rx_mb, net_tx_mb) = parse_io_pair(&v, "NetIO");
+            let (block_read_mb, block_write_mb) = parse_io_pair(&v, "BlockIO");
+            Some((id, StatsSample { cpu_percent, mem_usage_mb: parse_size(&mem), net_rx_mb, net_tx_mb, block_read_mb, block_write_mb }))
+        })
+        .collect()
+}
+
+/// One round of resource figures for the running containers `ids`, keyed
+/// by those ids. Containers the backend reports nothing for are left out,
+/// and any failure gives an empty map: the column simply stays hidden.
+pub async fn fetch_all_stats(meta: &DockerMeta, cwd: &Path, ids: &[String]) -> std::collections::HashMap<String, StatsSample> {
+    if ids.is_empty() {
+        return Default::default();
+    }
+    if meta.client.is_some() {
+        let stats = futures_util::future::join_all(ids.iter().map(|id| fetch_stats(meta, cwd, id))).await;
+        return ids.iter().cloned()
+            .zip(stats)
+            .filter_map(|(id, s)| s.ok().map(|s| (id, StatsSample::from(&s))))
+            .collect();
+    }
+    let Ok(out) = crate::docker::cmd_out(meta, cwd, &["stats", "--no-stream", "--format", "{{json .}}"]).await else {
+        return Default::default();
+    };
+    let parsed = parse_stats_lines(&out);
+    // `stats` prints 12-character ids; ours may be full ones.
+    ids.iter()
+        .filter_map(|id| parsed.iter().find(|(short, _)| id.starts_with(short.as_str())).map(|(_, s)| (id.clone(), *s)))
+        .collect()
+}
+
+/// `cpu 12% mem 340MiB` for the sidebar, or the columns `view` picks.
+pub fn format_stats_sample(s: &StatsSample, view: StatsView) -> String {
+    let mem = if s.mem_usage_mb >= 1024.0 {
+        format!("{:.1}GiB", s.mem_usage_mb / 1024.0)
+    } else {
+        format!("{:.0}MiB", s.mem_usage_mb)
+    };
+    let net = format!("↓{} ↑{}", short_mb(s.net_rx_mb), short_mb(s.net_tx_mb));
+    let io = format!("R{} W{}", short_mb(s.block_read_mb), short_mb(s.block_write_mb));
+    match view {
+        StatsView::CpuMem => format!("cpu {:.0}% mem {mem}", s.cpu_percent),
+        StatsView::Net => format!("net {net}"),
+        StatsView::Io => format!("io {io}"),
+        StatsView::All => format!("cpu {:.0}% mem {mem} {net} {io}", s.cpu_percent),
+    }
+}
+
+/// Last `tail` log lines of a container, without following: for stopped
+/// containers, where a follower would have nothing live to wait for, and
+/// for what was already there when coming back to one (`until`, unix
+/// seconds). `timestamps` prefixes each line with its RFC3339 time.
+pub async fn fetch_container_logs(
+    meta: &DockerMeta,
+    cwd: &Path,
+    id: &str,
+    tail: usize,
+    until: Option<i64>,
+    timestamps: bool,
+) -> Result<Vec<String>> {
+    let text = if let Some(client) = &meta.client {
+        let options = Some(LogsOptions {
+            follow: false,
+            stdout: true,
+            stderr: true,
+            tail: tail.to_string(),
+            until: until.unwrap_or_default() as i32,
+            timestamps,
+            ..Default::default()
+        });
+        let started = std::time::Instant::now();
+        let mut stream = client.logs(id, options);
+        let mut text = String::new();
+        let mut res = Ok(());
+        while let Some(log) = stream.next().await {
+            match log {
+                Ok(bollard::container::LogOutput::StdIn { .. }) => {}
+                Ok(bollard::container::LogOutput::StdErr { message }) => text.push_str(&tag_stderr(&String::from_utf8_lossy(&message))),
+                Ok(log) => text.push_str(&String::from_utf8_lossy(&log.into_bytes())),
+                Err(e) => {
+                    res = Err(e);
+                    break;
+                }
+            }
+        }
+        meta.history.record_api(&format!("docker logs --tail {tail} {id}"), started, &res);
+        res?;
+        text
+    } else {
+        let mut cmd = meta.command();
+        cmd.current_dir(cwd).args(["logs", "--tail", &tail.to_string()]);
+        if timestamps {
+            cmd.arg("--timestamps");
+        }
+        if let Some(until) = until {
+            cmd.args(["--until", &until.to_string()]);
+        }
+        let out = cmd.arg(id).output().await?;
+        if !out.status.success() {
+            return Err(anyhow!("docker logs failed: {}", String::from_utf8_lossy(&out.stderr).trim()));
+        }
+        // Interleaving of the two streams is lost here; stderr usually holds the crash.
+        let mut text = String::from_utf8_lossy(&out.stdout).to_string();
+        text.push_str(&tag_stderr(&String::from_utf8_lossy(&out.stderr)));
+        text
+    };
+    Ok(text.lines().map(|l| l.to_string()).collect())
+}
+
+/// Indices of the `(id, name)` candidates matching `query` for the go-to
+/// prompt. An exact name or id wins on its own; otherwise every name or id
+/// starting with `query` (case-insensitive for names) matches.
+pub fn match_containers(candidates: &[(&str, &str)], query: &str) -> Vec<usize> {
+    let query = query.trim();
+    if query.is_empty() {
+        return vec![];
+    }
+    if let Some(exact) = candidates.iter().position(|(id, name)| *name == query || *id == query) {
+        return vec![exact];
+    }
+    let lower = query.to_lowercase();
+    candidates.iter()
+        .enumerate()
+        .filter(|(_, (id, name))| id.starts_with(query) || name.to_lowercase().starts_with(&lower))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// `…(truncated 12kb)`, appended where [`truncate_line`] cut a line.
+pub fn truncation_marker(dropped_bytes: usize) -> String {
+    format!("…(truncated {}kb)", dropped_bytes.div_ceil(1024))
+}
+
+/// Cut `line` to at most `max` bytes (on a char boundary) plus a
+/// [`truncation_marker`], so one huge line cannot stall wrapping in the log
+/// pane. `max == 0` keeps lines whole.
+pub fn truncate_line(line: &str, max: usize) -> std::borrow::Cow<'_, str> {
+    if max == 0 || line.len() <= max {
+        return line.into();
+    }
+    let mut end = max;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &line[..end], truncation_marker(line.len() - end)).into()
+}
+
+/// `text` with every line tagged `[ERR] `, so stderr can be told apart
+/// (and coloured) once it is merged with stdout in the log pane.
+pub fn tag_stderr(text: &str) -> String {
+    text.split_inclusive('\n').map(|line| format!("[ERR] {line}")).collect()
+}
+
+/// Follow a container's output from its last `tail` lines, or from the last
+/// `tail` lines written after `since` (unix seconds) when given.
 pub async fn stream_container_logs(
     meta: &DockerMeta,
     _cwd: &Path,
     id: &str,
     tail: usize,
+    since: Option<i64>,
+    timestamps: bool,
 ) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
     if let Some(client) = &meta.client {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -456,6 +931,8 @@ pub async fn stream_container_logs(
             stdout: true,
             stderr: true,
             tail: tail.to_string(),
+            since: since.unwrap_or_default() as i32,
+            timestamps,
             ..Default::default()
         });
         
@@ -464,7 +941,7 @@ pub async fn stream_container_logs(
             while let Some(Ok(log)) = stream.next().await {
                 let msg = match log {
                     bollard::container::LogOutput::StdOut { message } => String::from_utf8_lossy(&message).to_string(),
-                    bollard::container::LogOutput::StdErr { message } => String::from_utf8_lossy(&message).to_string(),
+                    bollard::container::LogOutput::StdErr { message } => tag_stderr(&String::from_utf8_lossy(&message)),
                     bollard::container::LogOutput::Console { message } => String::from_utf8_lossy(&message).to_string(),
                     bollard::container::LogOutput::StdIn { message: _ } => continue,
                 };
@@ -474,9 +951,16 @@ pub async fn stream_container_logs(
 
         Ok((LogStream::Task(task), rx))
     } else {
-        let mut child = Command::new(&meta.docker_bin)
-            .current_dir(_cwd)
-            .args(["logs", "-f", "--tail", &tail.to_string(), id])
+        let mut cmd = meta.command();
+        cmd.current_dir(_cwd).args(["logs", "-f", "--tail", &tail.to_string()]);
+        if timestamps {
+            cmd.arg("--timestamps");
+        }
+        if let Some(since) = since {
+            cmd.args(["--since", &since.to_string()]);
+        }
+        let mut child = cmd
+            .arg(id)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -495,13 +979,13 @@ pub async fn stream_container_logs(
                 }
             });
         }
-        if let Some(mut err) = stderr {
+        if let Some(err) = stderr {
+            // By line, so every stderr line gets its own tag.
             let tx2 = tx.clone();
             tokio::spawn(async move {
-                let mut buf = [0u8; 4096];
-                while let Ok(n) = err.read(&mut buf).await {
-                    if n == 0 { break; }
-                    let _ = tx2.send(String::from_utf8_lossy(&buf[..n]).to_string());
+                let mut lines = BufReader::new(err).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let _ = tx2.send(format!("[ERR] {line}\n"));
                 }
             });
         }
@@ -510,20 +994,6 @@ pub async fn stream_container_logs(
     }
 }
 
-pub fn spawn_logs_follow(
-    meta: &DockerMeta,
-    cwd: &std::path::Path,
-    id: &str,
-    tail: usize,
-) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
-    let rt = tokio::runtime::Handle::current();
-    tokio::task::block_in_place(|| {
-        rt.block_on(async {
-            stream_container_logs(meta, cwd, id, tail).await
-        })
-    })
-}
-
 pub async fn spawn_shell(
     meta: &DockerMeta,
     _cwd: &Path,
@@ -566,7 +1036,7 @@ pub async fn spawn_shell(
             Err(anyhow!("Failed to attach to exec session"))
         }
     } else {
-        let mut child = Command::new(&meta.docker_bin)
+        let mut child = meta.command()
             .current_dir(_cwd)
             .args(["exec", "-i", id, "sh"])
             .stdin(Stdio::piped())
@@ -604,22 +1074,30 @@ pub async fn spawn_shell(
     }
 }
 
-pub fn pick_best_public_port(ports: &[Port]) -> Option<u16> {
-    let preferred_private = [3000u16, 8025, 54323, 5678, 5173, 4173, 8080, 80, 1337];
+pub const DEFAULT_PREFERRED_PRIVATE_PORTS: [u16; 9] = [3000, 8025, 54323, 5678, 5173, 4173, 8080, 80, 1337];
+pub const DEFAULT_PREFERRED_PUBLIC_PORTS: [u16; 4] = [80, 3000, 8080, 54324];
+
+/// Pick the port `o` opens. `preferred` (from OPEN_PREFERRED_PORTS) replaces
+/// both built-in preference lists when non-empty.
+pub fn pick_best_public_port(ports: &[Port], preferred: &[u16]) -> Option<u16> {
+    let (preferred_private, preferred_public): (&[u16], &[u16]) = if preferred.is_empty() {
+        (&DEFAULT_PREFERRED_PRIVATE_PORTS, &DEFAULT_PREFERRED_PUBLIC_PORTS)
+    } else {
+        (preferred, preferred)
+    };
 
     let tcp_ports: Vec<&Port> = ports
         .iter()
         .filter(|p| p.public_port.is_some() && p.port_type.as_deref().unwrap_or("tcp") == "tcp")
         .collect();
 
-    for privp in preferred_private {
+    for &privp in preferred_private {
         if let Some(hit) = tcp_ports.iter().find(|p| p.private_port == Some(privp)) {
             return hit.public_port;
         }
     }
 
-    let preferred_public = [80u16, 3000, 8080, 54324];
-    for pubp in preferred_public {
+    for &pubp in preferred_public {
         if let Some(hit) = tcp_ports.iter().find(|p| p.public_port == Some(pubp)) {
             return hit.public_port;
         }
@@ -676,10 +1154,113 @@ pub fn parse_port_string(raw: &str) -> Vec<Port> {
     results
 }
 
+/// Explain a container that does not behave as its restart policy says:
+/// `always` yet exited, or `no` yet replaced by new containers
+/// (`recreations` counts containers of its compose service replaced this
+/// session).
+pub fn restart_policy_hint(policy: &str, state: &str, recreations: u32) -> Option<String> {
+    match policy {
+        "always" if matches!(state, "exited" | "dead") => {
+            Some("restart: always, but exited (stopped by hand, or the daemon is not restarting it)".to_string())
+        }
+        "no" | "" if recreations >= 2 => {
+            Some(format!("restart: no, but recreated {recreations}x this session (compose up / watcher?)"))
+        }
+        _ => None,
+    }
+}
+
+/// Parse the `CreatedAt` column of `docker ps --format json`, e.g.
+/// `2024-01-01 10:00:00 +0100 CET`, into unix seconds. The trailing zone
+/// abbreviation is ignored; the numeric offset is authoritative.
+pub fn parse_created_at(raw: &str) -> Option<i64> {
+    let head: Vec<&str> = raw.split_whitespace().take(3).collect();
+    chrono::DateTime::parse_from_str(&head.join(" "), "%Y-%m-%d %H:%M:%S %z")
+        .ok()
+        .map(|d| d.timestamp())
+}
+
+/// Compact "how long ago" for an elapsed number of seconds: `45s ago`,
+/// `12m ago`, `3h ago`, `5d ago`, `7w ago`.
+pub fn relative_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs < 60 {
+        format!("{secs}s ago")
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h ago", secs / 3600)
+    } else if secs < 14 * 86_400 {
+        format!("{}d ago", secs / 86_400)
+    } else {
+        format!("{}w ago", secs / (7 * 86_400))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_exec_shell_args() {
+        assert_eq!(exec_shell_args("abc", &[]), ["exec", "-it", "abc", "sh"]);
+        assert_eq!(exec_shell_args("abc", &["bash -l".to_string()]), ["exec", "-it", "abc", "bash", "-l"]);
+        assert_eq!(
+            exec_shell_args("abc", &["bash".to_string(), "sh".to_string()]),
+            ["exec", "-it", "abc", "sh", "-c", "command -v bash >/dev/null 2>&1 && exec bash; command -v sh >/dev/null 2>&1 && exec sh"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stream_container_logs_cli() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("docker-cli-logs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let bin = dir.join("docker");
+        std::fs::write(&bin, "#!/bin/sh\necho \"args: $*\"\necho oops >&2\n").unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let meta = DockerMeta::offline(&bin.to_string_lossy());
+        let (_stream, mut rx) = stream_container_logs(&meta, &dir, "abc", 50, Some(7), true).await.unwrap();
+        let mut out = String::new();
+        while let Ok(Some(chunk)) = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv()).await {
+            out.push_str(&chunk);
+        }
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(out.contains("args: logs -f --tail 50 --timestamps --since 7 abc"), "{out}");
+        assert!(out.contains("[ERR] oops\n"), "{out}");
+    }
+
+    #[test]
+    fn test_reuse_inspected() {
+        let summary = |id: &str, status: &str| -> ContainerSummary {
+            serde_json::from_value(serde_json::json!({"ID": id, "Names": id, "State": "running", "Status": status, "Ports": ""})).unwrap()
+        };
+        let mut old = summary("a", "Up 5 minutes");
+        old.restart_count = 3;
+        old.restart_policy = "always".to_string();
+        old.health = "healthy".to_string();
+        let previous = vec![(old, vec![]), (summary("b", "Up 1 second"), vec![])];
+        let mut res = vec![(summary("a", "Up 5 minutes"), vec![]), (summary("b", "Up 2 seconds"), vec![]), (summary("c", "Up 1 second"), vec![])];
+        assert_eq!(reuse_inspected(&mut res, &previous), [1, 2]);
+        assert_eq!((res[0].0.restart_count, res[0].0.restart_policy.as_str(), res[0].0.health.as_str()), (3, "always", "healthy"));
+    }
+
+    #[test]
+    fn test_parse_stats_lines() {
+        let out = r#"{"ID":"0123456789ab","CPUPerc":"12.50%","MemUsage":"340MiB / 7.6GiB","NetIO":"2MB / 512kB","BlockIO":"0B / 20MB"}
+{"ID":"ba9876543210","CPUPerc":"0.00%","MemUsage":"1.5GiB / 7.6GiB"}
+{"ID":"ffffffffffff","CPUPerc":"--","MemUsage":"-- / --"}"#;
+        let stats = parse_stats_lines(out);
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats["0123456789ab"].cpu_percent, stats["0123456789ab"].mem_usage_mb), (12.5, 340.0));
+        assert_eq!(format_stats_sample(&stats["0123456789ab"], StatsView::CpuMem), "cpu 12% mem 340MiB");
+        assert_eq!(format_stats_sample(&stats["ba9876543210"], StatsView::CpuMem), "cpu 0% mem 1.5GiB");
+        assert_eq!(format_stats_sample(&stats["0123456789ab"], StatsView::Net), "net ↓2.0M ↑0.5M");
+        assert_eq!(format_stats_sample(&stats["0123456789ab"], StatsView::Io), "io R0.0M W20M");
+        assert_eq!(format_stats_sample(&stats["ba9876543210"], StatsView::All), "cpu 0% mem 1.5GiB ↓0.0M ↑0.0M R0.0M W0.0M");
+    }
+
     #[test]
     fn test_parse_port_string() {
         let ports = parse_port_string("0.0.0.0:80->80/tcp, :::80->80/tcp, 443/tcp");
@@ -701,6 +1282,13 @@ mod tests {
         assert_eq!(ports[2].port_type, Some("tcp".to_string()));
     }
 
+    #[test]
+    fn test_pick_best_public_port_preferred() {
+        let ports = parse_port_string("0.0.0.0:8081->80/tcp, 0.0.0.0:4201->4200/tcp");
+        assert_eq!(pick_best_public_port(&ports, &[]), Some(8081));
+        assert_eq!(pick_best_public_port(&ports, &[4200]), Some(4201));
+    }
+
     #[test]
     fn test_parse_port_string_simple() {
         let ports = parse_port_string("8080/tcp");
@@ -708,5 +1296,83 @@ mod tests {
         assert_eq!(ports[0].private_port, Some(8080));
         assert_eq!(ports[0].public_port, None);
     }
-}
 
+    #[test]
+    fn test_created_at_and_age() {
+        assert_eq!(parse_created_at("2024-01-01 10:00:00 +0100 CET"), Some(1704099600));
+        assert_eq!(parse_created_at("2024-01-01 09:00:00 +0000 UTC"), Some(1704099600));
+        assert_eq!(parse_created_at("yesterday"), None);
+
+        assert_eq!(relative_age(-5), "0s ago");
+        assert_eq!(relative_age(59), "59s ago");
+        assert_eq!(relative_age(2 * 3600 + 5), "2h ago");
+        assert_eq!(relative_age(3 * 86_400), "3d ago");
+        assert_eq!(relative_age(30 * 86_400), "4w ago");
+    }
+
+    #[test]
+    fn test_restart_policy_hint() {
+        assert!(restart_policy_hint("always", "exited", 0).is_some());
+        assert!(restart_policy_hint("always", "running", 0).is_none());
+        assert!(restart_policy_hint("unless-stopped", "exited", 0).is_none());
+        assert!(restart_policy_hint("no", "running", 1).is_none());
+        assert_eq!(
+            restart_policy_hint("no", "running", 3).as_deref(),
+            Some("restart: no, but recreated 3x this session (compose up / watcher?)")
+        );
+        assert!(restart_policy_hint("", "exited", 2).is_some());
+    }
+
+    #[test]
+    fn test_docker_run_command() {
+        let json: Value = serde_json::from_str(r#"{
+            "Name": "/web-1",
+            "HostConfig": {
+                "RestartPolicy": {"Name": "unless-stopped", "MaximumRetryCount": 0},
+                "NetworkMode": "app_default",
+                "PortBindings": {"80/tcp": [{"HostIp": "", "HostPort": "8080"}], "53/udp": [{"HostIp": "127.0.0.1", "HostPort": "5353"}]}
+            },
+            "Config": {"Image": "nginx:1.27", "Env": ["A=1", "MSG=hello world"], "Cmd": ["nginx", "-g", "daemon off;"]},
+            "Mounts": [
+                {"Type": "bind", "Source": "/srv/site", "Destination": "/usr/share/nginx/html", "RW": false},
+                {"Type": "volume", "Name": "cache", "Source": "/var/lib/docker/volumes/cache/_data", "Destination": "/cache", "RW": true}
+            ]
+        }"#).unwrap();
+        let cmd = docker_run_command(&json);
+        let lines: Vec<&str> = cmd.lines().map(|l| l.trim().trim_end_matches(" \\")).collect();
+        assert_eq!(lines, [
+            "docker run -d",
+            "--name web-1",
+            "--restart unless-stopped",
+            "--network app_default",
+            "-p 127.0.0.1:5353:53/udp",
+            "-p 8080:80",
+            "-e A=1",
+            "-e 'MSG=hello world'",
+            "-v /srv/site:/usr/share/nginx/html:ro",
+            "-v cache:/cache",
+            "nginx:1.27 nginx -g 'daemon off;'",
+        ]);
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_truncate_line() {
+        assert_eq!(truncate_line("short", 10), "short");
+        assert_eq!(truncate_line(&"x".repeat(5000), 0).len(), 5000);
+        assert_eq!(truncate_line(&"x".repeat(5000), 10), format!("{}…(truncated 5kb)", "x".repeat(10)));
+        // Never splits a multi-byte char.
+        assert_eq!(truncate_line("ééé", 3), "é…(truncated 1kb)");
+    }
+
+    #[test]
+    fn test_match_containers() {
+        let cands = [("4f2a91c0", "web-1"), ("4f7b0033", "web-10"), ("9c1d2e3f", "db")];
+        assert_eq!(match_containers(&cands, "web-1"), [0]);
+        assert_eq!(match_containers(&cands, "WEB"), [0, 1]);
+        assert_eq!(match_containers(&cands, "4f"), [0, 1]);
+        assert_eq!(match_containers(&cands, "9c1"), [2]);
+        assert!(match_containers(&cands, "api").is_empty());
+        assert!(match_containers(&cands, "  ").is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use bollard::query_parameters::{ListImagesOptions, RemoveImageOptions};
+use bollard::query_parameters::{ListImagesOptions, PruneImagesOptions, RemoveImageOptions};
 use crate::docker::DockerMeta;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +13,13 @@ pub struct DockerImage {
     pub created_since: String,
 }
 
+impl DockerImage {
+    /// Untagged layer left behind by a rebuild (`<none>:<none>`).
+    pub fn is_dangling(&self) -> bool {
+        self.repository == "<none>" && self.tag == "<none>"
+    }
+}
+
 pub async fn get_images(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<DockerImage>> {
     if let Some(client) = &meta.client {
         let options = Some(ListImagesOptions {
@@ -53,7 +60,7 @@ pub async fn get_images(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<DockerImag
         }
         Ok(results)
     } else {
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["image", "ls", "--format", "{{json .}}"]).await?;
+        let out = crate::docker::cmd_out(meta, _cwd, &["image", "ls", "--format", "{{json .}}"]).await?;
         let mut results = Vec::new();
         for line in out.lines().filter(|l| !l.trim().is_empty()) {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
@@ -71,20 +78,49 @@ pub async fn get_images(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<DockerImag
     }
 }
 
+/// `docker image inspect <id>`, pretty-printed.
+pub async fn inspect_image(meta: &DockerMeta, cwd: &Path, id: &str) -> Result<String> {
+    if let Some(client) = &meta.client {
+        let image = client.inspect_image(id).await?;
+        return Ok(serde_json::to_string_pretty(&image)?);
+    }
+    crate::docker::cmd_out(meta, cwd, &["image", "inspect", id]).await
+}
+
+/// `docker image prune -f`: remove dangling images. Returns a summary line.
+pub async fn prune_images(meta: &DockerMeta, cwd: &Path) -> Result<String> {
+    if let Some(client) = &meta.client {
+        let started = std::time::Instant::now();
+        let res = client.prune_images(None::<PruneImagesOptions>).await;
+        meta.history.record_api("docker image prune -f", started, &res);
+        let p = res?;
+        return Ok(format!(
+            "Pruned {} images. Reclaimed {:.1} MB.",
+            p.images_deleted.unwrap_or_default().len(),
+            p.space_reclaimed.unwrap_or(0) as f64 / 1_048_576.0
+        ));
+    }
+    crate::docker::cmd_out(meta, cwd, &["image", "prune", "-f"]).await
+}
+
 pub async fn rm_image(meta: &DockerMeta, _cwd: &Path, id: &str, force: bool) -> Result<()> {
     if let Some(client) = &meta.client {
         let options = Some(RemoveImageOptions {
             force,
             ..Default::default()
         });
-        client.remove_image(id, options, None).await?;
+        let started = std::time::Instant::now();
+        let res = client.remove_image(id, options, None).await;
+        let flag = if force { " -f" } else { "" };
+        meta.history.record_api(&format!("docker image rm{flag} {id}"), started, &res);
+        res?;
         Ok(())
     } else {
         let mut args = vec!["image", "rm"];
         if force { args.push("-f"); }
         args.push(id);
         
-        let status = tokio::process::Command::new(&meta.docker_bin)
+        let status = meta.command()
             .current_dir(_cwd)
             .args(args)
             .status()
@@ -1,8 +1,9 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use tokio::process::Command;
+use std::process::Stdio;
 
+pub mod command_log;
 pub mod containers;
 pub mod images;
 pub mod networks;
@@ -11,6 +12,7 @@ pub mod volumes;
 pub mod compose;
 pub mod system;
 
+pub use command_log::*;
 pub use containers::*;
 pub use images::*;
 pub use networks::*;
@@ -28,11 +30,67 @@ pub struct DockerMeta {
     pub remote_host: String,
     pub available: bool,
     pub docker_bin: String,
+    /// Context passed as `--context` to every CLI call. `None` keeps the
+    /// docker CLI's own current context.
+    pub context: Option<String>,
     pub client: Option<bollard::Docker>,
+    pub history: CommandHistory,
+    /// Why detection fell short (daemon unreachable, no Engine API client…).
+    pub error: Option<String>,
+    /// How compose is invoked on this machine.
+    pub compose: ComposeCli,
+    /// `COMPOSE_FILES`, passed in order as `-f` to every compose call; empty
+    /// lets compose find its own file.
+    pub compose_files: Vec<std::path::PathBuf>,
+}
+
+/// Compose flavour found at startup: the v2 `docker compose` plugin, or the
+/// legacy standalone `docker-compose` (v1) binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComposeCli {
+    Plugin,
+    Legacy,
+    /// Neither answered; commands still go through the plugin syntax so the
+    /// error the user sees names the missing piece.
+    Missing,
+}
+
+impl ComposeCli {
+    pub async fn detect(docker_bin: &str, history: &CommandHistory) -> Self {
+        let works = |mut c: DockerCommand| async move {
+            c.stdout(Stdio::null()).stderr(Stdio::null()).status().await.is_ok_and(|s| s.success())
+        };
+        let mut plugin = DockerCommand::new(docker_bin, history.clone());
+        plugin.args(["compose", "version"]);
+        if works(plugin).await {
+            return ComposeCli::Plugin;
+        }
+        let mut legacy = DockerCommand::new("docker-compose", history.clone());
+        legacy.arg("version");
+        if works(legacy).await {
+            return ComposeCli::Legacy;
+        }
+        ComposeCli::Missing
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ComposeCli::Plugin => "docker compose (v2 plugin)",
+            ComposeCli::Legacy => "docker-compose (legacy v1)",
+            ComposeCli::Missing => "not found (neither docker compose nor docker-compose)",
+        }
+    }
 }
 
 impl DockerMeta {
     pub async fn detect(cwd: &Path, docker_bin: &str) -> Self {
+        Self::detect_context(cwd, docker_bin, None, CommandHistory::default()).await
+    }
+
+    /// Detect the daemon behind `context`, or behind the user's current
+    /// context when `None`. Never runs `docker context use`, so the global
+    /// default context is left untouched.
+    pub async fn detect_context(cwd: &Path, docker_bin: &str, context: Option<&str>, history: CommandHistory) -> Self {
         let docker_bin = docker_bin.to_string();
         let cwd_buf = cwd.to_path_buf();
 
@@ -42,60 +100,83 @@ impl DockerMeta {
         let mut socket_path = "".to_string();
         let mut remote_host = "localhost".to_string();
         let mut host_raw = "".to_string();
+        let mut error = None;
 
-        // 1. Primary check: docker context show
+        // 1. Primary check: docker context show (or the explicitly requested context)
         // If this works, the binary is found and functional.
-        if let Ok(ctx_out) = cmd_out(&docker_bin, &cwd_buf, &["context", "show"]).await {
-            available = true;
-            let ctx = ctx_out.trim().to_string();
-            ctx_name = ctx.clone();
-            
-            // docker context inspect <ctx>
-            if let Ok(info) = cmd_out(&docker_bin, &cwd_buf, &["context", "inspect", &ctx]).await {
-                if let Ok(v) = serde_json::from_str::<serde_json::Value>(&info) {
-                    let host = v
-                        .get(0)
-                        .and_then(|x| x.get("Endpoints"))
-                        .and_then(|x| x.get("docker"))
-                        .and_then(|x| x.get("Host"))
-                        .and_then(|x| x.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    socket_path = host.clone();
-                    host_raw = host.clone();
-                    remote_host = if host.starts_with("ssh://") || host.starts_with("tcp://") {
-                        host.split("//").nth(1).and_then(|s| s.split('@').last()).and_then(|s| s.split(':').next()).unwrap_or("localhost").to_string()
-                    } else {
-                        "localhost".to_string()
-                    };
-                    backend = classify(&ctx, &host);
+        let current = match context {
+            Some(name) => Ok(name.to_string()),
+            None => output_text(DockerCommand::new(&docker_bin, history.clone()), &cwd_buf, &["context", "show"]).await,
+        };
+        match current {
+            Ok(ctx_out) => {
+                // An explicit context only counts as available once it inspects cleanly.
+                available = context.is_none();
+                let ctx = ctx_out.trim().to_string();
+                ctx_name = ctx.clone();
+
+                // docker context inspect <ctx>
+                match output_text(DockerCommand::new(&docker_bin, history.clone()), &cwd_buf, &["context", "inspect", &ctx]).await {
+                    Ok(info) => {
+                        available = true;
+                        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&info) {
+                            let host = v
+                                .get(0)
+                                .and_then(|x| x.get("Endpoints"))
+                                .and_then(|x| x.get("docker"))
+                                .and_then(|x| x.get("Host"))
+                                .and_then(|x| x.as_str())
+                                .unwrap_or("")
+                                .to_string();
+                            socket_path = host.clone();
+                            host_raw = host.clone();
+                            remote_host = if host.starts_with("ssh://") || host.starts_with("tcp://") {
+                                host.split("//").nth(1).and_then(|s| s.split('@').next_back()).and_then(|s| s.split(':').next()).unwrap_or("localhost").to_string()
+                            } else {
+                                "localhost".to_string()
+                            };
+                            backend = classify(&ctx, &host);
+                        }
+                    }
+                    Err(e) => error = Some(e.to_string()),
                 }
             }
-        } else {
-            // 2. Fallback: just check if docker binary exists/works at all
-            if let Ok(o) = Command::new(&docker_bin).args(["--version"]).output().await {
-                if o.status.success() {
-                    available = true;
+            Err(show_err) => {
+                // 2. Fallback: just check if docker binary exists/works at all
+                match DockerCommand::new(&docker_bin, history.clone()).args(["--version"]).output().await {
+                    Ok(o) if o.status.success() => {
+                        available = true;
+                        error = Some(show_err.to_string());
+                    }
+                    Ok(o) => error = Some(format!("{docker_bin} --version exited with {}", o.status)),
+                    Err(e) => error = Some(format!("cannot run {docker_bin}: {e}")),
                 }
             }
         }
 
-        // Build bollard client depending on the transport type
+        // Build bollard client depending on the transport type. DOCKER_HOST is
+        // only exported for the user's own context: an explicit one reaches
+        // the CLI through `--context` and must not leak into the process env.
+        let export_host = |host: &str| {
+            if context.is_none() {
+                std::env::set_var("DOCKER_HOST", host);
+            }
+        };
         let client = if available {
             if host_raw.starts_with("ssh://") {
                 // bollard does not support SSH — set env var so the docker binary works,
                 // but leave the bollard client as None. CLI commands will still function.
-                std::env::set_var("DOCKER_HOST", &host_raw);
+                export_host(&host_raw);
                 None
             } else if host_raw.starts_with("tcp://") || host_raw.starts_with("http://") {
-                std::env::set_var("DOCKER_HOST", &host_raw);
+                export_host(&host_raw);
                 let addr = host_raw.trim_start_matches("tcp://").trim_start_matches("http://");
                 bollard::Docker::connect_with_http(addr, 120, bollard::API_DEFAULT_VERSION)
                     .ok()
             } else if !host_raw.is_empty() {
                 // unix socket path, e.g. unix:///var/run/docker.sock
-                std::env::set_var("DOCKER_HOST", &host_raw);
-                let mut d = bollard::Docker::connect_with_defaults().ok();
+                export_host(&host_raw);
+                let mut d = bollard::Docker::connect_with_socket(&host_raw, 120, bollard::API_DEFAULT_VERSION).ok();
                 if let Some(dock) = d {
                     d = dock.negotiate_version().await.ok();
                 }
@@ -111,6 +192,15 @@ impl DockerMeta {
         } else {
             None
         };
+        let compose = if available {
+            ComposeCli::detect(&docker_bin, &history).await
+        } else {
+            ComposeCli::Missing
+        };
+
+        if available && client.is_none() && error.is_none() {
+            error = Some("no Engine API client for this endpoint; using the docker CLI".to_string());
+        }
 
         DockerMeta {
             backend,
@@ -119,8 +209,64 @@ impl DockerMeta {
             remote_host,
             available,
             docker_bin,
+            context: context.map(|c| c.to_string()),
             client,
+            history,
+            error,
+            compose,
+            compose_files: Vec::new(),
+        }
+    }
+
+    /// Start a compose invocation in the detected flavour: `docker compose …`,
+    /// or `docker-compose …` with the context passed through `DOCKER_CONTEXT`.
+    /// `COMPOSE_FILES` come first as `-f` options.
+    pub fn compose_command(&self) -> DockerCommand {
+        let mut c = if self.compose != ComposeCli::Legacy {
+            let mut c = self.command();
+            c.arg("compose");
+            c
+        } else {
+            let mut c = DockerCommand::new("docker-compose", self.history.clone());
+            if let Some(ctx) = &self.context {
+                c.envs([("DOCKER_CONTEXT", ctx)]);
+            }
+            c
+        };
+        for file in &self.compose_files {
+            c.arg("-f");
+            c.arg(file.to_string_lossy().as_ref());
+        }
+        c
+    }
+
+    /// Start a docker CLI invocation, pinned to `self.context` when set.
+    /// A reachable-looking daemon driven only through `docker_bin`, with no
+    /// Engine API client and no context, for tests.
+    #[cfg(test)]
+    pub fn offline(docker_bin: &str) -> Self {
+        DockerMeta {
+            backend: "test".to_string(),
+            context_name: String::new(),
+            socket_path: String::new(),
+            remote_host: String::new(),
+            available: true,
+            docker_bin: docker_bin.to_string(),
+            context: None,
+            client: None,
+            history: Default::default(),
+            error: None,
+            compose: ComposeCli::Plugin,
+            compose_files: Vec::new(),
+        }
+    }
+
+    pub fn command(&self) -> DockerCommand {
+        let mut c = DockerCommand::new(&self.docker_bin, self.history.clone());
+        if let Some(ctx) = &self.context {
+            c.args(["--context", ctx]);
         }
+        c
     }
 }
 
@@ -133,10 +279,19 @@ pub(crate) fn classify(context_name: &str, socket_path: &str) -> String {
     }
 }
 
-pub(crate) async fn cmd_out(bin: &str, cwd: &Path, args: &[&str]) -> Result<String> {
-    let out = Command::new(bin).current_dir(cwd).args(args).output().await?;
+pub(crate) async fn cmd_out(meta: &DockerMeta, cwd: &Path, args: &[&str]) -> Result<String> {
+    output_text(meta.command(), cwd, args).await
+}
+
+/// [`cmd_out`] for compose subcommands (`args` without the leading `compose`).
+pub(crate) async fn compose_out(meta: &DockerMeta, cwd: &Path, args: &[&str]) -> Result<String> {
+    output_text(meta.compose_command(), cwd, args).await
+}
+
+async fn output_text(mut cmd: DockerCommand, cwd: &Path, args: &[&str]) -> Result<String> {
+    let out = cmd.current_dir(cwd).args(args).output().await?;
     if !out.status.success() {
-        return Err(anyhow!("command failed: {bin} {:?}", args));
+        return Err(anyhow!("command failed: {}", cmd.display()));
     }
     Ok(String::from_utf8_lossy(&out.stdout).trim_end().to_string())
 }
@@ -35,7 +35,7 @@ pub async fn get_networks(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<DockerNe
         }
         Ok(results)
     } else {
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["network", "ls", "--format", "{{json .}}"]).await?;
+        let out = crate::docker::cmd_out(meta, _cwd, &["network", "ls", "--format", "{{json .}}"]).await?;
         let mut results = Vec::new();
         for line in out.lines().filter(|l| !l.trim().is_empty()) {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
@@ -54,10 +54,13 @@ pub async fn get_networks(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<DockerNe
 
 pub async fn rm_network(meta: &DockerMeta, _cwd: &Path, id: &str) -> Result<()> {
     if let Some(client) = &meta.client {
-        client.remove_network(id).await?;
+        let started = std::time::Instant::now();
+        let res = client.remove_network(id).await;
+        meta.history.record_api(&format!("docker network rm {id}"), started, &res);
+        res?;
         Ok(())
     } else {
-        let status = tokio::process::Command::new(&meta.docker_bin)
+        let status = meta.command()
             .current_dir(_cwd)
             .args(["network", "rm", id])
             .status()
@@ -2,7 +2,6 @@ use anyhow::{anyhow, Result};
 use std::path::Path;
 use std::process::Stdio;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
 
 use crate::docker::{cmd_out, DockerMeta, LogStream};
 
@@ -19,14 +18,14 @@ pub struct SwarmService {
 
 pub async fn list_swarm_services(meta: &DockerMeta, cwd: &Path) -> Vec<SwarmService> {
     let out = cmd_out(
-        &meta.docker_bin,
+        meta,
         cwd,
         &["service", "ls", "--format", "{{json .}}"],
     )
     .await
     .unwrap_or_default();
 
-    fn field<'a>(v: &'a serde_json::Value, keys: &[&str]) -> String {
+    fn field(v: &serde_json::Value, keys: &[&str]) -> String {
         for k in keys {
             if let Some(s) = v.get(k).and_then(|x| x.as_str()) {
                 if !s.is_empty() { return s.to_string(); }
@@ -71,7 +70,7 @@ pub async fn list_swarm_services(meta: &DockerMeta, cwd: &Path) -> Vec<SwarmServ
     }
 
     if services.is_empty() && !out.is_empty() {
-        if let Ok(txt) = cmd_out(&meta.docker_bin, cwd, &["service", "ls"]).await {
+        if let Ok(txt) = cmd_out(meta, cwd, &["service", "ls"]).await {
             for line in txt.lines().skip(1) {
                 let cols: Vec<&str> = line.split_whitespace().collect();
                 if cols.len() >= 4 {
@@ -100,11 +99,11 @@ pub async fn list_swarm_services(meta: &DockerMeta, cwd: &Path) -> Vec<SwarmServ
 }
 
 pub async fn cmd_inspect_service(meta: &DockerMeta, cwd: &Path, id: &str) -> Result<String> {
-    cmd_out(&meta.docker_bin, cwd, &["service", "inspect", "--pretty", id]).await
+    cmd_out(meta, cwd, &["service", "inspect", "--pretty", id]).await
 }
 
 pub async fn service_rolling_restart(meta: &DockerMeta, cwd: &Path, id: &str) -> Result<()> {
-    let status = Command::new(&meta.docker_bin)
+    let status = meta.command()
         .current_dir(cwd)
         .args(["service", "update", "--force", id])
         .status()
@@ -117,7 +116,7 @@ pub async fn service_rolling_restart(meta: &DockerMeta, cwd: &Path, id: &str) ->
 }
 
 pub async fn service_scale(meta: &DockerMeta, cwd: &Path, id: &str, replicas: usize) -> Result<()> {
-    let status = Command::new(&meta.docker_bin)
+    let status = meta.command()
         .current_dir(cwd)
         .args(["service", "scale", &format!("{}={}", id, replicas)])
         .status()
@@ -130,7 +129,7 @@ pub async fn service_scale(meta: &DockerMeta, cwd: &Path, id: &str, replicas: us
 }
 
 pub async fn service_rm(meta: &DockerMeta, cwd: &Path, id: &str) -> Result<()> {
-    let status = Command::new(&meta.docker_bin)
+    let status = meta.command()
         .current_dir(cwd)
         .args(["service", "rm", id])
         .status()
@@ -148,7 +147,7 @@ pub async fn stream_service_logs(
     id: &str,
     tail: usize,
 ) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
-    let mut child = Command::new(&meta.docker_bin)
+    let mut child = meta.command()
         .current_dir(cwd)
         .args(["service", "logs", "-f", "--tail", &tail.to_string(), id])
         .stdout(Stdio::piped())
@@ -183,21 +182,6 @@ pub async fn stream_service_logs(
     Ok((LogStream::Child(child), rx))
 }
 
-pub fn spawn_service_logs_follow(
-    meta: &DockerMeta,
-    cwd: &std::path::Path,
-    id: &str,
-    tail: usize,
-) -> Result<(LogStream, tokio::sync::mpsc::UnboundedReceiver<String>)> {
-    let rt = tokio::runtime::Handle::current();
-    tokio::task::block_in_place(|| {
-        rt.block_on(async {
-            let (s, rx) = stream_service_logs(meta, cwd, id, tail).await?;
-            Ok((s, rx))
-        })
-    })
-}
-
 pub async fn find_service_task_container(
     meta: &DockerMeta,
     cwd: &Path,
@@ -205,7 +189,7 @@ pub async fn find_service_task_container(
 ) -> Option<String> {
     // Try filtering by service ID label first
     let by_id = cmd_out(
-        &meta.docker_bin,
+        meta,
         cwd,
         &[
             "ps",
@@ -228,7 +212,7 @@ pub async fn find_service_task_container(
 
     // Fallback: filter by service name label
     let by_name = cmd_out(
-        &meta.docker_bin,
+        meta,
         cwd,
         &[
             "ps",
@@ -252,7 +236,7 @@ pub async fn find_service_task_container(
     // Last fallback: match by container name prefix (Swarm names containers as "<service>.<n>.<id>")
     // Also handles stack services named "<stack>_<service>.<n>.<id>"
     let all = cmd_out(
-        &meta.docker_bin,
+        meta,
         cwd,
         &["ps", "--filter", "status=running", "--format", "{{.ID}} {{.Names}}"],
     )
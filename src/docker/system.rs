@@ -1,7 +1,6 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use tokio::process::Command;
 use bollard::query_parameters::{PruneImagesOptions, PruneContainersOptions, PruneNetworksOptions, PruneVolumesOptions};
 
 use crate::docker::{cmd_out, DockerMeta};
@@ -17,24 +16,18 @@ pub struct DockerContext {
 }
 
 pub async fn list_contexts(meta: &DockerMeta, cwd: &Path) -> Vec<DockerContext> {
-    let out = cmd_out(&meta.docker_bin, cwd, &["context", "ls", "--format", "{{json .}}"]).await.unwrap_or_default();
+    let out = cmd_out(meta, cwd, &["context", "ls", "--format", "{{json .}}"]).await.unwrap_or_default();
     let mut results = Vec::new();
     for line in out.lines() {
-        if let Ok(ctx) = serde_json::from_str::<DockerContext>(line) {
+        if let Ok(mut ctx) = serde_json::from_str::<DockerContext>(line) {
+            // "Current" means the context this session targets, not the global default
+            ctx.current = ctx.name == meta.context_name;
             results.push(ctx);
         }
     }
     results
 }
 
-pub async fn use_context(meta: &DockerMeta, cwd: &Path, name: &str) -> Result<()> {
-    let out = Command::new(&meta.docker_bin).current_dir(cwd).args(["context", "use", name]).output().await?;
-    if !out.status.success() {
-        return Err(anyhow!("Failed to switch context: {}", String::from_utf8_lossy(&out.stderr)));
-    }
-    Ok(())
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SystemDfRow {
     pub kind: String,
@@ -95,7 +88,7 @@ pub async fn get_system_df(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<SystemD
     } else {
         // Fallback for system df: try to get something, but it's hard to parse without bollard
         // We'll just return an empty vec for now to avoid errors, or try to parse 'docker system df'
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["system", "df"]).await.unwrap_or_default();
+        let out = crate::docker::cmd_out(meta, _cwd, &["system", "df"]).await.unwrap_or_default();
         let mut rows = Vec::new();
         // Very basic parsing
         for line in out.lines() {
@@ -136,7 +129,7 @@ pub async fn system_prune(meta: &DockerMeta, _cwd: &Path) -> Result<String> {
         
         Ok(out)
     } else {
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["system", "prune", "-f"]).await?;
+        let out = crate::docker::cmd_out(meta, _cwd, &["system", "prune", "-f"]).await?;
         Ok(out)
     }
 }
@@ -2,7 +2,7 @@ use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use bollard::query_parameters::{ListVolumesOptions, RemoveVolumeOptions};
-use crate::docker::DockerMeta;
+use crate::docker::{container_name, ContainerSummary, DockerMeta, Port};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerVolume {
@@ -32,7 +32,7 @@ pub async fn get_volumes(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<DockerVol
         }
         Ok(results)
     } else {
-        let out = crate::docker::cmd_out(&meta.docker_bin, _cwd, &["volume", "ls", "--format", "{{json .}}"]).await?;
+        let out = crate::docker::cmd_out(meta, _cwd, &["volume", "ls", "--format", "{{json .}}"]).await?;
         let mut results = Vec::new();
         for line in out.lines().filter(|l| !l.trim().is_empty()) {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
@@ -47,19 +47,44 @@ pub async fn get_volumes(meta: &DockerMeta, _cwd: &Path) -> Result<Vec<DockerVol
     }
 }
 
+/// Names of the containers mounting `volume`. Mount names cut with `…` by
+/// the CLI listing match on the part that is left.
+pub fn volume_used_by(containers: &[(ContainerSummary, Vec<Port>)], volume: &str) -> Vec<String> {
+    containers.iter()
+        .filter(|(c, _)| c.mounts.iter().any(|m| match m.strip_suffix('…') {
+            Some(prefix) => volume.starts_with(prefix),
+            None => m == volume,
+        }))
+        .map(|(c, _)| container_name(&c.names))
+        .collect()
+}
+
+/// `docker volume inspect <name>`, pretty-printed.
+pub async fn inspect_volume(meta: &DockerMeta, cwd: &Path, name: &str) -> Result<String> {
+    if let Some(client) = &meta.client {
+        let volume = client.inspect_volume(name).await?;
+        return Ok(serde_json::to_string_pretty(&volume)?);
+    }
+    crate::docker::cmd_out(meta, cwd, &["volume", "inspect", name]).await
+}
+
 pub async fn rm_volume(meta: &DockerMeta, _cwd: &Path, name: &str, force: bool) -> Result<()> {
     if let Some(client) = &meta.client {
         let options = Some(RemoveVolumeOptions {
             force,
         });
-        client.remove_volume(name, options).await?;
+        let started = std::time::Instant::now();
+        let res = client.remove_volume(name, options).await;
+        let flag = if force { " -f" } else { "" };
+        meta.history.record_api(&format!("docker volume rm{flag} {name}"), started, &res);
+        res?;
         Ok(())
     } else {
         let mut args = vec!["volume", "rm"];
         if force { args.push("-f"); }
         args.push(name);
         
-        let status = tokio::process::Command::new(&meta.docker_bin)
+        let status = meta.command()
             .current_dir(_cwd)
             .args(args)
             .status()
@@ -1,66 +1,343 @@
-use crate::config::TaskSpec;
+use crate::config::{ContainerAction, TaskSpec};
 use anyhow::Result;
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
-/// Load .env and optional .env.<profile> into process env.
-/// Also performs a few passes of ${VAR} and ${VAR:-default} expansion.
-pub fn load_env(cwd: &Path, profile: Option<&str>) -> Result<Vec<String>> {
+/// Files read by [`load_env`] and the problems met while expanding them.
+#[derive(Debug, Default)]
+pub struct LoadedEnv {
+    pub files: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Settings file read from the project root, below the environment.
+pub const CONFIG_FILE: &str = "docker-cli.toml";
+
+/// Load .env, then `docker-cli.toml`, then optional .env.<profile> into
+/// process env. Variables already set win over the TOML file.
+/// Also expands `$VAR`, `${VAR}`, `${VAR:-default}`, `${VAR:+alt}` and
+/// `${VAR:?message}` in the values those files set (`$$` is a literal `$`).
+pub fn load_env(cwd: &Path, profile: Option<&str>) -> Result<LoadedEnv> {
+    load(cwd, profile, false)
+}
+
+/// Like [`load_env`], but values from `.env` replace the ones already in the
+/// process env, so edits made while the tool runs take effect.
+pub fn reload_env(cwd: &Path, profile: &str) -> Result<LoadedEnv> {
+    load(cwd, Some(profile), true)
+}
+
+/// Profile named by a file in the project root: `.env.<profile>`, or
+/// `docker-compose.<profile>.yml` / `compose.<profile>.yml` (`.yaml` too).
+/// Templates and overrides (`.env.example`, `docker-compose.override.yml`…)
+/// do not name a profile.
+pub fn profile_from_file_name(name: &str) -> Option<&str> {
+    const NOT_PROFILES: [&str; 6] = ["example", "sample", "template", "dist", "defaults", "override"];
+    let profile = match name.strip_prefix(".env.") {
+        Some(profile) => profile,
+        None => {
+            let stem = name.strip_suffix(".yml").or_else(|| name.strip_suffix(".yaml"))?;
+            stem.strip_prefix("docker-compose.").or_else(|| stem.strip_prefix("compose."))?
+        }
+    };
+    let valid = !profile.is_empty()
+        && profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !NOT_PROFILES.contains(&profile.to_lowercase().as_str());
+    valid.then_some(profile)
+}
+
+/// The file `Ctrl+E` edits: `.env.<profile>` when present, else `.env`.
+pub fn editable_env_file(cwd: &Path, profile: &str) -> std::path::PathBuf {
+    let prof = cwd.join(format!(".env.{}", profile.trim()));
+    if !profile.trim().is_empty() && prof.exists() {
+        prof
+    } else {
+        cwd.join(".env")
+    }
+}
+
+fn load(cwd: &Path, profile: Option<&str>, override_base: bool) -> Result<LoadedEnv> {
     let mut loaded: Vec<String> = Vec::new();
+    let mut set = Vec::new();
+    let mut warnings = Vec::new();
 
     let base = cwd.join(".env");
-    if base.exists() {
-        dotenvy::from_path(&base).ok();
+    if let Some(raw) = read_file_if_exists(&base) {
+        apply_dotenv(".env", &raw, override_base, &mut set, &mut warnings);
         loaded.push(".env".to_string());
     }
 
+    if let Some(raw) = read_file_if_exists(&cwd.join(CONFIG_FILE)) {
+        match file_config_vars(&raw) {
+            Ok(vars) => {
+                for (key, value) in vars {
+                    if std::env::var_os(&key).is_none() {
+                        std::env::set_var(key, value);
+                    }
+                }
+            }
+            Err(e) => warnings.push(format!("{CONFIG_FILE}: {e}")),
+        }
+        loaded.push(CONFIG_FILE.to_string());
+    }
+
     if let Some(profile) = profile {
         let prof = profile.trim();
         if !prof.is_empty() {
-            let pf = cwd.join(format!(".env.{prof}"));
-            if pf.exists() {
-                dotenvy::from_path_override(&pf).ok();
-                loaded.push(format!(".env.{prof}"));
+            let name = format!(".env.{prof}");
+            if let Some(raw) = read_file_if_exists(&cwd.join(&name)) {
+                apply_dotenv(&name, &raw, true, &mut set, &mut warnings);
+                loaded.push(name);
             } else {
                 // JS implementation pushes even if missing; we don't.
             }
         }
     }
 
-    // Multi-pass expansion for ${VAR} and ${VAR:-default}
-    for _pass in 0..5 {
-        let keys: Vec<String> = std::env::vars().map(|(k, _)| k).collect();
-        let mut changes = 0;
+    warnings.extend(expand_loaded(set));
 
-        for key in keys {
-            if let Ok(val) = std::env::var(&key) {
-                if !val.contains("${") {
+    let uniq: Vec<String> = loaded.into_iter().collect::<HashSet<_>>().into_iter().collect();
+    Ok(LoadedEnv { files: uniq, warnings })
+}
+
+/// Environment variables set by a `docker-cli.toml`. Each key names the
+/// variable in lower case (`max_log_lines = 2000` is `MAX_LOG_LINES`); arrays
+/// are joined with commas, or one entry per line for `post_up_tasks` and
+/// `container_actions`. `[tasks]` maps task names to commands, `[keys]`
+/// action names to keys (`restart = "R"` is `KEY_RESTART`), and
+/// `[profiles.<name>]` holds per-profile values (`MAX_LOG_LINES_<NAME>`).
+pub fn file_config_vars(raw: &str) -> Result<Vec<(String, String)>> {
+    fn collect(table: &toml::Table, profile: &str, vars: &mut Vec<(String, String)>) -> Result<()> {
+        for (key, value) in table {
+            let var = match key.as_str() {
+                "tasks" => "POST_UP_TASKS".to_string(),
+                _ => key.to_uppercase(),
+            };
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => value.to_string(),
+                toml::Value::Array(items) => {
+                    let items = items.iter()
+                        .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+                        .collect::<Vec<_>>();
+                    let sep = if matches!(var.as_str(), "POST_UP_TASKS" | "CONTAINER_ACTIONS") { "\n" } else { "," };
+                    items.join(sep)
+                }
+                toml::Value::Table(tasks) if var == "POST_UP_TASKS" => tasks.iter()
+                    .map(|(name, cmd)| match cmd.as_str() {
+                        Some(cmd) => Ok(format!("{name}::{cmd}")),
+                        None => Err(anyhow::anyhow!("tasks.{name} must be a command string")),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join("\n"),
+                toml::Value::Table(keys) if key == "keys" => {
+                    for (action, value) in keys {
+                        let Some(value) = value.as_str() else {
+                            anyhow::bail!("keys.{action} must be a string");
+                        };
+                        vars.push((key_for_profile(&format!("KEY_{}", action.to_uppercase()), profile), value.to_string()));
+                    }
                     continue;
                 }
-                let new_val = expand_value(&key, &val);
-                if new_val != val {
-                    std::env::set_var(&key, new_val);
-                    changes += 1;
+                toml::Value::Table(inner) if key == "profiles" && profile.is_empty() => {
+                    for (name, values) in inner {
+                        let Some(values) = values.as_table() else {
+                            anyhow::bail!("profiles.{name} must be a table");
+                        };
+                        collect(values, name, vars)?;
+                    }
+                    continue;
+                }
+                _ => anyhow::bail!("unsupported value for {key}"),
+            };
+            vars.push((key_for_profile(&var, profile), value));
+        }
+        Ok(())
+    }
+
+    let table: toml::Table = raw.parse()?;
+    let mut vars = Vec::new();
+    collect(&table, "", &mut vars)?;
+    Ok(vars)
+}
+
+/// Set the variables of the `.env` file `name`. Variables already in the
+/// environment are kept unless `override_existing`, or unless this load set
+/// them (so a later file, or a later line, wins). `set` collects the keys
+/// written, for [`expand_loaded`].
+fn apply_dotenv(name: &str, raw: &str, override_existing: bool, set: &mut Vec<String>, warnings: &mut Vec<String>) {
+    let (vars, problems) = parse_dotenv(raw);
+    warnings.extend(problems.into_iter().map(|p| format!("{name}:{p}")));
+    for (key, value) in vars {
+        if override_existing || set.contains(&key) || std::env::var_os(&key).is_none() {
+            std::env::set_var(&key, value);
+            if !set.contains(&key) {
+                set.push(key);
+            }
+        }
+    }
+}
+
+/// `KEY=value` lines of a `.env` file, with the values left unexpanded: a
+/// `$` that must stay literal (single quotes, `\$` in double quotes) comes
+/// back as `$$`. Double-quoted values may span lines and take `\n`, `\t`,
+/// `\"` and `\\` escapes; ` #` starts a comment after an unquoted value.
+/// A line that cannot be read is reported (`<line>: <problem>`) and skipped.
+fn parse_dotenv(raw: &str) -> (Vec<(String, String)>, Vec<String>) {
+    let mut vars = Vec::new();
+    let mut problems = Vec::new();
+    let mut lines = raw.lines().enumerate();
+    while let Some((n, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            problems.push(format!("{}: expected KEY=value", n + 1));
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+            problems.push(format!("{}: invalid name {key:?}", n + 1));
+            continue;
+        }
+        let value = value.trim_start();
+        let parsed = match value.chars().next() {
+            Some(quote @ ('\'' | '"')) => {
+                let mut text = value[1..].to_string();
+                loop {
+                    if let Some(v) = unquote(&text, quote) {
+                        break Some(v);
+                    }
+                    match lines.next() {
+                        Some((_, more)) => {
+                            text.push('\n');
+                            text.push_str(more);
+                        }
+                        None => break None,
+                    }
                 }
             }
+            _ => {
+                let end = value.find(" #").or_else(|| value.find("\t#")).unwrap_or(value.len());
+                Some(value[..end].trim_end().to_string())
+            }
+        };
+        match parsed {
+            Some(value) => vars.push((key.to_string(), value)),
+            None => problems.push(format!("{}: unterminated quote in {key}", n + 1)),
         }
-        if changes == 0 {
+    }
+    (vars, problems)
+}
+
+/// The value of `text` up to the closing `quote`, `None` if it has none yet.
+fn unquote(text: &str, quote: char) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == quote => return Some(out),
+            '$' if quote == '\'' => out.push_str("$$"),
+            '\\' if quote == '"' => match chars.next()? {
+                'n' => out.push('\n'),
+                't' => out.push('\t'),
+                'r' => out.push('\r'),
+                '$' => out.push_str("$$"),
+                c @ ('"' | '\\') => out.push(c),
+                c => {
+                    out.push('\\');
+                    out.push(c);
+                }
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+/// Expand the variables just set by [`apply_dotenv`] (`keys`), in place.
+/// Variables set by the shell were not written, so their values are never
+/// expanded, and an escaped `$$` is expanded exactly once.
+fn expand_loaded(keys: Vec<String>) -> Vec<String> {
+    let mut pending: Vec<String> = keys
+        .into_iter()
+        .filter(|key| std::env::var(key).is_ok_and(|v| v.contains('$')))
+        .collect();
+
+    // Expand each key once, after the pending keys it references, so chains
+    // like A=${B}, B=${C} resolve. Whatever is left when no key is ready is a
+    // cycle and gets expanded as-is.
+    let refs_of = |key: &str| {
+        let mut refs = Vec::new();
+        expand_with(key, &std::env::var(key).unwrap_or_default(), &mut |name| {
+            refs.push(name.to_string());
+            None
+        }, &mut Vec::new());
+        refs
+    };
+    let mut warnings = Vec::new();
+    while !pending.is_empty() {
+        let ready: Vec<String> = pending
+            .iter()
+            .filter(|key| refs_of(key).iter().all(|r| r == *key || !pending.contains(r)))
+            .cloned()
+            .collect();
+        if ready.is_empty() {
             break;
         }
+        for key in &ready {
+            let val = std::env::var(key).unwrap_or_default();
+            std::env::set_var(key, expand_value(key, &val, &mut warnings));
+        }
+        pending.retain(|k| !ready.contains(k));
+    }
+    for key in &pending {
+        let through: Vec<String> = refs_of(key).into_iter().filter(|r| r != key && pending.contains(r)).collect();
+        warnings.push(format!("{key}: circular reference through {}, left unresolved", through.join(", ")));
     }
+    for key in pending {
+        let val = std::env::var(&key).unwrap_or_default();
+        std::env::set_var(&key, expand_value(&key, &val, &mut warnings));
+    }
+    warnings
+}
 
-    let uniq: Vec<String> = loaded.into_iter().collect::<HashSet<_>>().into_iter().collect();
-    Ok(uniq)
+fn expand_value(current_key: &str, input: &str, warnings: &mut Vec<String>) -> String {
+    expand_with(current_key, input, &mut |name| std::env::var(name).ok(), warnings)
 }
 
-fn expand_value(current_key: &str, input: &str) -> String {
-    // Regex-free small parser: replace occurrences of ${NAME} or ${NAME:-default}
+/// Small parser for `$NAME`, `${NAME}`, `${NAME:-default}`, `${NAME:+alt}`
+/// and `${NAME:?message}`; `$$` yields a literal `$`. As in compose, the
+/// colon forms treat an empty value like an unset one. References to
+/// `current_key` itself resolve to nothing.
+fn expand_with(
+    current_key: &str,
+    input: &str,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+    warnings: &mut Vec<String>,
+) -> String {
+    let is_ident = |c: char, first: bool| c == '_' || c.is_ascii_alphabetic() || (!first && c.is_ascii_digit());
     let mut out = String::new();
     let chars: Vec<char> = input.chars().collect();
     let mut i = 0;
     while i < chars.len() {
-        if chars[i] == '$' && i + 1 < chars.len() && chars[i + 1] == '{' {
+        if chars[i] != '$' || i + 1 >= chars.len() {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        if chars[i + 1] == '$' {
+            out.push('$');
+            i += 2;
+            continue;
+        }
+
+        let (name, op, next) = if chars[i + 1] == '{' {
             // find closing }
             let mut j = i + 2;
             while j < chars.len() && chars[j] != '}' {
@@ -72,33 +349,48 @@ fn expand_value(current_key: &str, input: &str) -> String {
                 continue;
             }
             let inner: String = chars[i + 2..j].iter().collect();
-            let (name, def) = if let Some(pos) = inner.find(":-") {
-                (inner[..pos].to_string(), Some(inner[pos + 2..].to_string()))
-            } else {
-                (inner, None)
-            };
-
-            let mut resolved: Option<String> = None;
-            if name != current_key {
-                if let Ok(v) = std::env::var(&name) {
-                    if !v.is_empty() {
-                        resolved = Some(v);
-                    }
-                }
+            let op = inner.find(':').and_then(|pos| {
+                let kind = inner[pos + 1..].chars().next().filter(|c| matches!(c, '-' | '+' | '?'))?;
+                Some((pos, kind))
+            });
+            match op {
+                Some((pos, kind)) => (inner[..pos].to_string(), Some((kind, inner[pos + 2..].to_string())), j + 1),
+                None => (inner, None, j + 1),
             }
-
-            if let Some(v) = resolved {
-                out.push_str(&v);
-            } else if let Some(d) = def {
-                out.push_str(&d);
+        } else if is_ident(chars[i + 1], true) {
+            let mut j = i + 1;
+            while j < chars.len() && is_ident(chars[j], false) {
+                j += 1;
             }
-
-            i = j + 1;
+            (chars[i + 1..j].iter().collect(), None, j)
+        } else {
+            out.push(chars[i]);
+            i += 1;
             continue;
-        }
+        };
 
-        out.push(chars[i]);
-        i += 1;
+        let resolved = if name != current_key {
+            lookup(&name).filter(|v| !v.is_empty())
+        } else {
+            None
+        };
+        match (resolved, op) {
+            (Some(_), Some(('+', alt))) => out.push_str(&alt),
+            (Some(v), _) => out.push_str(&v),
+            (None, Some(('-', def))) => out.push_str(&def),
+            (None, Some(('?', msg))) => {
+                let msg = if msg.is_empty() { "not set".to_string() } else { msg };
+                warnings.push(format!("{current_key}: ${{{name}}} {msg}"));
+            }
+            (None, Some(_)) => {}
+            (None, None) if name == current_key => {
+                warnings.push(format!("{current_key}: references itself, using an empty string"));
+            }
+            (None, None) => {
+                warnings.push(format!("{current_key}: ${{{name}}} is not set, using an empty string"));
+            }
+        }
+        i = next;
     }
     out
 }
@@ -141,25 +433,421 @@ pub fn parse_post_up_tasks(raw: &str) -> Vec<TaskSpec> {
             continue;
         }
         if let Some(idx) = line.find("::") {
-            let name = line[..idx].trim();
+            let (name, attrs) = split_attributes(line[..idx].trim());
             let cmd = line[idx + 2..].trim();
             if !cmd.is_empty() {
                 tasks.push(TaskSpec {
                     name: if name.is_empty() { "task".to_string() } else { name.to_string() },
                     cmd: cmd.to_string(),
+                    every: attrs.every,
+                    plain: attrs.plain,
+                    service: attrs.service,
                 });
             }
         } else {
             tasks.push(TaskSpec {
                 name: "task".to_string(),
                 cmd: line.to_string(),
+                every: None,
+                plain: false,
+                service: None,
             });
         }
     }
     tasks
 }
 
-#[allow(dead_code)]
+/// Break a task command into display lines. A multi-line command (heredoc,
+/// `\` continuations) keeps its own lines and indentation; a one-liner is
+/// split before each top-level `&&`, `||` and `;` (outside quotes), e.g.
+/// `cd web`, `&& npm ci`, `&& npm run dev`.
+pub fn command_lines(cmd: &str) -> Vec<String> {
+    let cmd = cmd.trim_matches('\n');
+    if cmd.contains('\n') {
+        return cmd.lines().map(str::trim_end).filter(|l| !l.trim().is_empty()).map(str::to_string).collect();
+    }
+    let mut lines = Vec::new();
+    let mut cur = String::new();
+    let mut quote: Option<char> = None;
+    let mut chars = cmd.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '&' | '|') if chars.peek() == Some(&c) => {
+                chars.next();
+                lines.push(std::mem::take(&mut cur).trim().to_string());
+                cur = format!("{c}{c} ");
+                continue;
+            }
+            (None, ';') => {
+                lines.push(std::mem::take(&mut cur).trim().to_string());
+                cur = "; ".to_string();
+                continue;
+            }
+            _ => {}
+        }
+        // Drop the spaces between an operator and the next word.
+        if c == ' ' && cur.ends_with(' ') && cur.trim().len() <= 2 {
+            continue;
+        }
+        cur.push(c);
+    }
+    lines.push(cur.trim().to_string());
+    lines.retain(|l| !l.is_empty() && l != ";");
+    lines
+}
+
+/// The program a task command mainly runs, for the sidebar: env assignments,
+/// `cd` steps and wrappers (`sh -c`, `exec`, `env`…) are skipped and paths
+/// reduced to their file name. `npx vite --host` gives `vite`.
+pub fn command_program(cmd: &str) -> String {
+    const WRAPPERS: [&str; 9] = ["exec", "env", "sudo", "time", "nohup", "npx", "sh", "bash", "-c"];
+    for line in command_lines(cmd) {
+        let line = line.trim_start_matches(['&', '|', ';']).trim();
+        let words = crate::docker::split_args(line);
+        let mut words = words.iter().skip_while(|w| w.contains('=') && !w.starts_with('-'));
+        let Some(first) = words.clone().next() else { continue };
+        if first == "cd" {
+            continue;
+        }
+        // `sh -c "cd x && make"`: look inside the script.
+        if matches!(first.as_str(), "sh" | "bash") {
+            if let Some(script) = words.clone().skip_while(|w| *w != "-c").nth(1) {
+                return command_program(script);
+            }
+        }
+        if let Some(program) = words.find(|w| !WRAPPERS.contains(&w.as_str())) {
+            return program.rsplit('/').next().unwrap_or(program).to_string();
+        }
+    }
+    String::new()
+}
+
+/// Parse `CONTAINER_ACTIONS`: one `name (key: b)::cmd` per line. Lines
+/// without a single-character key or a command are skipped.
+pub fn parse_container_actions(raw: &str) -> Vec<ContainerAction> {
+    let mut actions = Vec::new();
+    for line in raw.lines().map(|l| l.trim()).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let Some((head, cmd)) = line.split_once("::") else {
+            continue;
+        };
+        let Some(open) = head.find('(') else {
+            continue;
+        };
+        let attr = head[open + 1..].trim_end().trim_end_matches(')').trim();
+        let key = attr
+            .strip_prefix("key")
+            .map(|rest| rest.trim_start().trim_start_matches(':').trim());
+        let mut chars = key.unwrap_or_default().chars();
+        let (Some(key), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let name = head[..open].trim();
+        let cmd = cmd.trim();
+        if !name.is_empty() && !cmd.is_empty() {
+            actions.push(ContainerAction { name: name.to_string(), key, cmd: cmd.to_string() });
+        }
+    }
+    actions
+}
+
+/// Attributes in parentheses after a task name, e.g.
+/// `(every: 30s, plain, service: db)`.
+#[derive(Default)]
+struct TaskAttributes {
+    every: Option<Duration>,
+    plain: bool,
+    service: Option<String>,
+}
+
+/// Split `warm-cache (every: 30s, plain)` into the task name and its
+/// attributes. An unparsable interval leaves the task unscheduled; unknown
+/// attributes are ignored.
+fn split_attributes(raw: &str) -> (&str, TaskAttributes) {
+    let mut parsed = TaskAttributes::default();
+    let Some(open) = raw.find('(') else {
+        return (raw, parsed);
+    };
+    let attrs = raw[open + 1..].trim_end().trim_end_matches(')');
+    for attr in attrs.split(',').map(str::trim) {
+        let (key, value) = match attr.split_once(':') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => (attr, ""),
+        };
+        match key {
+            "every" => parsed.every = parse_interval(value),
+            "plain" => parsed.plain = true,
+            "service" if !value.is_empty() => parsed.service = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    (raw[..open].trim(), parsed)
+}
+
+/// Parse intervals such as `30s`, `5m`, `1h`, `1h30m` or a bare `45`
+/// (seconds). Zero and malformed values yield `None`.
+pub fn parse_interval(raw: &str) -> Option<Duration> {
+    let text = raw.trim().to_lowercase();
+    if text.is_empty() {
+        return None;
+    }
+    if let Ok(secs) = text.parse::<u64>() {
+        return (secs > 0).then(|| Duration::from_secs(secs));
+    }
+
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in text.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            ' ' => {}
+            's' | 'm' | 'h' | 'd' => {
+                let n: u64 = digits.parse().ok()?;
+                digits.clear();
+                let unit = match c {
+                    's' => 1,
+                    'm' => 60,
+                    'h' => 3600,
+                    _ => 86_400,
+                };
+                total = total.checked_add(n.checked_mul(unit)?)?;
+            }
+            _ => return None,
+        }
+    }
+    if !digits.is_empty() || total == 0 {
+        return None;
+    }
+    Some(Duration::from_secs(total))
+}
+
 pub fn read_file_if_exists(p: &Path) -> Option<String> {
     fs::read_to_string(p).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_from_file_name() {
+        assert_eq!(profile_from_file_name(".env.staging"), Some("staging"));
+        assert_eq!(profile_from_file_name("docker-compose.prod.yml"), Some("prod"));
+        assert_eq!(profile_from_file_name("compose.dev.yaml"), Some("dev"));
+        assert_eq!(profile_from_file_name(".env"), None);
+        assert_eq!(profile_from_file_name(".env.example"), None);
+        assert_eq!(profile_from_file_name(".env.local.bak"), None);
+        assert_eq!(profile_from_file_name("docker-compose.yml"), None);
+        assert_eq!(profile_from_file_name("docker-compose.override.yml"), None);
+        assert_eq!(profile_from_file_name("docker-compose.prod.json"), None);
+    }
+
+    #[test]
+    fn test_file_config_vars() {
+        let vars = file_config_vars(r#"
+            db_container = "db"
+            max_log_lines = 2000
+            auto_compose_up = false
+            compose_files = ["docker-compose.yml", "docker-compose.dev.yml"]
+
+            [tasks]
+            migrate = "npm run migrate"
+            "seed (every: 5m)" = "npm run seed"
+
+            [keys]
+            restart = "b"
+
+            [profiles.staging]
+            docker_bin = "docker --context staging"
+            tasks = ["smoke::./smoke.sh"]
+        "#).unwrap();
+        let get = |k: &str| vars.iter().find(|(key, _)| key == k).map(|(_, v)| v.as_str());
+        assert_eq!(get("DB_CONTAINER"), Some("db"));
+        assert_eq!(get("MAX_LOG_LINES"), Some("2000"));
+        assert_eq!(get("AUTO_COMPOSE_UP"), Some("false"));
+        assert_eq!(get("COMPOSE_FILES"), Some("docker-compose.yml,docker-compose.dev.yml"));
+        assert_eq!(get("POST_UP_TASKS"), Some("migrate::npm run migrate\nseed (every: 5m)::npm run seed"));
+        assert_eq!(get("KEY_RESTART"), Some("b"));
+        assert_eq!(get("DOCKER_BIN_STAGING"), Some("docker --context staging"));
+        assert_eq!(get("POST_UP_TASKS_STAGING"), Some("smoke::./smoke.sh"));
+        assert!(file_config_vars("refresh_ms = ").is_err());
+        assert!(file_config_vars("[db]\nname = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_interval("5m"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_interval(" 1H "), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_interval("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_interval("45"), Some(Duration::from_secs(45)));
+        assert_eq!(parse_interval("0s"), None);
+        assert_eq!(parse_interval("10x"), None);
+        assert_eq!(parse_interval("m"), None);
+        assert_eq!(parse_interval("5"), Some(Duration::from_secs(5)));
+    }
+
+    fn expand_warn(input: &str) -> (String, Vec<String>) {
+        let mut warnings = Vec::new();
+        let out = expand_with("SELF", input, &mut |name| match name {
+            "FOO" => Some("/opt".to_string()),
+            "SELF" => Some("loop".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }, &mut warnings);
+        (out, warnings)
+    }
+
+    fn expand(input: &str) -> String {
+        expand_warn(input).0
+    }
+
+    #[test]
+    fn test_expand_value() {
+        assert_eq!(expand("$FOO/bar"), "/opt/bar");
+        assert_eq!(expand("${FOO}bar"), "/optbar");
+        assert_eq!(expand("$FOObar"), "");
+        assert_eq!(expand("${MISSING:-dflt}"), "dflt");
+        assert_eq!(expand("cost: $$5 and $$FOO"), "cost: $5 and $FOO");
+        assert_eq!(expand("$ 1 and trailing $"), "$ 1 and trailing $");
+        assert_eq!(expand("$SELF-${SELF}"), "-");
+    }
+
+    /// `load_env` on a project holding only a `.env` with `contents`.
+    fn load_dotenv(tag: &str, contents: &str) -> LoadedEnv {
+        let dir = std::env::temp_dir().join(format!("docker-cli-env-{tag}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".env"), contents).unwrap();
+        let loaded = load_env(&dir, None).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        loaded
+    }
+
+    #[test]
+    fn test_load_env_expands_file_values() {
+        std::env::set_var("T889_SHELL", "from shell");
+        let loaded = load_dotenv("expand", r#"
+            T889_HOST=db
+            T889_URL=postgres://$T889_HOST/${T889_HOST}_test
+            T889_PRICE=cost: $$5
+            export T889_SINGLE='$T889_HOST stays'
+            T889_DOUBLE="on $T889_HOST, \$HOME kept" # comment
+            T889_MULTI="a
+            b"
+            this line is broken
+            T889_SHELL=from file
+            T889_AFTER=${T889_SHELL}
+        "#);
+        let var = |k: &str| std::env::var(k).unwrap_or_default();
+        assert_eq!(var("T889_URL"), "postgres://db/db_test");
+        assert_eq!(var("T889_PRICE"), "cost: $5");
+        assert_eq!(var("T889_SINGLE"), "$T889_HOST stays");
+        assert_eq!(var("T889_DOUBLE"), "on db, $HOME kept");
+        assert_eq!(var("T889_MULTI"), "a\n            b");
+        assert_eq!(var("T889_SHELL"), "from shell");
+        assert_eq!(var("T889_AFTER"), "from shell");
+        assert_eq!(loaded.warnings, vec![".env:9: expected KEY=value".to_string()]);
+    }
+
+    #[test]
+    fn test_load_env_operators() {
+        let loaded = load_dotenv("operators", r#"
+            T890_SET=on
+            T890_DEFAULT=${T890_MISSING:-dflt}
+            T890_QUOTED_DEFAULT="${T890_MISSING:-two words}"
+            T890_ALT=${T890_SET:+alt}
+            T890_NO_ALT="${T890_MISSING:+alt}"
+            T890_REQUIRED=${T890_MISSING:?needs a value}
+            T890_REQUIRED_OK="${T890_SET:?unused}"
+        "#);
+        let var = |k: &str| std::env::var(k).unwrap_or_default();
+        assert_eq!(var("T890_DEFAULT"), "dflt");
+        assert_eq!(var("T890_QUOTED_DEFAULT"), "two words");
+        assert_eq!(var("T890_ALT"), "alt");
+        assert_eq!(var("T890_NO_ALT"), "");
+        assert_eq!(var("T890_REQUIRED"), "");
+        assert_eq!(var("T890_REQUIRED_OK"), "on");
+        assert_eq!(loaded.warnings, vec!["T890_REQUIRED: ${T890_MISSING} needs a value".to_string()]);
+    }
+
+    #[test]
+    fn test_expand_operators() {
+        // (input, expected output, warns) for set / unset / empty
+        let matrix = [
+            ("${FOO:-d}", "/opt", false),
+            ("${UNSET:-d}", "d", false),
+            ("${EMPTY:-d}", "d", false),
+            ("${FOO:+alt}", "alt", false),
+            ("${UNSET:+alt}", "", false),
+            ("${EMPTY:+alt}", "", false),
+            ("${FOO:?required}", "/opt", false),
+            ("${UNSET:?required}", "", true),
+            ("${EMPTY:?required}", "", true),
+        ];
+        for (input, expected, warns) in matrix {
+            let (out, warnings) = expand_warn(input);
+            assert_eq!(out, expected, "{input}");
+            assert_eq!(!warnings.is_empty(), warns, "{input}");
+        }
+        assert_eq!(expand_warn("${UNSET:?must be set}").1, vec!["SELF: ${UNSET} must be set"]);
+        assert_eq!(expand_warn("${UNSET:?}").1, vec!["SELF: ${UNSET} not set"]);
+    }
+
+    #[test]
+    fn test_expand_unresolved_warnings() {
+        assert!(expand_warn("$FOO ${UNSET:-x} ${UNSET:+y}").1.is_empty());
+        assert_eq!(expand_warn("$UNSET/x").1, vec!["SELF: ${UNSET} is not set, using an empty string"]);
+        assert_eq!(expand_warn("${SELF}").1, vec!["SELF: references itself, using an empty string"]);
+    }
+
+    #[test]
+    fn test_parse_scheduled_task() {
+        let tasks = parse_post_up_tasks("warm (every: 30s)::curl -s localhost\nplain::echo hi");
+        assert_eq!(tasks[0].name, "warm");
+        assert_eq!(tasks[0].every, Some(Duration::from_secs(30)));
+        assert_eq!(tasks[1].name, "plain");
+        assert_eq!(tasks[1].every, None);
+
+        let tasks = parse_post_up_tasks("web (plain)::npm run dev\nwarm (every: 5m, plain)::curl -s localhost");
+        assert_eq!((tasks[0].name.as_str(), tasks[0].every, tasks[0].plain), ("web", None, true));
+        assert_eq!((tasks[1].name.as_str(), tasks[1].every, tasks[1].plain), ("warm", Some(Duration::from_secs(300)), true));
+
+        let tasks = parse_post_up_tasks("migrate (service: db)::npm run migrate\nseed (service:)::echo");
+        assert_eq!(tasks[0].service.as_deref(), Some("db"));
+        assert_eq!(tasks[1].service, None);
+    }
+
+    #[test]
+    fn test_command_lines_and_program() {
+        assert_eq!(command_lines("cd web && npm ci && npm run dev"), ["cd web", "&& npm ci", "&& npm run dev"]);
+        assert_eq!(command_lines("make build || echo 'a && b'; true"), ["make build", "|| echo 'a && b'", "; true"]);
+        assert_eq!(command_lines("npm run dev"), ["npm run dev"]);
+        assert_eq!(command_lines("psql <<SQL\n  select 1; select 2;\nSQL\n"), ["psql <<SQL", "  select 1; select 2;", "SQL"]);
+        assert_eq!(command_program("cd web && PORT=3000 npm run dev"), "npm");
+        assert_eq!(command_program("exec ./node_modules/.bin/vite --host"), "vite");
+        assert_eq!(command_program("sh -c \"cd api && cargo watch -x run\""), "cargo");
+        assert_eq!(command_program(""), "");
+    }
+
+    #[test]
+    fn test_parse_container_actions() {
+        let actions = parse_container_actions(
+            "backup (key: b)::docker exec {{id}} pg_dump -U postgres > {{name}}.sql\n\
+             # comment\n\
+             nokey::echo skipped\n\
+             wide (key: ab)::echo skipped\n\
+             curl (key:Q)::curl -s localhost:{{port}}/health",
+        );
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].name, "backup");
+        assert_eq!(actions[0].key, 'b');
+        assert_eq!(
+            actions[0].render("abc123", "db", None).as_deref(),
+            Some("docker exec abc123 pg_dump -U postgres > db.sql")
+        );
+        assert_eq!(actions[1].key, 'Q');
+        assert_eq!(actions[1].render("x", "api", Some(8080)).as_deref(), Some("curl -s localhost:8080/health"));
+        assert_eq!(actions[1].render("x", "api", None), None);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::env::get_profile_value;
+
+/// Sidebar actions whose key can be changed with `KEY_<ACTION>` (or a
+/// `[keys]` table in `docker-cli.toml`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Restart,
+    Stop,
+    Start,
+    Kill,
+    Reset,
+    Compose,
+    Inspect,
+    Web,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::Restart, Action::Stop, Action::Start, Action::Kill,
+        Action::Reset, Action::Compose, Action::Inspect, Action::Web,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Restart => "restart",
+            Action::Stop => "stop",
+            Action::Start => "start",
+            Action::Kill => "kill",
+            Action::Reset => "reset",
+            Action::Compose => "compose",
+            Action::Inspect => "inspect",
+            Action::Web => "web",
+        }
+    }
+
+    /// The built-in key, which `handle_action` dispatches on.
+    pub fn default_key(self) -> char {
+        match self {
+            Action::Restart => 'r',
+            Action::Stop => 's',
+            Action::Start => 't',
+            Action::Kill => 'k',
+            Action::Reset => 'x',
+            Action::Compose => 'c',
+            Action::Inspect => 'i',
+            Action::Web => 'o',
+        }
+    }
+}
+
+/// Keys taken by shortcuts that cannot be rebound; binding an action to one
+/// of them is reported as a conflict.
+const BUILTIN_KEYS: &str = "qjgGnNvV CwWLHhTIEFfUaAYzZmM?!:;/+-@OBSpudyePRlKD0123456789";
+
+#[derive(Debug, Clone)]
+pub struct Keybindings {
+    keys: [char; Action::ALL.len()],
+    /// Problems found while reading the bindings, reported in the app log.
+    pub conflicts: Vec<String>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self { keys: Action::ALL.map(Action::default_key), conflicts: Vec::new() }
+    }
+}
+
+impl Keybindings {
+    /// Read `KEY_RESTART`, `KEY_STOP`… for `profile`.
+    pub fn from_env(profile: &str) -> Self {
+        Self::parse(|action| get_profile_value(&format!("KEY_{}", action.name().to_uppercase()), profile))
+    }
+
+    /// Bindings from `value` (empty for the default). A key that is not a
+    /// single character or belongs to a built-in shortcut keeps its default;
+    /// if two actions end up on the same key, every action keeps its default.
+    pub fn parse(value: impl Fn(Action) -> String) -> Self {
+        let mut bindings = Self::default();
+        for (i, action) in Action::ALL.into_iter().enumerate() {
+            let raw = value(action);
+            let raw = raw.trim();
+            if raw.is_empty() {
+                continue;
+            }
+            let mut chars = raw.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if BUILTIN_KEYS.contains(c) => bindings.conflicts.push(format!(
+                    "{} cannot use '{c}', already a built-in shortcut; keeping '{}'",
+                    action.name(), action.default_key()
+                )),
+                (Some(c), None) => bindings.keys[i] = c,
+                _ => bindings.conflicts.push(format!(
+                    "{} = \"{raw}\" is not a single key; keeping '{}'",
+                    action.name(), action.default_key()
+                )),
+            }
+        }
+        for (i, a) in Action::ALL.into_iter().enumerate() {
+            if let Some(b) = Action::ALL.into_iter().skip(i + 1).find(|b| bindings.key(*b) == bindings.key(a)) {
+                bindings.conflicts.push(format!(
+                    "{} and {} are both bound to '{}'; using the default keys",
+                    a.name(), b.name(), bindings.key(a)
+                ));
+                bindings.keys = Self::default().keys;
+                break;
+            }
+        }
+        bindings
+    }
+
+    /// Drop the `CONTAINER_ACTIONS` entries that could never run: those on
+    /// a built-in shortcut, on an action key (bound or left by a rebinding),
+    /// or on a key an earlier entry already took. Each is reported.
+    pub fn check_container_actions(&mut self, actions: &mut Vec<crate::config::ContainerAction>) {
+        let mut taken: Vec<char> = Vec::new();
+        actions.retain(|a| {
+            let owner = if BUILTIN_KEYS.contains(a.key) {
+                Some("a built-in shortcut".to_string())
+            } else if let Some(action) = Action::ALL.into_iter().find(|b| self.key(*b) == a.key || b.default_key() == a.key) {
+                Some(format!("the {} key", action.name()))
+            } else if taken.contains(&a.key) {
+                Some("an earlier CONTAINER_ACTIONS entry".to_string())
+            } else {
+                None
+            };
+            match owner {
+                Some(owner) => {
+                    self.conflicts.push(format!("CONTAINER_ACTIONS {} cannot use '{}', already {owner}; skipped", a.name, a.key));
+                    false
+                }
+                None => {
+                    taken.push(a.key);
+                    true
+                }
+            }
+        });
+    }
+
+    pub fn key(&self, action: Action) -> char {
+        let i = Action::ALL.iter().position(|a| *a == action).expect("listed in ALL");
+        self.keys[i]
+    }
+
+    /// The built-in key to dispatch for a pressed `c`: the default key of
+    /// the action bound to it, `None` for the old key of a rebound action,
+    /// and `c` itself otherwise.
+    pub fn dispatch(&self, c: char) -> Option<char> {
+        if let Some(action) = Action::ALL.into_iter().find(|a| self.key(*a) == c) {
+            return Some(action.default_key());
+        }
+        if Action::ALL.iter().any(|a| a.default_key() == c) {
+            return None;
+        }
+        Some(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(pairs: &[(Action, &str)]) -> Keybindings {
+        Keybindings::parse(|a| pairs.iter().find(|(b, _)| *b == a).map(|(_, v)| v.to_string()).unwrap_or_default())
+    }
+
+    #[test]
+    fn test_keybindings() {
+        let swapped = bindings(&[(Action::Restart, "s"), (Action::Stop, "r"), (Action::Web, "b")]);
+        assert!(swapped.conflicts.is_empty());
+        assert_eq!(swapped.dispatch('s'), Some('r'));
+        assert_eq!(swapped.dispatch('r'), Some('s'));
+        assert_eq!(swapped.dispatch('b'), Some('o'));
+        assert_eq!(swapped.dispatch('o'), None);
+        assert_eq!(swapped.dispatch('e'), Some('e'));
+
+        let builtin = bindings(&[(Action::Restart, "q"), (Action::Kill, "ctrl+k")]);
+        assert_eq!(builtin.conflicts.len(), 2);
+        assert_eq!((builtin.key(Action::Restart), builtin.key(Action::Kill)), ('r', 'k'));
+
+        let clash = bindings(&[(Action::Restart, "b"), (Action::Stop, "b")]);
+        assert_eq!(clash.conflicts.len(), 1);
+        assert_eq!((clash.key(Action::Restart), clash.key(Action::Stop)), ('r', 's'));
+
+        let mut custom = swapped.clone();
+        let mut actions = crate::env::parse_container_actions(
+            "quit (key: q)::echo\nweb (key: b)::echo\nold web (key: o)::echo\nbackup (key: Q)::echo\nagain (key: Q)::echo",
+        );
+        custom.check_container_actions(&mut actions);
+        assert_eq!(actions.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), ["backup"]);
+        assert_eq!(custom.conflicts.len(), 4);
+        assert!(custom.conflicts[1].contains("already the web key"));
+    }
+}
@@ -3,12 +3,25 @@ mod env;
 mod docker;
 mod tasks;
 mod pins;
+mod bookmarks;
+mod keybindings;
 mod ui;
 
 use anyhow::Result;
+use std::io::IsTerminal;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // The TUI needs a real terminal: raw mode and the alternate screen make a
+    // mess of pipes and CI logs, so bail out before touching either.
+    if !std::io::stdout().is_terminal() {
+        eprintln!(
+            "docker-cli is an interactive TUI and stdout is not a terminal (piped or CI).\n\
+             Run it from a terminal, or use `docker ps` / `docker compose ps` for scripted output."
+        );
+        std::process::exit(2);
+    }
+
     // Install a panic hook that restores the terminal BEFORE printing the
     // panic message, so it doesn't disappear inside the alternate screen.
     let default_hook = std::panic::take_hook();
@@ -24,19 +37,35 @@ async fn main() -> Result<()> {
     }));
 
     let start_dir = std::env::current_dir()?;
-    let root = config::find_project_root(&start_dir);
+    let root = if std::env::var_os("COMPOSE_ROOT").is_some() {
+        config::find_project_root(&start_dir)
+    } else {
+        let candidates = config::compose_root_candidates(&start_dir);
+        if candidates.len() > 1 {
+            let root = choose_compose_root(&start_dir, &candidates)?;
+            // get_config() resolves the root again later; pin it to the choice.
+            std::env::set_var("COMPOSE_ROOT", &root);
+            root
+        } else {
+            config::find_project_root(&start_dir)
+        }
+    };
 
     // Preload base .env (profile-specific env is loaded after profile selection)
     // Ignore errors when outside a project — .env is optional
-    let _ = env::load_env(&root, None);
+    let env_warnings = env::load_env(&root, None).map(|l| l.warnings).unwrap_or_default();
 
-    let docker_bin = config::resolve_docker_binary();
+    // Only an explicit profile is known this early; ui::run re-detects if the
+    // chosen profile has its own DOCKER_BIN_<PROFILE>.
+    let early_profile = std::env::var("DOCKER_PROFILE").or_else(|_| std::env::var("COMPOSE_PROFILE")).unwrap_or_default();
+    let docker_bin = config::resolve_docker_binary(&early_profile);
     let docker_meta = docker::DockerMeta::detect(&root, &docker_bin).await;
 
     if let Err(e) = ui::run(ui::RunOpts {
         root,
         docker_bin,
         docker_meta,
+        env_warnings,
     })
     .await
     {
@@ -53,3 +82,34 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Plain-terminal prompt (before the TUI starts) for monorepos with several
+/// compose projects. Enter keeps the first candidate.
+fn choose_compose_root(start_dir: &std::path::Path, candidates: &[std::path::PathBuf]) -> Result<std::path::PathBuf> {
+    use std::io::Write;
+
+    println!("Several compose projects found (set COMPOSE_ROOT to skip this prompt):");
+    for (i, dir) in candidates.iter().enumerate() {
+        let shown = match dir.strip_prefix(start_dir) {
+            Ok(rel) if rel.as_os_str().is_empty() => ".".to_string(),
+            Ok(rel) => rel.display().to_string(),
+            Err(_) => dir.display().to_string(),
+        };
+        println!("  {}) {shown}", i + 1);
+    }
+    loop {
+        print!("Choose [1-{}] (Enter = 1): ", candidates.len());
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            return Ok(candidates[0].clone());
+        }
+        match line.trim() {
+            "" => return Ok(candidates[0].clone()),
+            n => match n.parse::<usize>() {
+                Ok(i) if (1..=candidates.len()).contains(&i) => return Ok(candidates[i - 1].clone()),
+                _ => println!("Please enter a number between 1 and {}.", candidates.len()),
+            },
+        }
+    }
+}
@@ -40,7 +40,7 @@ fn spawn_shell(cmd: &str, cwd: &std::path::Path) -> Result<Child> {
     unsafe {
         c.pre_exec(|| {
             nix::unistd::setpgid(nix::unistd::Pid::from_raw(0), nix::unistd::Pid::from_raw(0))
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+                .map_err(std::io::Error::other)?;
             Ok(())
         });
     }
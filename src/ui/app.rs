@@ -1,14 +1,57 @@
-use crate::config::Config;
+use crate::config::{Config, ContainerAction, TaskSpec};
 use crate::docker;
 use crate::pins;
+use crate::bookmarks::{self, Bookmark};
 use crate::tasks::{self, TaskStatus};
-use crate::ui::types::{SidebarKind, UiItem, TaskRuntime, Popup};
-use anyhow::{Result};
+use crate::ui::draw::utils::fit_name;
+use crate::ui::types::{SidebarKind, UiItem, TaskRuntime, Popup, LogRecording, ForegroundJob, ComposeJob, IDLE_AFTER, TaskStream, strip_stream_tags};
+use anyhow::{anyhow, Result};
 use ratatui::layout::{Rect};
 use std::collections::{HashMap, HashSet, VecDeque};
 use tokio::sync::mpsc;
 use std::path::PathBuf;
 
+/// Lines of history requested when a log follower starts.
+pub const DEFAULT_LOG_TAIL: usize = 200;
+const MIN_LOG_TAIL: usize = 10;
+/// Start of the divider put between a container's old lines and those it
+/// logged while another target was on screen.
+pub const NEW_SINCE_MARKER: &str = "──── new since";
+/// How long a container that just restarted keeps flashing in the sidebar.
+pub const RESTART_FLASH: std::time::Duration = std::time::Duration::from_secs(10);
+/// How long `g` and typed digits wait for the next digit of a quick jump.
+pub const QUICK_JUMP_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(1500);
+/// How long the recovery hint replaces the help bar after a destructive action.
+pub const RECOVERY_HINT_FOR: std::time::Duration = std::time::Duration::from_secs(10);
+/// Sidebar id of the app log entry.
+pub const APP_LOG_ID: &str = "__app_log__";
+pub const STACK_LOGS_ID: &str = "__stack_logs__";
+/// Sidebar group of containers without a `com.docker.compose.project` label.
+pub const STANDALONE_GROUP: &str = "(standalone)";
+
+/// Sidebar badge for a container's state; a running container failing its
+/// healthcheck gets its own so it stands out.
+pub fn container_badge(c: &docker::ContainerSummary) -> &'static str {
+    match c.state.to_lowercase().as_str() {
+        "running" if c.health == "unhealthy" => "🟠",
+        "running" => "🟢",
+        "paused" => "🟡",
+        "restarting" => "🔵",
+        "exited" | "dead" => "🔴",
+        _ => "⚪️",
+    }
+}
+
+/// `  ↻3` for a container that restarted, so crash loops show in the list.
+fn restarts_suffix(c: &docker::ContainerSummary) -> String {
+    if c.restart_count > 0 { format!("  ↻{}", c.restart_count) } else { String::new() }
+}
+
+/// Containers `w` hides: not running and not about to be.
+pub fn is_stopped(c: &docker::ContainerSummary) -> bool {
+    matches!(c.state.to_lowercase().as_str(), "exited" | "dead" | "created")
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum SortBy {
     Name,
@@ -29,6 +72,8 @@ pub struct RunOpts {
     #[allow(dead_code)]
     pub docker_bin: String,
     pub docker_meta: docker::DockerMeta,
+    /// Expansion warnings from the base `.env` preload.
+    pub env_warnings: Vec<String>,
 }
 
 pub struct App {
@@ -41,18 +86,55 @@ pub struct App {
 
     pub current_target: String,
     pub log_lines: VecDeque<String>,
+    /// Bytes held by `log_lines`, updated on every change so trimming to
+    /// `MAX_LOG_BYTES` does not sum the buffer.
+    pub log_bytes: usize,
+    /// Colour of each container prefix in aggregate log views, hashed from
+    /// the name so it stays the same across views and sessions.
+    pub prefix_colors: HashMap<String, ratatui::style::Color>,
+    /// Tool-level messages, kept apart from container logs (see `push_app_log`).
+    pub app_log: VecDeque<String>,
+    /// Bytes held by `app_log`, like `log_bytes` for the pane.
+    pub app_log_bytes: usize,
+    /// Tasks that failed while another target was on screen, badged in the
+    /// sidebar until they are selected.
+    pub unseen_failures: HashSet<String>,
+    /// When each container's live logs were last left, so coming back can
+    /// mark where the lines written meanwhile start.
+    pub container_left_at: HashMap<String, chrono::DateTime<chrono::Local>>,
+    /// Bytes cut so far from the line `push_partial_log` is filling, past
+    /// `MAX_LINE_BYTES`; reported by a marker when the line ends.
+    pub partial_truncated: usize,
+    /// Lines dropped so far to stay within `LOG_MEM_BUDGET_MB`, all buffers
+    /// together and from the app log alone.
+    pub evicted_log_lines: usize,
+    pub evicted_app_log_lines: usize,
     pub log_scroll: u16,
     pub stick_to_bottom: bool,
     pub follow_mode: bool,
     pub last_log_height: u16,
+    /// Inner width of the sidebar at the last draw; labels are sized from it.
+    pub sidebar_width: u16,
+    /// Width of the name column in container and service labels, set by
+    /// `rebuild_items`. Longer names are cut with `…`.
+    pub name_width: usize,
 
     pub docker_log_child: Option<crate::docker::LogStream>,
     pub docker_log_rx: Option<mpsc::UnboundedReceiver<String>>,
 
     pub tasks: HashMap<String, TaskRuntime>,
+    /// Tasks started by container actions (`action@container`), listed after
+    /// the configured tasks in launch order.
+    pub action_runs: Vec<String>,
     pub containers: Vec<(docker::ContainerSummary, Vec<docker::Port>)>,
     pub expanded_groups: HashSet<String>,
     pub refreshing: bool,
+    /// `Ctrl+R` asked for a refresh; the run loop does it once the
+    /// header has shown `refreshing…`.
+    pub refresh_requested: bool,
+    /// The background listing in flight predates a manual refresh: its
+    /// result is dropped when it lands.
+    pub stale_refresh: bool,
 
     pub swarm_services: Vec<docker::SwarmService>,
     pub swarm_refreshing: bool,
@@ -62,11 +144,20 @@ pub struct App {
 
     pub popup: Option<Popup>,
     pub copy_mode: bool,
+    /// Whether the app receives mouse events; off leaves selection to the
+    /// terminal until `Ctrl+T` turns it back on.
+    pub mouse_capture: bool,
 
     pub container_stats: Option<docker::ContainerStats>,
+    pub stats_view: docker::StatsView,
     pub stats_history: HashMap<String, VecDeque<(f64, f64)>>,
     pub stats_refreshing: bool,
+    /// Latest CPU/memory of each running container, for the sidebar column.
+    pub list_stats: HashMap<String, docker::StatsSample>,
+    pub list_stats_refreshing: bool,
     pub pins: HashSet<String>,
+    /// Saved `:` command lines for this project (`;` lists them).
+    pub bookmarks: Vec<Bookmark>,
 
     pub shell_stdin: Option<std::pin::Pin<Box<dyn tokio::io::AsyncWrite + Send>>>,
     pub shell_process: Option<crate::docker::LogStream>,
@@ -79,26 +170,71 @@ pub struct App {
     pub filter_query: String,
     pub is_filtering: bool,
     pub log_filter_query: String,
+    /// Log search (`/` in the logs pane): unlike the filter, every line stays
+    /// on screen and matches are highlighted. `Some` while it is the mode in use.
+    pub log_search: Option<String>,
     pub is_filtering_logs: bool,
+    /// `log_filter_query` compiled as a case-insensitive regex; an invalid
+    /// pattern keeps the last valid one and sets `log_filter_error`.
+    pub log_filter: Option<regex::Regex>,
+    pub log_filter_error: Option<String>,
+    /// Freeze the periodic container/swarm/stats refresh (`z`); `Ctrl+R` still
+    /// refreshes on demand.
+    pub refresh_paused: bool,
+    /// `docker events` follower driving the refresh in events mode (the default),
+    /// with the context it was started for.
+    pub refresh_events: Option<(docker::LogStream, mpsc::UnboundedReceiver<String>, String)>,
+    /// Context whose event stream ended; it is polled until the context changes.
+    pub refresh_events_failed: Option<String>,
+    /// Events arrived since the last refresh.
+    pub refresh_events_pending: bool,
+    pub last_poll: std::time::Instant,
+    /// Append each container's age (`3d ago`) to its sidebar label (`a`).
+    pub show_age: bool,
+    /// List exited, dead and created containers too (`w`).
+    pub show_stopped: bool,
+    /// Stream filter applied to task buffers in the log pane (`Y`).
+    pub task_stream: TaskStream,
+    /// Log search match last reached with `n`/`N`; `None` until navigating.
+    pub log_match: Option<usize>,
 
     pub multi_selected: HashSet<String>,
     pub toast: Option<(String, std::time::Instant, ratatui::style::Color)>,
+    /// What was just destroyed and how to get it back, shown in the footer
+    /// for `RECOVERY_HINT_FOR`.
+    pub recovery_hint: Option<(String, std::time::Instant)>,
+
+    /// `.env` files loaded for the active profile, shown in diagnostics.
+    pub env_files: Vec<String>,
+    /// Per-target tail overrides set with `+`/`-`, kept across selections.
+    pub log_tails: HashMap<String, usize>,
+    /// Container logs are read with `--timestamps` (`t` in the logs pane).
+    pub log_timestamps: bool,
+    /// Last seen `RestartCount` per container id.
+    pub restart_counts: HashMap<String, i64>,
+    /// Containers whose restart count went up recently, for the sidebar flash.
+    pub restart_flash: HashMap<String, std::time::Instant>,
+    /// Container ids last listed for each compose `(project, service)`: all
+    /// replicas, and leftover stopped containers too.
+    pub service_containers: HashMap<(String, String), HashSet<String>>,
+    /// Times a compose service had a container replaced by a new one this session.
+    pub recreations: HashMap<(String, String), u32>,
+    /// Editor or interactive command waiting for the main loop to hand it the terminal.
+    pub pending_foreground: Option<ForegroundJob>,
+    /// Startup `compose up -d` / `restart` in progress.
+    pub compose_job: Option<ComposeJob>,
+    pub log_recording: Option<LogRecording>,
+    /// Digits typed after `g` for a quick jump, with the time of the last key.
+    pub jump_input: Option<(String, std::time::Instant)>,
+    /// When the open confirm popup was first seen, for `CONFIRM_TIMEOUT`.
+    pub confirm_opened: Option<std::time::Instant>,
 }
 
 impl App {
     pub fn new(cfg: Config, docker: docker::DockerMeta) -> Self {
         let mut tasks_map = HashMap::new();
         for t in &cfg.post_up_tasks {
-            tasks_map.insert(
-                t.name.clone(),
-                TaskRuntime {
-                    spec: t.clone(),
-                    status: TaskStatus::Pending,
-                    lines: VecDeque::new(),
-                    child: None,
-                    rx: None,
-                },
-            );
+            tasks_map.insert(t.name.clone(), TaskRuntime::new(t.clone()));
         }
 
         let mut log_lines = VecDeque::new();
@@ -118,6 +254,9 @@ impl App {
         };
         log_lines.push_back(format!("Post-up tasks: {tasks}"));
 
+        let show_age = cfg.show_age;
+        let mouse_capture = cfg.mouse_capture;
+        let saved_bookmarks = bookmarks::load_bookmarks(&cfg.cwd);
         Self {
             cfg,
             docker,
@@ -125,27 +264,46 @@ impl App {
             selected: 0,
             focus_on_list: true,
             current_target: "".to_string(),
+            app_log: log_lines.clone(),
+            app_log_bytes: log_lines.iter().map(String::len).sum(),
+            unseen_failures: HashSet::new(),
+            container_left_at: HashMap::new(),
+            partial_truncated: 0,
+            evicted_log_lines: 0,
+            evicted_app_log_lines: 0,
+            log_bytes: log_lines.iter().map(String::len).sum(),
             log_lines,
             log_scroll: 0,
             stick_to_bottom: true,
             follow_mode: true,
             last_log_height: 0,
+            prefix_colors: HashMap::new(),
+            sidebar_width: 0,
+            name_width: 20,
             docker_log_child: None,
             docker_log_rx: None,
             tasks: tasks_map,
+            action_runs: Vec::new(),
             containers: Vec::new(),
             expanded_groups: HashSet::new(),
             refreshing: false,
+            refresh_requested: false,
+            stale_refresh: false,
             swarm_services: Vec::new(),
             swarm_refreshing: false,
             list_state: ratatui::widgets::ListState::default(),
             list_area: Rect::default(),
             popup: None,
             copy_mode: false,
+            mouse_capture,
             container_stats: None,
+            stats_view: docker::StatsView::default(),
             stats_history: HashMap::new(),
             stats_refreshing: false,
+            list_stats: HashMap::new(),
+            list_stats_refreshing: false,
             pins: pins::load_pins(),
+            bookmarks: saved_bookmarks,
             shell_stdin: None,
             shell_process: None,
             shell_active: false,
@@ -153,14 +311,89 @@ impl App {
             filter_query: String::new(),
             is_filtering: false,
             log_filter_query: String::new(),
+            log_search: None,
             is_filtering_logs: false,
+            log_filter: None,
+            log_filter_error: None,
+            log_match: None,
+            refresh_paused: false,
+            refresh_events: None,
+            refresh_events_failed: None,
+            refresh_events_pending: false,
+            last_poll: std::time::Instant::now(),
+            show_age,
+            show_stopped: true,
+            task_stream: TaskStream::default(),
             multi_selected: HashSet::new(),
             toast: None,
+            recovery_hint: None,
+            env_files: Vec::new(),
+            log_tails: HashMap::new(),
+            log_timestamps: false,
+            restart_counts: HashMap::new(),
+            restart_flash: HashMap::new(),
+            service_containers: HashMap::new(),
+            recreations: HashMap::new(),
+            pending_foreground: None,
+            compose_job: None,
+            log_recording: None,
+            jump_input: None,
+            confirm_opened: None,
             sort_by: SortBy::Name,
             sort_order: SortOrder::Asc,
         }
     }
 
+    /// Command that brings back the containers `ids` after a stop/kill
+    /// (`docker start`) or a removal (`compose up` of their services). Call it
+    /// before the refresh that drops them from `containers`.
+    pub fn recovery_command(&self, ids: &[String], removed: bool) -> Option<String> {
+        let found: Vec<&docker::ContainerSummary> = self.containers.iter()
+            .map(|(c, _)| c)
+            .filter(|c| ids.contains(&c.id))
+            .collect();
+        if !removed {
+            let names: Vec<String> = found.iter().map(|c| docker::container_name(&c.names)).collect();
+            return (!names.is_empty()).then(|| format!("docker start {}", names.join(" ")));
+        }
+        let mut by_project: Vec<(String, Vec<String>)> = Vec::new();
+        for c in found {
+            let (Some(project), Some(service)) = (&c.compose_project, &c.compose_service) else { continue };
+            match by_project.iter_mut().find(|(p, _)| p == project) {
+                Some((_, services)) => services.push(service.clone()),
+                None => by_project.push((project.clone(), vec![service.clone()])),
+            }
+        }
+        let cmds: Vec<String> = by_project.into_iter()
+            .map(|(project, services)| format!("docker compose -p {project} up -d {}", services.join(" ")))
+            .collect();
+        (!cmds.is_empty()).then(|| cmds.join(" && "))
+    }
+
+    /// Show `done` and its recovery command in the footer, and keep a copy in
+    /// the app log.
+    pub fn show_recovery(&mut self, done: &str, cmd: Option<String>) {
+        let hint = match cmd {
+            Some(cmd) => format!("{done} — to undo: {cmd}"),
+            None => format!("{done} — no compose labels, recreate it the way it was started"),
+        };
+        self.push_app_log(&format!("↩️ {hint}"));
+        self.recovery_hint = Some((hint, std::time::Instant::now()));
+    }
+
+    /// Colour of the profile badge: the `PROFILE_COLORS` entry for the
+    /// profile, else its `*` entry, else green for `local` and red otherwise.
+    pub fn profile_color(&self) -> ratatui::style::Color {
+        use ratatui::style::Color;
+        let profile = self.cfg.profile.to_lowercase();
+        let configured = |p: &str| self.cfg.profile_colors.iter()
+            .find(|(name, _)| name == p)
+            .and_then(|(_, color)| color.parse::<Color>().ok());
+        configured(&profile)
+            .or_else(|| configured("*"))
+            .unwrap_or(if profile == "local" { Color::Green } else { Color::Red })
+    }
+
     pub fn notify(&mut self, msg: String, color: ratatui::style::Color) {
         self.toast = Some((msg, std::time::Instant::now(), color));
     }
@@ -175,10 +408,119 @@ impl App {
     }
 
     pub async fn refresh_containers(&mut self) -> Result<()> {
-        self.containers = docker::list_containers_all(&self.docker, &self.cfg.cwd).await?;
+        self.containers = docker::list_containers_all(&self.docker, &self.cfg.cwd, &self.containers).await?;
+        self.track_restarts();
         Ok(())
     }
 
+    /// One-off refresh of containers, swarm services and the selected
+    /// container's stats; works while the ticker is paused.
+    pub async fn refresh_now(&mut self) -> Result<()> {
+        self.refresh_containers().await?;
+        self.swarm_services = docker::list_swarm_services(&self.docker, &self.cfg.cwd).await;
+        if let Some(item) = self.items.get(self.selected).filter(|i| i.kind == SidebarKind::Container) {
+            self.container_stats = docker::fetch_stats(&self.docker, &self.cfg.cwd, &item.id).await.ok();
+        }
+        self.rebuild_items();
+        Ok(())
+    }
+
+    /// Whether the ticker should list containers now. Polling refreshes on
+    /// every tick; in events mode (the default) only once a relevant event came
+    /// in, or after `EVENTS_POLL_SECS` as a safety net. If `docker events`
+    /// cannot run or stops, the context goes back to polling.
+    pub fn refresh_due(&mut self) -> bool {
+        let context = format!("{} {}", self.docker.docker_bin, self.docker.context_name);
+        let wanted = self.cfg.refresh_on_events && self.refresh_events_failed.as_ref() != Some(&context);
+        if self.refresh_events.as_ref().is_some_and(|(_, _, ctx)| !wanted || *ctx != context) {
+            if let Some((mut stream, _, _)) = self.refresh_events.take() {
+                stream.kill();
+            }
+        }
+        if wanted && self.refresh_events.is_none() {
+            match docker::stream_refresh_events(&self.docker, &self.cfg.cwd) {
+                Ok((stream, rx)) => {
+                    self.refresh_events = Some((stream, rx, context.clone()));
+                    self.refresh_events_pending = true;
+                }
+                Err(e) => {
+                    self.push_app_log(&format!("⚠️ docker events unavailable ({e}), polling every {}ms", self.cfg.refresh_ms));
+                    self.refresh_events_failed = Some(context.clone());
+                }
+            }
+        }
+
+        let mut closed = false;
+        if let Some((_, rx, _)) = self.refresh_events.as_mut() {
+            loop {
+                match rx.try_recv() {
+                    Ok(_) => self.refresh_events_pending = true,
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => {
+                        closed = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if closed {
+            self.refresh_events = None;
+            self.refresh_events_failed = Some(context);
+            self.push_app_log(&format!("⚠️ docker events stopped, polling every {}ms", self.cfg.refresh_ms));
+        }
+
+        if self.refresh_events.is_none() {
+            return true;
+        }
+        // Keep pending events for the next tick while a listing is in flight.
+        let due = !self.refreshing
+            && (self.refresh_events_pending || self.last_poll.elapsed().as_secs() >= self.cfg.events_poll_secs);
+        if due {
+            self.refresh_events_pending = false;
+            self.last_poll = std::time::Instant::now();
+        }
+        due
+    }
+
+    /// Compare each container's `RestartCount` with the previous refresh and
+    /// warn about the ones that went up. Also counts compose services whose
+    /// container was replaced, for [`docker::restart_policy_hint`].
+    pub fn track_restarts(&mut self) {
+        let mut restarted = Vec::new();
+        for (c, _) in &self.containers {
+            let prev = self.restart_counts.insert(c.id.clone(), c.restart_count);
+            if prev.is_some_and(|p| c.restart_count > p) {
+                restarted.push((c.id.clone(), docker::container_name(&c.names), c.restart_count));
+            }
+        }
+        // A replacement is an id that went away while a new one showed up;
+        // more replicas or a leftover stopped container are not.
+        let mut listed: HashMap<(String, String), HashSet<String>> = HashMap::new();
+        for (c, _) in &self.containers {
+            if let (Some(project), Some(service)) = (&c.compose_project, &c.compose_service) {
+                listed.entry((project.clone(), service.clone())).or_default().insert(c.id.clone());
+            }
+        }
+        for (key, ids) in listed {
+            if let Some(prev) = self.service_containers.get(&key) {
+                let replaced = ids.difference(prev).count().min(prev.difference(&ids).count());
+                if replaced > 0 {
+                    *self.recreations.entry(key.clone()).or_default() += replaced as u32;
+                }
+            }
+            self.service_containers.insert(key, ids);
+        }
+        let live: HashSet<&String> = self.containers.iter().map(|(c, _)| &c.id).collect();
+        self.restart_counts.retain(|id, _| live.contains(id));
+        self.restart_flash.retain(|_, at| at.elapsed() < RESTART_FLASH);
+
+        for (id, name, count) in restarted {
+            self.push_app_log(&format!("⚠️ {name} restarted (restart count: {count}) — possible crash loop"));
+            self.notify(format!("⚠️ {name} restarted ({count}x)"), ratatui::style::Color::Red);
+            self.restart_flash.insert(id, std::time::Instant::now());
+        }
+    }
+
     pub async fn refresh_swarm(&mut self) -> Result<()> {
         self.swarm_services = docker::list_swarm_services(&self.docker, &self.cfg.cwd).await;
         Ok(())
@@ -202,7 +544,11 @@ impl App {
 
     pub fn rebuild_items(&mut self) {
         let mut sorted_containers = self.containers.clone();
+        if !self.show_stopped {
+            sorted_containers.retain(|(c, _)| !is_stopped(c));
+        }
         let mut sorted_swarm = self.swarm_services.clone();
+        self.name_width = self.fit_name_width();
 
         match self.sort_by {
             SortBy::Name => {
@@ -269,14 +615,9 @@ impl App {
                 };
 
                 if is_match {
-                    let state = c.state.to_lowercase();
-                    let badge = if state == "running" { "🟢" }
-                        else if state == "paused" { "🟡" }
-                        else if state == "restarting" { "🔵" }
-                        else if state == "exited" || state == "dead" { "🔴" }
-                        else { "⚪️" };
-                    
-                    let mut label = format!(" {badge} {name}");
+                    let badge = container_badge(c);
+                    let health = if c.health.is_empty() { String::new() } else { format!(" ({})", c.health) };
+                    let mut label = format!(" {badge} {name}{health}{}{}", restarts_suffix(c), self.age_suffix(c));
                     if let Some(history) = self.stats_history.get(&c.id) {
                         let cpu_spark = self.get_sparkline(history.iter().map(|h| h.0), 100.0, 5);
                         label.push_str(&format!("  [C:{}]", cpu_spark));
@@ -314,13 +655,32 @@ impl App {
                 }
             }
 
-            self.items = items;
-            if self.selected >= self.items.len() {
-                self.selected = self.items.len().saturating_sub(1);
-            }
+            self.set_items(items);
             return;
         }
 
+        // -- App log --
+        items.push(UiItem {
+            kind: SidebarKind::AppLog,
+            id: APP_LOG_ID.to_string(),
+            name: "app log".to_string(),
+            label: format!("📝 app log  ({} messages)", self.app_log.len()),
+            ports: vec![],
+            selected: false,
+            depth: 0,
+        });
+        if self.cfg.auto_compose_up && self.docker.available {
+            items.push(UiItem {
+                kind: SidebarKind::StackLogs,
+                id: STACK_LOGS_ID.to_string(),
+                name: "stack logs".to_string(),
+                label: format!("📜 stack logs  (compose --profile {})", self.cfg.compose_profile),
+                ports: vec![],
+                selected: false,
+                depth: 0,
+            });
+        }
+
         // -- Pinned containers --
         let pinned: Vec<&(docker::ContainerSummary, Vec<docker::Port>)> = sorted_containers
             .iter()
@@ -342,9 +702,7 @@ impl App {
                     "running" => "🟢", "paused" => "🟡",
                     "exited" | "dead" => "🔴", _ => "⚪️",
                 };
-                let stats_suffix = self.container_stats.as_ref()
-                    .map(|s| s.sidebar_label()).unwrap_or_default();
-                let label = format!("    {state_icon} {name}{stats_suffix}");
+                let label = format!("    {state_icon} {name}{}{}", self.age_suffix(c), self.stats_suffix(c));
                 items.push(UiItem {
                     kind: SidebarKind::Container,
                     id: c.id.clone(),
@@ -358,11 +716,15 @@ impl App {
         }
 
         // -- Tasks section --
-        for t in &self.cfg.post_up_tasks {
+        let task_specs: Vec<TaskSpec> = self.cfg.post_up_tasks.iter().cloned()
+            .chain(self.action_runs.iter().filter_map(|n| self.tasks.get(n).map(|rt| rt.spec.clone())))
+            .collect();
+        for t in &task_specs {
             let rt = self.tasks.get(&t.name);
             let (status, lines) = rt
                 .map(|r| (r.status, r.lines.len()))
                 .unwrap_or((TaskStatus::Pending, 0));
+            let next_run = rt.and_then(|r| r.next_run);
             let badge = match status {
                 TaskStatus::Run => "🟢",
                 TaskStatus::Ok => "⚪️",
@@ -370,12 +732,37 @@ impl App {
                 TaskStatus::Stop => "⚪️",
                 TaskStatus::Pending => "⚪️",
             };
-            let label = format!(
+            let mut label = format!(
                 "{badge} task: {:<14}  [{:<4}]  logs:{:>4}",
                 t.name,
                 status.as_str(),
                 lines
             );
+            let program = crate::env::command_program(&t.cmd);
+            if !program.is_empty() {
+                label.push_str(&format!("  $ {program}"));
+            }
+            if let Some(service) = &t.service {
+                label.push_str(&format!("  🔗 {service}"));
+            }
+            if self.unseen_failures.contains(&t.name) {
+                label.push_str("  ❗ new");
+            }
+            if let Some(at) = next_run {
+                let left = at.saturating_duration_since(std::time::Instant::now()).as_secs();
+                label.push_str(&format!("  ⏱ {left}s"));
+            }
+            if let Some(rt) = rt.filter(|r| r.status == TaskStatus::Run) {
+                let rate = rt.line_rate();
+                let silent = rt.last_output.map(|t| t.elapsed()).unwrap_or_default();
+                if rate >= 1.0 {
+                    label.push_str(&format!("  ~{rate:.0} l/s"));
+                } else if rate > 0.0 {
+                    label.push_str(&format!("  ~{rate:.1} l/s"));
+                } else if silent >= IDLE_AFTER {
+                    label.push_str(&format!("  💤 idle {}m", silent.as_secs() / 60));
+                }
+            }
             items.push(UiItem {
                 kind: SidebarKind::Task,
                 id: t.name.clone(),
@@ -391,7 +778,7 @@ impl App {
         let mut project_order: Vec<String> = Vec::new();
         let mut project_containers: HashMap<String, Vec<&(docker::ContainerSummary, Vec<docker::Port>)>> = HashMap::new();
         for entry in &sorted_containers {
-            let key = entry.0.compose_project.clone().unwrap_or_else(|| "(ungrouped)".to_string());
+            let key = entry.0.compose_project.clone().unwrap_or_else(|| STANDALONE_GROUP.to_string());
             project_containers.entry(key.clone()).or_default().push(entry);
             if !project_order.contains(&key) {
                 project_order.push(key);
@@ -399,8 +786,8 @@ impl App {
         }
         project_order.sort_by(|a, b| {
             match (a.as_str(), b.as_str()) {
-                ("(ungrouped)", _) => std::cmp::Ordering::Greater,
-                (_, "(ungrouped)") => std::cmp::Ordering::Less,
+                (STANDALONE_GROUP, _) => std::cmp::Ordering::Greater,
+                (_, STANDALONE_GROUP) => std::cmp::Ordering::Less,
                 _ => a.cmp(b),
             }
         });
@@ -424,31 +811,46 @@ impl App {
             });
 
             if !collapsed {
-                for (c, ports) in group_containers {
-                    let name = docker::container_name(&c.names);
-                    let state = c.state.to_lowercase();
-                    let badge = if state == "running" { "🟢" }
-                        else if state == "paused" { "🟡" }
-                        else if state == "restarting" { "🔵" }
-                        else if state == "created" { "⚪️" }
-                        else if state == "exited" || state == "dead" { "🔴" }
-                        else { "⚪️" };
-
-                    let status_txt = c.status.split_whitespace().collect::<Vec<_>>().join(" ");
-                    let mut label = format!("  {badge} {:<20} {status_txt}", name);
-                    if let Some(history) = self.stats_history.get(&c.id) {
-                        let cpu_spark = self.get_sparkline(history.iter().map(|h| h.0), 100.0, 5);
-                        label.push_str(&format!("  [C:{}]", cpu_spark));
+                // Scaled services get their own header so their replicas can be
+                // followed together; single-replica services stay flat.
+                let mut replicas: HashMap<&str, usize> = HashMap::new();
+                for (c, _) in group_containers {
+                    if let Some(svc) = c.compose_service.as_deref() {
+                        *replicas.entry(svc).or_default() += 1;
                     }
+                }
+                let mut emitted_services: HashSet<&str> = HashSet::new();
+                for entry in group_containers {
+                    let service = entry.0.compose_service.as_deref().filter(|s| replicas.get(s).copied().unwrap_or(0) > 1);
+                    let Some(service) = service else {
+                        items.push(self.compose_container_item(entry, 1));
+                        continue;
+                    };
+                    if !emitted_services.insert(service) {
+                        continue;
+                    }
+                    let svc_id = format!("svc:{project}/{service}");
+                    let svc_collapsed = !self.expanded_groups.contains(&svc_id);
+                    let members: Vec<_> = group_containers
+                        .iter()
+                        .filter(|(m, _)| m.compose_service.as_deref() == Some(service))
+                        .collect();
+                    let running = members.iter().filter(|(m, _)| m.state.to_lowercase() == "running").count();
+                    let arrow = if svc_collapsed { "▶" } else { "▼" };
                     items.push(UiItem {
-                        kind: SidebarKind::Container,
-                        id: c.id.clone(),
-                        name: name.clone(),
-                        label,
-                        ports: ports.to_vec(),
-                        selected: self.multi_selected.contains(&c.id),
+                        kind: SidebarKind::GroupHeader,
+                        id: svc_id,
+                        name: service.to_string(),
+                        label: format!("  {arrow} ⚙ {service}  ({running}/{})", members.len()),
+                        ports: vec![],
+                        selected: false,
                         depth: 1,
                     });
+                    if !svc_collapsed {
+                        for entry in members {
+                            items.push(self.compose_container_item(entry, 2));
+                        }
+                    }
                 }
             }
         }
@@ -495,7 +897,7 @@ impl App {
 
                 if !collapsed {
                     for svc in group_services {
-                        let label = format!("    🐳 {:<22} {} ({}) {}", svc.name, svc.replicas, svc.mode, svc.image);
+                        let label = format!("    🐳 {} {} ({}) {}", fit_name(&svc.name, self.name_width), svc.replicas, svc.mode, svc.image);
                         items.push(UiItem {
                             kind: SidebarKind::SwarmService,
                             id: svc.id.clone(),
@@ -510,15 +912,51 @@ impl App {
             }
         }
 
-        self.items = items;
-        if self.selected >= self.items.len() {
-            self.selected = self.items.len().saturating_sub(1);
-        }
+        self.set_items(items);
         while self.selected < self.items.len()
             && self.items[self.selected].kind == SidebarKind::Separator
         {
             self.selected += 1;
         }
+        self.list_state.select(Some(self.selected));
+    }
+
+    /// Swap in a rebuilt item list, keeping the cursor on the same item (by
+    /// id) rather than the same index so re-sorts don't move the selection.
+    /// A container listed twice (pinned + group) keeps the same occurrence.
+    fn set_items(&mut self, items: Vec<UiItem>) {
+        let anchor = self.items.get(self.selected).map(|cur| {
+            let nth = self.items[..self.selected].iter().filter(|i| i.id == cur.id).count();
+            (cur.id.clone(), nth)
+        });
+        self.items = items;
+
+        let anchored = anchor.and_then(|(id, nth)| {
+            let mut hits = self.items.iter().enumerate().filter(|(_, i)| i.id == id).map(|(pos, _)| pos);
+            let first = hits.next()?;
+            Some(if nth == 0 { first } else { hits.nth(nth - 1).unwrap_or(first) })
+        });
+        self.selected = match anchored {
+            Some(pos) => pos,
+            None => self.selected.min(self.items.len().saturating_sub(1)),
+        };
+        self.list_state.select(Some(self.selected));
+    }
+
+    /// Flip `show_stopped`. When that hides the selected container, the
+    /// nearest item left in its place is selected instead.
+    pub async fn toggle_show_stopped(&mut self) {
+        self.show_stopped = !self.show_stopped;
+        let before = self.items.get(self.selected).map(|i| i.id.clone());
+        self.rebuild_items();
+        if before.is_none() || self.items.get(self.selected).map(|i| &i.id) == before.as_ref() {
+            return;
+        }
+        let below = (self.selected..self.items.len()).find(|&i| self.items[i].kind != SidebarKind::Separator);
+        let above = (0..self.selected).rev().find(|&i| self.items[i].kind != SidebarKind::Separator);
+        if let Some(idx) = below.or(above) {
+            let _ = self.select(idx).await;
+        }
     }
 
     pub fn toggle_select(&mut self, id: &str) {
@@ -533,65 +971,399 @@ impl App {
         self.focus_on_list = list;
     }
 
-    pub fn push_current_log(&mut self, line: &str) {
-        self.log_lines.push_back(line.to_string());
-        while self.log_lines.len() > self.cfg.max_log_lines {
-            self.log_lines.pop_front();
+    /// The `Y` stream filter in effect: only task buffers are tagged, so any
+    /// other target shows everything.
+    pub fn active_task_stream(&self) -> TaskStream {
+        if self.tasks.contains_key(&self.current_target) {
+            self.task_stream
+        } else {
+            TaskStream::Both
         }
-        if self.follow_mode {
+    }
+
+    /// Whether the log pane shows a container, whose stderr lines carry an
+    /// `[ERR] ` tag that is only there for colouring.
+    pub fn shows_container_logs(&self) -> bool {
+        self.containers.iter().any(|(c, _)| c.id == self.current_target)
+    }
+
+    /// The log buffer as text, without the container stderr tags.
+    pub fn log_text(&self) -> String {
+        let text = self.log_lines.iter().map(String::as_str).collect::<Vec<_>>().join("\n");
+        if self.shows_container_logs() {
+            strip_stream_tags(&text).into_owned()
+        } else {
+            text
+        }
+    }
+
+    /// The log search query if searching, else the log filter query.
+    pub fn log_query(&self) -> &str {
+        self.log_search.as_deref().unwrap_or(&self.log_filter_query)
+    }
+
+    /// The log query as a regex: the search text taken literally, or the
+    /// filter pattern. Case-insensitive either way; `None` when unset.
+    pub fn log_regex(&self) -> Option<regex::Regex> {
+        match &self.log_search {
+            Some(query) if query.is_empty() => None,
+            Some(query) => regex::RegexBuilder::new(&regex::escape(query)).case_insensitive(true).build().ok(),
+            None => self.log_filter.clone(),
+        }
+    }
+
+    /// Compile `log_filter_query` after an edit.
+    pub fn update_log_filter(&mut self) {
+        if self.log_filter_query.is_empty() {
+            self.log_filter = None;
+            self.log_filter_error = None;
+            return;
+        }
+        match regex::RegexBuilder::new(&self.log_filter_query).case_insensitive(true).build() {
+            Ok(re) => {
+                self.log_filter = Some(re);
+                self.log_filter_error = None;
+            }
+            // The last line of the message says what is wrong; the rest
+            // draws the pattern with a caret under it.
+            Err(e) => self.log_filter_error = e.to_string().lines().last().map(|l| l.trim_start_matches("error: ").to_string()),
+        }
+    }
+
+    /// Drop the log search and filter.
+    pub fn clear_log_query(&mut self) {
+        self.log_search = None;
+        self.log_filter_query.clear();
+        self.update_log_filter();
+        self.log_match = None;
+    }
+
+    /// Indices in `log_lines` of the lines matching the log search query.
+    pub fn log_matches(&self) -> Vec<usize> {
+        let Some(re) = self.log_regex() else {
+            return Vec::new();
+        };
+        let stream = self.active_task_stream();
+        self.log_lines.iter()
+            .enumerate()
+            .filter(|(_, l)| stream.shows(l) && re.is_match(l))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Row of log line `idx` in the pane: the filtered view lists only
+    /// matches, the search view every line of the active stream.
+    pub fn log_row(&self, idx: usize, match_no: usize) -> usize {
+        if self.log_search.is_none() {
+            return match_no;
+        }
+        let stream = self.active_task_stream();
+        self.log_lines.iter().take(idx).filter(|l| stream.shows(l)).count()
+    }
+
+    /// Step to the next (`forward`) or previous log search match and scroll
+    /// it to the middle of the pane. Appended lines no longer pull the view
+    /// to the bottom until `End` or a new search.
+    pub fn jump_log_match(&mut self, forward: bool) {
+        let matches = self.log_matches();
+        let count = matches.len();
+        if count == 0 {
+            return;
+        }
+        let next = match (self.log_match.map(|i| i.min(count - 1)), forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        self.log_match = Some(next);
+        self.stick_to_bottom = false;
+        let row = self.log_row(matches[next], next);
+        self.log_scroll = (row as u16).saturating_sub(self.last_log_height / 2);
+    }
+
+    /// Record a tool-level message (action started, compose result…) in the
+    /// app log rather than the selected target's buffer, so it survives
+    /// switching targets.
+    pub fn push_app_log(&mut self, line: &str) {
+        let line = format!("[{}] {line}", chrono::Local::now().format("%H:%M:%S"));
+        self.app_log_bytes += line.len();
+        self.app_log.push_back(line.clone());
+        while self.app_log.len() > self.cfg.max_log_lines {
+            if let Some(old) = self.app_log.pop_front() {
+                self.app_log_bytes -= old.len();
+            }
+        }
+        if self.current_target == APP_LOG_ID {
+            self.push_current_log(&line);
+        }
+    }
+
+    /// `(targets, lines, bytes)` held by the app log, the task buffers and the
+    /// log pane, from their running byte counts. The pane is left out while it
+    /// mirrors the app log or a task, which are already counted.
+    pub fn log_buffer_usage(&self) -> (usize, usize, usize) {
+        let mirrored = self.current_target == APP_LOG_ID || self.tasks.contains_key(&self.current_target);
+        let buffers = std::iter::once((self.app_log.len(), self.app_log_bytes))
+            .chain(self.tasks.values().map(|rt| (rt.lines.len(), rt.line_bytes)))
+            .chain((!mirrored).then_some((self.log_lines.len(), self.log_bytes)));
+        let (mut targets, mut lines, mut bytes) = (0, 0, 0);
+        for (len, size) in buffers.filter(|(len, _)| *len > 0) {
+            targets += 1;
+            lines += len;
+            bytes += size;
+        }
+        (targets, lines, bytes)
+    }
+
+    /// Drop the oldest lines of the least recently viewed tasks, then of the
+    /// app log, until all buffers fit `LOG_MEM_BUDGET_MB`. Whatever is on
+    /// screen, task or app log, is left alone.
+    fn enforce_log_budget(&mut self) {
+        let budget = self.cfg.log_mem_budget;
+        if let Some(rt) = self.tasks.get_mut(&self.current_target) {
+            rt.last_viewed = Some(std::time::Instant::now());
+        }
+        let (_, _, mut bytes) = self.log_buffer_usage();
+        if budget == 0 || bytes <= budget {
+            return;
+        }
+        let before = self.evicted_log_lines;
+        let mut stalest: Vec<(Option<std::time::Instant>, String)> = self.tasks.iter()
+            .filter(|(name, _)| **name != self.current_target)
+            .map(|(name, rt)| (rt.last_viewed, name.clone()))
+            .collect();
+        stalest.sort();
+        for (_, name) in stalest {
+            let rt = self.tasks.get_mut(&name).expect("listed above");
+            while bytes > budget {
+                let Some(l) = rt.pop_line() else { break };
+                bytes -= l.len();
+                self.evicted_log_lines += 1;
+            }
+        }
+        if self.current_target != APP_LOG_ID {
+            while bytes > budget {
+                let Some(l) = self.app_log.pop_front() else { break };
+                self.app_log_bytes -= l.len();
+                bytes -= l.len();
+                self.evicted_log_lines += 1;
+                self.evicted_app_log_lines += 1;
+            }
+        }
+        if before == 0 && self.evicted_log_lines > 0 {
+            self.push_app_log(&format!(
+                "🧹 Log buffers over LOG_MEM_BUDGET_MB ({} MB): dropping the oldest lines of unviewed tasks, then of the app log (count in Diagnostics, `!`)",
+                budget / (1024 * 1024)
+            ));
+        }
+    }
+
+    pub fn push_current_log(&mut self, line: &str) {
+        let line = docker::truncate_line(line, self.cfg.max_line_bytes).into_owned();
+        self.log_bytes += line.len();
+        self.log_lines.push_back(line);
+        self.trim_log_front();
+        if self.follow_mode && self.log_match.is_none() {
             self.stick_to_bottom = true;
         }
     }
 
+    /// Drop the oldest lines until at most `max_log_lines` and
+    /// `max_log_bytes` are held, keeping the newest line. While a search
+    /// match is on screen, the match index and the scroll follow the lines up
+    /// so the view stays where it is.
+    fn trim_log_front(&mut self) {
+        let re = self.log_match.and_then(|_| self.log_regex());
+        let stream = self.active_task_stream();
+        let max_bytes = self.cfg.max_log_bytes;
+        while self.log_lines.len() > self.cfg.max_log_lines
+            || (max_bytes > 0 && self.log_bytes > max_bytes && self.log_lines.len() > 1)
+        {
+            let Some(line) = self.log_lines.pop_front() else { break };
+            self.log_bytes -= line.len();
+            let Some(m) = self.log_match else { continue };
+            if !stream.shows(&line) {
+                continue;
+            }
+            let hit = re.as_ref().is_some_and(|re| re.is_match(&line));
+            if hit {
+                self.log_match = Some(m.saturating_sub(1));
+            }
+            if hit || self.log_search.is_some() {
+                self.log_scroll = self.log_scroll.saturating_sub(1);
+            }
+        }
+    }
+
     pub fn push_partial_log(&mut self, data: &str) {
+        let max = self.cfg.max_line_bytes;
         for c in data.chars() {
             match c {
                 '\n' => {
+                    if self.partial_truncated > 0 {
+                        if let Some(last) = self.log_lines.back_mut() {
+                            let marker = docker::truncation_marker(self.partial_truncated);
+                            self.log_bytes += marker.len();
+                            last.push_str(&marker);
+                        }
+                        self.partial_truncated = 0;
+                    }
                     self.log_lines.push_back(String::new());
                 }
                 '\r' => {
                     if let Some(last) = self.log_lines.back_mut() {
+                        self.log_bytes -= last.len();
                         last.clear();
                     }
+                    self.partial_truncated = 0;
                 }
                 '\x08' | '\x7f' => {
                     if let Some(last) = self.log_lines.back_mut() {
-                        if !last.is_empty() {
-                            last.pop();
+                        if let Some(c) = last.pop() {
+                            self.log_bytes -= c.len_utf8();
                         }
                     }
                 }
                 _ => {
                     if let Some(last) = self.log_lines.back_mut() {
-                        last.push(c);
+                        if max > 0 && last.len() >= max {
+                            self.partial_truncated += c.len_utf8();
+                        } else {
+                            self.log_bytes += c.len_utf8();
+                            last.push(c);
+                        }
                     } else {
+                        self.log_bytes += c.len_utf8();
                         self.log_lines.push_back(c.to_string());
                     }
                 }
             }
         }
 
-        while self.log_lines.len() > self.cfg.max_log_lines {
-            self.log_lines.pop_front();
-        }
-        if self.follow_mode {
+        self.trim_log_front();
+        if self.follow_mode && self.log_match.is_none() {
             self.stick_to_bottom = true;
         }
     }
 
     pub fn replace_current_logs(&mut self, all: Vec<String>) {
         self.log_lines.clear();
+        self.log_bytes = 0;
+        self.partial_truncated = 0;
         for l in all {
+            let l = docker::truncate_line(&l, self.cfg.max_line_bytes).into_owned();
+            self.log_bytes += l.len();
             self.log_lines.push_back(l);
         }
-        while self.log_lines.len() > self.cfg.max_log_lines {
-            self.log_lines.pop_front();
-        }
+        self.log_match = None;
+        self.trim_log_front();
         if self.follow_mode {
             self.stick_to_bottom = true;
         }
     }
 
+    /// Stable colour for a container prefix in aggregate logs. Red is left
+    /// out, it already means errors.
+    pub fn prefix_color(&mut self, name: &str) -> ratatui::style::Color {
+        use ratatui::style::Color;
+        const PALETTE: [Color; 10] = [
+            Color::Cyan, Color::Yellow, Color::Green, Color::Magenta, Color::Blue,
+            Color::LightCyan, Color::LightYellow, Color::LightGreen, Color::LightMagenta, Color::LightBlue,
+        ];
+        *self.prefix_colors.entry(name.to_string()).or_insert_with(|| {
+            // FNV-1a: std's hasher is randomly seeded per process.
+            let hash = name.bytes().fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+            PALETTE[(hash % PALETTE.len() as u64) as usize]
+        })
+    }
+
+    /// Name column width: `NAME_WIDTH` if set, else the longest container or
+    /// service name, capped so the status after it still fits in the pane.
+    fn fit_name_width(&self) -> usize {
+        if let Some(w) = self.cfg.name_width {
+            return w;
+        }
+        let longest = self.containers.iter()
+            .map(|(c, _)| docker::container_name(&c.names).chars().count())
+            .chain(self.swarm_services.iter().map(|s| s.name.chars().count()))
+            .max()
+            .unwrap_or(0);
+        if self.sidebar_width == 0 {
+            return longest.min(20);
+        }
+        // Highlight, select marker, tree prefix and badge take ~12 columns; keep
+        // ~16 for the status ("Up 3 hours").
+        longest.min((self.sidebar_width as usize).saturating_sub(28).max(12))
+    }
+
+    /// Full name of the selected item when its sidebar label cut it short.
+    pub fn truncated_selection(&self) -> Option<&str> {
+        let item = self.items.get(self.selected)?;
+        let labelled = matches!(item.kind, SidebarKind::Container | SidebarKind::SwarmService);
+        (labelled && item.name.chars().count() > self.name_width).then_some(item.name.as_str())
+    }
+
+    /// Why `c` is not behaving as its restart policy says, if it isn't.
+    pub fn policy_hint(&self, c: &docker::ContainerSummary) -> Option<String> {
+        let recreations = match (&c.compose_project, &c.compose_service) {
+            (Some(p), Some(s)) => self.recreations.get(&(p.clone(), s.clone())).copied().unwrap_or(0),
+            _ => 0,
+        };
+        docker::restart_policy_hint(&c.restart_policy, &c.state.to_lowercase(), recreations)
+    }
+
+    /// `  · 2h ago` when the age column is on and the creation time is known.
+    fn age_suffix(&self, c: &docker::ContainerSummary) -> String {
+        match c.created {
+            Some(created) if self.show_age => {
+                format!("  · {}", docker::relative_age(chrono::Utc::now().timestamp() - created))
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// `  cpu 12% mem 340MiB` (or the `M` view's columns) for a running
+    /// container the list stats cover.
+    fn stats_suffix(&self, c: &docker::ContainerSummary) -> String {
+        match self.list_stats.get(&c.id) {
+            Some(sample) if c.state.eq_ignore_ascii_case("running") => {
+                format!("  {}", docker::format_stats_sample(sample, self.stats_view))
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn compose_container_item(&self, (c, ports): &(docker::ContainerSummary, Vec<docker::Port>), depth: usize) -> UiItem {
+        let name = docker::container_name(&c.names);
+        let badge = container_badge(c);
+
+        let mut status_txt = c.status.split_whitespace().collect::<Vec<_>>().join(" ");
+        // `docker ps` already says `(healthy)` in the status on most engines.
+        if !c.health.is_empty() && !status_txt.contains(&c.health) {
+            status_txt.push_str(&format!(" ({})", c.health));
+        }
+        let indent = "  ".repeat(depth);
+        let mut label = format!("{indent}{badge} {} {status_txt}{}{}", fit_name(&name, self.name_width), restarts_suffix(c), self.age_suffix(c));
+        label.push_str(&self.stats_suffix(c));
+        if self.policy_hint(c).is_some() {
+            label.push_str("  ⚠ policy");
+        }
+        if let Some(history) = self.stats_history.get(&c.id) {
+            let cpu_spark = self.get_sparkline(history.iter().map(|h| h.0), 100.0, 5);
+            label.push_str(&format!("  [C:{}]", cpu_spark));
+        }
+        UiItem {
+            kind: SidebarKind::Container,
+            id: c.id.clone(),
+            name,
+            label,
+            ports: ports.clone(),
+            selected: self.multi_selected.contains(&c.id),
+            depth,
+        }
+    }
+
     pub async fn select(&mut self, idx: usize) -> Result<()> {
         if self.items.is_empty() {
             return Ok(());
@@ -611,13 +1383,33 @@ impl App {
 
         if let Some(mut c) = self.docker_log_child.take() {
             c.kill();
+            if self.current_target != item.id && self.containers.iter().any(|(c, _)| c.id == self.current_target) {
+                self.container_left_at.insert(self.current_target.clone(), chrono::Local::now());
+            }
         }
         self.docker_log_rx = None;
 
         self.current_target = item.id.clone();
 
         match item.kind {
+            SidebarKind::AppLog => {
+                let lines = self.app_log.iter().cloned().collect();
+                self.replace_current_logs(lines);
+            }
+            SidebarKind::StackLogs => {
+                let tail = self.log_tail(&item.id);
+                self.replace_current_logs(vec![
+                    format!("--- streaming logs for the whole stack (profile {}, tail {tail}) ---", self.cfg.compose_profile),
+                    String::new(),
+                ]);
+                let (child, rx) = docker::spawn_stack_logs_follow(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile, tail)?;
+                self.docker_log_child = Some(child);
+                self.docker_log_rx = Some(rx);
+            }
             SidebarKind::Task => {
+                if self.unseen_failures.remove(&item.id) {
+                    self.rebuild_items();
+                }
                 let rt = self.tasks.get(&item.id);
                 let lines = rt
                     .map(|r| r.lines.iter().cloned().collect::<Vec<_>>())
@@ -625,8 +1417,49 @@ impl App {
                 self.replace_current_logs(lines);
             }
             SidebarKind::Container => {
-                self.replace_current_logs(vec![format!("--- streaming logs for {} ---", item.name)]);
-                let (child, rx) = docker::spawn_logs_follow(&self.docker, &self.cfg.cwd, &item.id, 200)?;
+                let tail = self.log_tail(&item.id);
+                let stopped = self.containers.iter()
+                    .find(|(c, _)| c.id == item.id)
+                    .filter(|(c, _)| matches!(c.state.to_lowercase().as_str(), "exited" | "dead" | "created"))
+                    .map(|(c, _)| c.status.clone());
+                if let Some(status) = stopped {
+                    let mut lines = vec![format!("--- {} is stopped ({status}): last {tail} lines, not live ---", item.name)];
+                    match docker::fetch_container_logs(&self.docker, &self.cfg.cwd, &item.id, tail, None, self.log_timestamps).await {
+                        Ok(logs) if logs.is_empty() => lines.push("(no output)".to_string()),
+                        Ok(logs) => lines.extend(logs),
+                        Err(e) => lines.push(format!("❌ Cannot read logs: {e}")),
+                    }
+                    self.replace_current_logs(lines);
+                    return Ok(());
+                }
+                let mut lines = vec![format!("--- streaming logs for {} (tail {tail}) ---", item.name)];
+                // Back on a container: what was already there, a divider, then
+                // what it wrote while we were away.
+                let left_at = self.container_left_at.remove(&item.id);
+                let since = match left_at {
+                    Some(at) => match docker::fetch_container_logs(&self.docker, &self.cfg.cwd, &item.id, tail, Some(at.timestamp()), self.log_timestamps).await {
+                        Ok(before) => {
+                            lines.extend(before);
+                            lines.push(format!("{NEW_SINCE_MARKER} {} ────", at.format("%H:%M:%S")));
+                            Some(at.timestamp())
+                        }
+                        Err(_) => None,
+                    },
+                    None => None,
+                };
+                self.replace_current_logs(lines);
+                let (child, rx) = docker::stream_container_logs(&self.docker, &self.cfg.cwd, &item.id, tail, since, self.log_timestamps).await?;
+                self.docker_log_child = Some(child);
+                self.docker_log_rx = Some(rx);
+            }
+            SidebarKind::GroupHeader if item.id.starts_with("svc:") => {
+                let (project, service) = item.id["svc:".len()..].split_once('/').unwrap_or_default();
+                let tail = self.log_tail(&item.id);
+                self.replace_current_logs(vec![
+                    format!("--- streaming logs for service {service} (all replicas, tail {tail}) ---"),
+                    String::new(),
+                ]);
+                let (child, rx) = docker::spawn_service_logs_follow(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile, project, service, tail)?;
                 self.docker_log_child = Some(child);
                 self.docker_log_rx = Some(rx);
             }
@@ -637,8 +1470,9 @@ impl App {
                 ]);
             }
             SidebarKind::SwarmService => {
-                self.replace_current_logs(vec![format!("--- streaming logs for service {} ---", item.name)]);
-                let (child, rx) = docker::spawn_service_logs_follow(&self.docker, &self.cfg.cwd, &item.id, 200)?;
+                let tail = self.log_tail(&item.id);
+                self.replace_current_logs(vec![format!("--- streaming logs for service {} (tail {tail}) ---", item.name)]);
+                let (child, rx) = docker::stream_service_logs(&self.docker, &self.cfg.cwd, &item.id, tail).await?;
                 self.docker_log_child = Some(child);
                 self.docker_log_rx = Some(rx);
             }
@@ -648,6 +1482,60 @@ impl App {
         Ok(())
     }
 
+    pub fn log_tail(&self, target: &str) -> usize {
+        self.log_tails.get(target).copied().unwrap_or(DEFAULT_LOG_TAIL)
+    }
+
+    /// Double (`grow`) or halve the tail of the selected log target and
+    /// restart its follower. No-op for targets without a tailed stream.
+    pub async fn adjust_log_tail(&mut self, grow: bool) -> Result<()> {
+        let Some(item) = self.items.get(self.selected).cloned() else {
+            return Ok(());
+        };
+        let tailed = match item.kind {
+            SidebarKind::Container | SidebarKind::SwarmService | SidebarKind::StackLogs => true,
+            SidebarKind::GroupHeader => item.id.starts_with("svc:"),
+            _ => false,
+        };
+        if !tailed {
+            return Ok(());
+        }
+        let current = self.log_tail(&item.id);
+        let next = if grow {
+            current.saturating_mul(2).min(self.cfg.max_log_lines)
+        } else {
+            (current / 2).max(MIN_LOG_TAIL)
+        };
+        if next == current {
+            return Ok(());
+        }
+        self.log_tails.insert(item.id.clone(), next);
+        self.notify(format!("📜 Tail for {}: {next} lines", item.name), ratatui::style::Color::Cyan);
+        self.select(self.selected).await
+    }
+
+    /// Freeze or resume the auto-refresh of the list, services and stats.
+    pub fn toggle_refresh_pause(&mut self) {
+        self.refresh_paused = !self.refresh_paused;
+        if self.refresh_paused {
+            self.notify("⏸ Auto-refresh paused (Ctrl+R to refresh, z to resume)".to_string(), ratatui::style::Color::Yellow);
+        } else {
+            self.notify("▶ Auto-refresh resumed".to_string(), ratatui::style::Color::Green);
+        }
+    }
+
+    /// Turn `--timestamps` on or off and restart the container follower
+    /// with it, replacing the buffer.
+    pub async fn toggle_log_timestamps(&mut self) -> Result<()> {
+        self.log_timestamps = !self.log_timestamps;
+        let state = if self.log_timestamps { "ON" } else { "OFF" };
+        self.notify(format!("🕒 Log timestamps: {state}"), ratatui::style::Color::Cyan);
+        match self.items.get(self.selected) {
+            Some(item) if item.kind == SidebarKind::Container => self.select(self.selected).await,
+            _ => Ok(()),
+        }
+    }
+
     pub fn toggle_group_collapse(&mut self, project: &str) {
         if self.expanded_groups.contains(project) {
             self.expanded_groups.remove(project);
@@ -658,7 +1546,7 @@ impl App {
 
     pub async fn stop_shell(&mut self) {
         if let Some(mut child) = self.shell_process.take() {
-            let _ = child.kill();
+            child.kill();
         }
         self.shell_stdin = None;
         self.shell_active = false;
@@ -681,7 +1569,7 @@ impl App {
                 }
                 None => {
                     // Show what's actually running for debug
-                    let running = crate::docker::cmd_out(&self.docker.docker_bin, &self.cfg.cwd, &["ps", "--filter", "status=running", "--format", "{{.Names}}"]).await.unwrap_or_default();
+                    let running = crate::docker::cmd_out(&self.docker, &self.cfg.cwd, &["ps", "--filter", "status=running", "--format", "{{.Names}}"]).await.unwrap_or_default();
                     self.push_current_log("❌ No running tasks found. Running containers:");
                     for name in running.lines().take(10) {
                         self.push_current_log(&format!("  - {}", name));
@@ -715,7 +1603,7 @@ impl App {
 
     pub fn start_compose_logs(&mut self, project: String) -> Result<()> {
         if let Some(mut c) = self.docker_log_child.take() {
-            let _ = tokio::spawn(async move { c.kill() });
+            c.kill();
         }
         self.docker_log_rx = None;
         self.current_target = format!("project:{}", project);
@@ -727,31 +1615,370 @@ impl App {
         Ok(())
     }
 
+    /// `docker volume inspect` and the containers using the volume, in the
+    /// log pane.
+    pub async fn show_volume(&mut self, name: &str) -> Result<()> {
+        let json = docker::inspect_volume(&self.docker, &self.cfg.cwd, name).await?;
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
+        self.current_target = format!("volume:{name}");
+        let users = docker::volume_used_by(&self.containers, name);
+        let mut lines = vec![
+            format!("$ docker volume inspect {name}"),
+            match users.is_empty() {
+                true => "Used by: no container".to_string(),
+                false => format!("Used by: {}", users.join(", ")),
+            },
+            String::new(),
+        ];
+        lines.extend(json.lines().map(str::to_string));
+        self.replace_current_logs(lines);
+        Ok(())
+    }
+
+    /// `docker image inspect` in the log pane.
+    pub async fn show_image(&mut self, id: &str) -> Result<()> {
+        let json = docker::inspect_image(&self.docker, &self.cfg.cwd, id).await?;
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
+        self.current_target = format!("image:{id}");
+        let mut lines = vec![format!("$ docker image inspect {id}"), String::new()];
+        lines.extend(json.lines().map(str::to_string));
+        self.replace_current_logs(lines);
+        Ok(())
+    }
+
+    pub fn start_compose_events(&mut self, project: String) -> Result<()> {
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
+        self.current_target = format!("events:{}", project);
+        self.replace_current_logs(vec![
+            format!("📰 Daemon events for project: {}", project),
+            String::new(),
+        ]);
+
+        let (child, rx) = docker::stream_events(&self.docker, &self.cfg.cwd, &project)?;
+        self.docker_log_child = Some(child);
+        self.docker_log_rx = Some(rx);
+        Ok(())
+    }
+
+    /// Re-read the `.env` files and the config derived from them after an
+    /// edit. Running tasks keep going; tasks dropped from the config are stopped.
+    pub async fn reload_env(&mut self) -> Result<()> {
+        let loaded = crate::env::reload_env(&self.cfg.cwd, &self.cfg.profile)?;
+        let cfg = crate::config::get_config(&self.cfg.profile);
+
+        let names: HashSet<&String> = cfg.post_up_tasks.iter().map(|t| &t.name).collect();
+        let removed: Vec<String> = self.tasks.keys()
+            .filter(|n| !names.contains(n) && !self.action_runs.contains(n))
+            .cloned()
+            .collect();
+        for name in removed {
+            self.stop_task(&name).await;
+            self.tasks.remove(&name);
+        }
+        for t in &cfg.post_up_tasks {
+            match self.tasks.get_mut(&t.name) {
+                Some(rt) => rt.spec = t.clone(),
+                None => {
+                    self.tasks.insert(t.name.clone(), TaskRuntime::new(t.clone()));
+                }
+            }
+        }
+
+        self.cfg = cfg;
+        self.apply_compose_files();
+        self.report_key_conflicts();
+        self.env_files = loaded.files;
+        for w in &loaded.warnings {
+            self.push_app_log(&format!("⚠️ env: {w}"));
+        }
+        if self.docker.available {
+            let _ = self.refresh_containers().await;
+        }
+        self.rebuild_items();
+        self.notify("🔄 Environment reloaded".to_string(), ratatui::style::Color::Green);
+        Ok(())
+    }
+
+    /// Hand the `COMPOSE_FILES` that exist to compose calls, warning in the
+    /// app log about the others.
+    pub fn apply_compose_files(&mut self) {
+        let (found, missing): (Vec<_>, Vec<_>) = self.cfg.compose_files.iter().cloned().partition(|f| f.exists());
+        for f in missing {
+            self.push_app_log(&format!("⚠️ COMPOSE_FILES: {} not found, skipped", f.display()));
+        }
+        self.docker.compose_files = found;
+    }
+
+    /// Log the `KEY_*` bindings that could not be applied.
+    pub fn report_key_conflicts(&mut self) {
+        for conflict in self.cfg.keys.conflicts.clone() {
+            self.push_app_log(&format!("⚠️ keys: {conflict}"));
+        }
+    }
+
+    /// Seconds left before the open confirm popup is dismissed, if a timeout
+    /// is configured.
+    pub fn confirm_remaining(&self) -> Option<u64> {
+        let timeout = self.cfg.confirm_timeout?;
+        let opened = self.confirm_opened?;
+        Some(timeout.saturating_sub(opened.elapsed()).as_secs())
+    }
+
+    /// Dismiss an unanswered confirm popup once `CONFIRM_TIMEOUT` elapses.
+    /// Closing the popup is the safe answer for all of them (Cancel / Keep),
+    /// and it lets the refresh loop run again.
+    pub fn expire_confirm(&mut self) {
+        if !self.popup.as_ref().is_some_and(Popup::is_confirm) {
+            self.confirm_opened = None;
+            return;
+        }
+        let Some(timeout) = self.cfg.confirm_timeout else {
+            return;
+        };
+        let opened = *self.confirm_opened.get_or_insert_with(std::time::Instant::now);
+        if opened.elapsed() >= timeout {
+            self.popup = None;
+            self.confirm_opened = None;
+            self.push_app_log(&format!("⏱ No answer after {}s, confirmation dismissed.", timeout.as_secs()));
+            self.notify("⏱ Confirmation timed out — nothing was changed".to_string(), ratatui::style::Color::Yellow);
+        }
+    }
+
+    /// Services of the active profile without a running container, diffed
+    /// against the cached container list by compose project/service labels.
+    pub async fn missing_compose_services(&self) -> Result<Vec<String>> {
+        let (project, services) = docker::list_compose_services(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile).await?;
+        let running: HashSet<&str> = self.containers.iter()
+            .map(|(c, _)| c)
+            .filter(|c| c.state.eq_ignore_ascii_case("running") && c.compose_project.as_deref() == Some(project.as_str()))
+            .filter_map(|c| c.compose_service.as_deref())
+            .collect();
+        Ok(services.into_iter().filter(|s| !running.contains(s.as_str())).collect())
+    }
+
+    /// The compose project of the current profile and whether any of its
+    /// containers runs, deciding if `Z` stops or starts the stack.
+    pub async fn stack_running(&self) -> Result<(String, bool)> {
+        let (project, _) = docker::list_compose_services(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile).await?;
+        let running = self.containers.iter().any(|(c, _)| {
+            c.state.eq_ignore_ascii_case("running") && c.compose_project.as_deref() == Some(project.as_str())
+        });
+        Ok((project, running))
+    }
+
+    /// Stream `compose stop` / `compose start` for the whole stack into the
+    /// log pane.
+    pub fn toggle_stack(&mut self, stop: bool) -> Result<()> {
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
+        let verb = if stop { "stop" } else { "start" };
+        self.current_target = format!("compose:{verb}");
+        self.replace_current_logs(vec![
+            format!("$ docker compose --profile {} {verb}", self.cfg.compose_profile),
+            String::new(),
+        ]);
+        let (child, rx) = if stop {
+            docker::compose_stop_all(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile)?
+        } else {
+            docker::compose_start_all(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile)?
+        };
+        self.docker_log_child = Some(child);
+        self.docker_log_rx = Some(rx);
+        Ok(())
+    }
+
+    /// Names of the cached containers matching the `G` prompt's query, with
+    /// their ids.
+    pub fn goto_matches(&self, query: &str) -> Vec<(String, String)> {
+        let names: Vec<(String, String)> = self.containers.iter()
+            .map(|(c, _)| (c.id.clone(), docker::container_name(&c.names)))
+            .collect();
+        let candidates: Vec<(&str, &str)> = names.iter().map(|(id, name)| (id.as_str(), name.as_str())).collect();
+        docker::match_containers(&candidates, query).into_iter().map(|i| names[i].clone()).collect()
+    }
+
+    /// Select container `id` even when the list filter or a collapsed group
+    /// hides it: the filter is cleared and its groups are expanded.
+    pub async fn reveal_container(&mut self, id: &str) -> bool {
+        let position = |app: &Self| app.items.iter().position(|i| i.kind == SidebarKind::Container && i.id == id);
+        if position(self).is_none() {
+            self.filter_query.clear();
+            if let Some((c, _)) = self.containers.iter().find(|(c, _)| c.id == id) {
+                let project = c.compose_project.clone().unwrap_or_else(|| STANDALONE_GROUP.to_string());
+                if let Some(service) = &c.compose_service {
+                    self.expanded_groups.insert(format!("svc:{project}/{service}"));
+                }
+                self.expanded_groups.insert(project);
+            }
+            self.rebuild_items();
+        }
+        match position(self) {
+            Some(idx) => {
+                let _ = self.select(idx).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `l`: from a task declared with `service`, select that service's
+    /// container (a running one first); from a container, select the first
+    /// task linked to its compose service or name.
+    pub async fn jump_to_linked(&mut self) {
+        let Some(item) = self.items.get(self.selected).cloned() else { return };
+        match item.kind {
+            SidebarKind::Task => {
+                let Some(service) = self.tasks.get(&item.id).and_then(|rt| rt.spec.service.clone()) else {
+                    self.notify(format!("⚠️ {} has no (service: …) link", item.name), ratatui::style::Color::Yellow);
+                    return;
+                };
+                let mut linked: Vec<&docker::ContainerSummary> = self.containers.iter()
+                    .map(|(c, _)| c)
+                    .filter(|c| c.compose_service.as_deref() == Some(service.as_str()) || docker::container_name(&c.names) == service)
+                    .collect();
+                linked.sort_by_key(|c| c.state.to_lowercase() != "running");
+                match linked.first().map(|c| c.id.clone()) {
+                    Some(id) => {
+                        self.reveal_container(&id).await;
+                    }
+                    None => self.notify(format!("⚠️ No container for service {service}"), ratatui::style::Color::Yellow),
+                }
+            }
+            SidebarKind::Container => {
+                let service = self.containers.iter()
+                    .find(|(c, _)| c.id == item.id)
+                    .and_then(|(c, _)| c.compose_service.clone());
+                let task = self.cfg.post_up_tasks.iter()
+                    .find(|t| t.service.as_ref().is_some_and(|s| Some(s) == service.as_ref() || *s == item.name))
+                    .map(|t| t.name.clone());
+                let Some(task) = task else {
+                    self.notify(format!("⚠️ No task linked to {}", item.name), ratatui::style::Color::Yellow);
+                    return;
+                };
+                let position = |app: &Self| app.items.iter().position(|i| i.kind == SidebarKind::Task && i.id == task);
+                if position(self).is_none() {
+                    self.filter_query.clear();
+                    self.rebuild_items();
+                }
+                if let Some(idx) = position(self) {
+                    let _ = self.select(idx).await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Restart one compose service, streaming `compose restart` into the log pane.
+    pub fn restart_compose_service(&mut self, service: &str) -> Result<()> {
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
+        self.current_target = format!("compose:restart {service}");
+        self.replace_current_logs(vec![
+            format!("$ docker compose --profile {} restart {service}", self.cfg.compose_profile),
+            String::new(),
+        ]);
+        let (child, rx) = docker::compose_restart_service(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile, service)?;
+        self.docker_log_child = Some(child);
+        self.docker_log_rx = Some(rx);
+        Ok(())
+    }
+
+    /// Run `docker compose <input>` for the `:` prompt: streamed into the log
+    /// pane, or handed the terminal when the subcommand is interactive.
+    pub fn run_compose_command(&mut self, input: &str) -> Result<()> {
+        let args = docker::split_args(input);
+        if args.is_empty() {
+            return Ok(());
+        }
+        if docker::compose_is_interactive(&args) {
+            self.pending_foreground = Some(ForegroundJob::Compose(args));
+            return Ok(());
+        }
+
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
+        self.current_target = format!("compose:{}", args.join(" "));
+        self.replace_current_logs(vec![
+            format!("$ docker compose --profile {} {}", self.cfg.compose_profile, args.join(" ")),
+            String::new(),
+        ]);
+        let (child, rx) = docker::compose_raw(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile, &args)?;
+        self.docker_log_child = Some(child);
+        self.docker_log_rx = Some(rx);
+        Ok(())
+    }
+
+    /// Save `command` under `name`, replacing a bookmark of the same name.
+    pub fn save_bookmark(&mut self, name: &str, command: &str) {
+        let name = if name.trim().is_empty() { command.trim() } else { name.trim() };
+        let bookmark = Bookmark { name: name.to_string(), command: command.trim().to_string() };
+        match self.bookmarks.iter_mut().find(|b| b.name == bookmark.name) {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+        bookmarks::save_bookmarks(&self.cfg.cwd, &self.bookmarks);
+    }
+
+    pub fn delete_bookmark(&mut self, idx: usize) {
+        if idx < self.bookmarks.len() {
+            self.bookmarks.remove(idx);
+            bookmarks::save_bookmarks(&self.cfg.cwd, &self.bookmarks);
+        }
+    }
+
     pub async fn show_system_health(&mut self) -> Result<()> {
         let df = docker::get_system_df(&self.docker, &self.cfg.cwd).await?;
         self.popup = Some(Popup::SystemHealth { data: df });
         Ok(())
     }
 
+    pub async fn show_compose_ps(&mut self) -> Result<()> {
+        let rows = docker::compose_ps(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile).await?;
+        self.popup = Some(Popup::ComposePs { rows, selected: 0 });
+        Ok(())
+    }
+
     pub async fn trigger_prune(&mut self) -> Result<()> {
-        self.push_current_log("🧹 Starting System Prune...");
+        self.push_app_log("🧹 Starting System Prune...");
         let out = docker::system_prune(&self.docker, &self.cfg.cwd).await?;
-        self.push_current_log("✨ System Prune completed.");
+        self.push_app_log("✨ System Prune completed.");
         for line in out.lines() {
-             self.push_current_log(&format!("  {}", line));
+             self.push_app_log(&format!("  {}", line));
         }
         self.popup = None;
         Ok(())
     }
 
     pub async fn switch_context_and_refresh(&mut self, name: String) -> Result<()> {
-        docker::use_context(&self.docker, &self.cfg.cwd, &name).await?;
+        let mut new_meta = docker::DockerMeta::detect_context(&self.cfg.cwd, &self.cfg.docker_bin, Some(&name), self.docker.history.clone()).await;
+        if !new_meta.available {
+            return Err(anyhow!("context {name} is not reachable"));
+        }
+        new_meta.compose_files = std::mem::take(&mut self.docker.compose_files);
+        self.docker = new_meta;
         self.refresh_all_after_context_switch().await
     }
 
     pub async fn refresh_all_after_context_switch(&mut self) -> Result<()> {
-        let new_meta = docker::DockerMeta::detect(&self.cfg.cwd, &self.cfg.docker_bin).await;
-        self.docker = new_meta;
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
         
         self.containers.clear();
         self.swarm_services.clear();
@@ -765,67 +1992,137 @@ impl App {
         self.rebuild_items();
         let _ = self.select(0).await;
         
-        self.push_current_log(&format!("✅ Switched to context: {}", self.docker.context_name));
+        self.push_app_log(&format!("✅ Switched to context: {}", self.docker.context_name));
         Ok(())
     }
 
+    /// Overwrite the earlier line carrying the same progress key (build step
+    /// digest, pulled layer id) instead of appending `data`. Returns false
+    /// when `data` is not a progress update and must be appended as usual.
+    fn collapse_progress_line(&mut self, data: &str) -> bool {
+        // How far back an update may land; older progress lines stay as they are.
+        const WINDOW: usize = 300;
+        let Some(line) = data.strip_suffix('\n') else {
+            return false;
+        };
+        let Some(key) = docker::progress_key(line) else {
+            return false;
+        };
+        // The last entry is the partial line being filled by push_partial_log.
+        let done = self.log_lines.len().saturating_sub(1);
+        let found = self.log_lines.iter()
+            .take(done)
+            .rev()
+            .take(WINDOW)
+            .position(|l| docker::progress_key(l).as_deref() == Some(key.as_str()));
+        match found {
+            Some(back) => {
+                let old = std::mem::replace(&mut self.log_lines[done - 1 - back], line.to_string());
+                self.log_bytes = self.log_bytes - old.len() + line.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drain log streams and task output. While a popup is open, the stream
+    /// of the target on screen is left in its channel so the log pane holds
+    /// still; it catches up once the popup closes. Other tasks keep draining.
     pub async fn pump_background(&mut self) {
+        let hold_current = self.popup.is_some();
         let mut shell_data = Vec::new();
-        if let Some(rx) = self.docker_log_rx.as_mut() {
+        if let Some(rx) = self.docker_log_rx.as_mut().filter(|_| !hold_current) {
             while let Ok(data) = rx.try_recv() {
                 shell_data.push(data);
             }
         }
+        let mut compose_end = None;
+        if let Some(job) = self.compose_job.as_mut() {
+            let on_screen = job.target == self.current_target;
+            if !(hold_current && on_screen) {
+                while let Ok(data) = job.rx.try_recv() {
+                    if data.starts_with("==> ") {
+                        compose_end = Some(data.clone());
+                    }
+                    if on_screen {
+                        shell_data.push(data);
+                    }
+                }
+            }
+        }
+        if self.log_recording.as_ref().is_some_and(|r| r.target != self.current_target) {
+            self.stop_log_recording();
+        }
+        let collapse = self.cfg.collapse_progress && self.current_target.starts_with("compose:");
+        let container = self.shows_container_logs();
         for data in shell_data {
-            self.push_partial_log(&data);
+            if let Some(rec) = self.log_recording.as_mut() {
+                use std::io::Write;
+                let raw = if container { strip_stream_tags(&data) } else { data.as_str().into() };
+                if let Err(e) = rec.file.write_all(raw.as_bytes()) {
+                    self.notify(format!("❌ Recording stopped: {e}"), ratatui::style::Color::Red);
+                    self.log_recording = None;
+                } else {
+                    rec.bytes += raw.len();
+                }
+            }
+            if !(collapse && self.collapse_progress_line(&data)) {
+                self.push_partial_log(&data);
+            }
+        }
+        if let Some(end) = compose_end {
+            if let Some(job) = self.compose_job.take() {
+                self.finish_compose_job(job, &end).await;
+            }
         }
 
         let current = self.current_target.clone();
         let max_lines = self.cfg.max_log_lines;
+        let max_bytes = self.cfg.max_log_bytes;
+        let max_line_bytes = self.cfg.max_line_bytes;
 
         let mut ui_append: Vec<String> = Vec::new();
         let mut ui_replace: Option<Vec<String>> = None;
+        let mut failed: Vec<(String, i32)> = Vec::new();
 
         for (name, rt) in self.tasks.iter_mut() {
-            if let Some(rx) = rt.rx.as_mut() {
-                loop {
-                    match rx.try_recv() {
-                        Ok(line) => {
-                            let full = format!("[{name}] {line}");
-                            rt.lines.push_back(full.clone());
-                            while rt.lines.len() > max_lines {
-                                rt.lines.pop_front();
-                            }
-                            if current == *name {
-                                ui_append.push(full);
-                            }
-                        }
-                        Err(_) => break,
+            if hold_current && current == *name {
+                continue;
+            }
+            if let Some(mut rx) = rt.rx.take() {
+                let mut received = 0;
+                while let Ok(line) = rx.try_recv() {
+                    received += 1;
+                    let full = format!("[{name}] {}", docker::truncate_line(&line, max_line_bytes));
+                    rt.push_line(full.clone(), max_lines, max_bytes);
+                    if current == *name {
+                        ui_append.push(full);
                     }
                 }
+                rt.rx = Some(rx);
+                if received > 0 {
+                    rt.note_output(std::time::Instant::now(), received);
+                }
             }
 
             if let Some(child) = rt.child.as_mut() {
-                match child.try_wait() {
-                    Ok(Some(status)) => {
-                        let code = status.code().unwrap_or(if status.success() { 0 } else { 1 });
-                        rt.child = None;
-                        rt.rx = None;
-                        if code == 0 {
-                            rt.status = TaskStatus::Ok;
-                            rt.lines.push_back("==> OK".to_string());
-                        } else {
-                            rt.status = TaskStatus::Fail;
-                            rt.lines.push_back(format!("==> FAIL (exit {code})"));
-                        }
-                        while rt.lines.len() > max_lines {
-                            rt.lines.pop_front();
-                        }
-                        if current == *name {
-                            ui_replace = Some(rt.lines.iter().cloned().collect::<Vec<_>>());
+                if let Ok(Some(status)) = child.try_wait() {
+                    let code = status.code().unwrap_or(if status.success() { 0 } else { 1 });
+                    rt.child = None;
+                    rt.rx = None;
+                    if code == 0 {
+                        rt.status = TaskStatus::Ok;
+                        rt.push_line("==> OK".to_string(), max_lines, max_bytes);
+                    } else {
+                        rt.status = TaskStatus::Fail;
+                        rt.push_line(format!("==> FAIL (exit {code})"), max_lines, max_bytes);
+                        if current != *name {
+                            failed.push((name.clone(), code));
                         }
                     }
-                    _ => {}
+                    if current == *name {
+                        ui_replace = Some(rt.lines.iter().cloned().collect::<Vec<_>>());
+                    }
                 }
             }
         }
@@ -837,6 +2134,15 @@ impl App {
                 self.push_current_log(&l);
             }
         }
+        if self.cfg.notify_task_failures && !failed.is_empty() {
+            for (name, code) in failed {
+                self.push_app_log(&format!("🔴 Task {name} failed (exit {code})"));
+                self.notify(format!("🔴 Task {name} failed (exit {code})"), ratatui::style::Color::Red);
+                self.unseen_failures.insert(name);
+            }
+            self.rebuild_items();
+        }
+        self.enforce_log_budget();
     }
 
     pub async fn run_task(&mut self, task_name: &str) -> Result<()> {
@@ -847,9 +2153,18 @@ impl App {
             }
             rt.child = None;
             rt.rx = None;
-            rt.lines.clear();
+            rt.clear_lines();
             rt.status = TaskStatus::Run;
-            rt.lines.push_back(format!("==> RESTART: {}", rt.spec.cmd));
+            self.unseen_failures.remove(task_name);
+            rt.next_run = rt.spec.every.map(|every| std::time::Instant::now() + every);
+            rt.recent_output.clear();
+            rt.last_output = Some(std::time::Instant::now());
+            let mut cmd_lines = crate::env::command_lines(&rt.spec.cmd).into_iter();
+            let (max_lines, max_bytes) = (self.cfg.max_log_lines, self.cfg.max_log_bytes);
+            rt.push_line(format!("==> RESTART: {}", cmd_lines.next().unwrap_or_default()), max_lines, max_bytes);
+            for l in cmd_lines {
+                rt.push_line(format!("             {l}"), max_lines, max_bytes);
+            }
             let (child, rx) = tasks::spawn_task(&rt.spec.cmd, &self.cfg.cwd)?;
             rt.child = Some(child);
             rt.rx = Some(rx);
@@ -863,19 +2178,72 @@ impl App {
         Ok(())
     }
 
+    /// Run a `CONTAINER_ACTIONS` entry against the selected container as the
+    /// task `action@container`, then show its output.
+    pub async fn run_container_action(&mut self, action: &ContainerAction) {
+        let Some(item) = self.items.get(self.selected).filter(|i| i.kind == SidebarKind::Container).cloned() else {
+            self.notify(format!("⚠️ {} runs on a container; select one first", action.name), ratatui::style::Color::Yellow);
+            return;
+        };
+        let port = docker::pick_best_public_port(&item.ports, &self.cfg.open_preferred_ports);
+        let Some(cmd) = action.render(&item.id, &item.name, port) else {
+            self.notify(format!("❌ {}: {} publishes no port", action.name, item.name), ratatui::style::Color::Red);
+            return;
+        };
+        let task_name = format!("{}@{}", action.name, item.name);
+        let spec = TaskSpec { name: task_name.clone(), cmd, every: None, plain: false, service: None };
+        match self.tasks.get_mut(&task_name) {
+            Some(rt) => rt.spec = spec,
+            None => {
+                self.tasks.insert(task_name.clone(), TaskRuntime::new(spec));
+            }
+        }
+        if !self.action_runs.contains(&task_name) {
+            self.action_runs.push(task_name.clone());
+        }
+        self.push_app_log(&format!("⚡ {task_name}"));
+        if let Err(e) = self.run_task(&task_name).await {
+            self.push_app_log(&format!("❌ {task_name} failed to start: {e}"));
+            return;
+        }
+        self.rebuild_items();
+        if let Some(idx) = self.items.iter().position(|i| i.kind == SidebarKind::Task && i.id == task_name) {
+            let _ = self.select(idx).await;
+        }
+    }
+
+    /// Re-run scheduled tasks whose timer elapsed. A task still running when
+    /// due is picked up on the first check after it finishes.
+    pub async fn run_due_tasks(&mut self) {
+        let now = std::time::Instant::now();
+        let due: Vec<String> = self
+            .tasks
+            .iter()
+            .filter(|(_, rt)| rt.status != TaskStatus::Run && rt.next_run.is_some_and(|at| at <= now))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in due {
+            if let Err(e) = self.run_task(&name).await {
+                self.push_app_log(&format!("❌ Scheduled run of {name} failed: {e}"));
+            }
+        }
+        // Keep the countdown in the task labels ticking.
+        if self.tasks.values().any(|rt| rt.next_run.is_some()) {
+            self.rebuild_items();
+        }
+    }
+
     pub async fn stop_task(&mut self, task_name: &str) {
         let mut snapshot_for_ui: Option<Vec<String>> = None;
         if let Some(rt) = self.tasks.get_mut(task_name) {
             if let Some(child) = &rt.child {
                 tasks::kill_process_group(child);
                 rt.status = TaskStatus::Stop;
-                rt.lines.push_back("==> STOPPED (user)".to_string());
+                rt.push_line("==> STOPPED (user)".to_string(), self.cfg.max_log_lines, self.cfg.max_log_bytes);
             }
             rt.child = None;
             rt.rx = None;
-            while rt.lines.len() > self.cfg.max_log_lines {
-                rt.lines.pop_front();
-            }
+            rt.next_run = None;
             if self.current_target == task_name {
                 snapshot_for_ui = Some(rt.lines.iter().cloned().collect::<Vec<_>>());
             }
@@ -885,53 +2253,147 @@ impl App {
         }
     }
 
-    pub async fn compose_up_or_restart(&mut self, restart: bool) {
+    /// Start `compose restart` (falling back to `up -d` if it fails) or
+    /// `compose up -d` in the background, streaming into the log pane.
+    pub fn compose_up_or_restart(&mut self, restart: bool) {
         let profile = self.cfg.compose_profile.clone();
-        let cwd = self.cfg.cwd.clone();
-        if restart {
-            self.push_current_log(&format!("Restarting services (profile: {profile})..."));
-            let code = docker::docker_compose(&self.docker, &cwd, &profile, &["restart"]).await.unwrap_or(1);
-            if code != 0 {
-                self.push_current_log(&format!("Restart failed (exit {code}) → fallback: up -d"));
-                let code2 = docker::docker_compose(&self.docker, &cwd, &profile, &["up", "-d"]).await.unwrap_or(1);
-                if code2 != 0 {
-                    self.push_current_log(&format!("Compose up failed (exit {code2})"));
-                } else {
-                    self.push_current_log("Compose up OK");
-                }
-            } else {
-                self.push_current_log("Compose restart OK");
-            }
+        let (args, verb) = if restart {
+            (vec!["restart".to_string()], "Restarting")
         } else {
-            self.push_current_log(&format!("Starting services (profile: {profile})..."));
-            let code = docker::docker_compose(&self.docker, &cwd, &profile, &["up", "-d"]).await.unwrap_or(1);
-            if code != 0 {
-                self.push_current_log(&format!("Compose up FAILED (exit {code})"));
-            } else {
-                self.push_current_log("Compose up OK");
-            }
+            (vec!["up".to_string(), "-d".to_string()], "Starting")
+        };
+        self.push_app_log(&format!("{verb} services (profile: {profile})..."));
+        if let Err(e) = self.start_compose_job(args, restart) {
+            self.push_app_log(&format!("❌ docker compose failed to start: {e}"));
         }
+    }
 
+    /// Run `docker compose --profile <p> <args>` as the background job,
+    /// replacing whatever the log pane was following.
+    pub fn start_compose_job(&mut self, args: Vec<String>, fallback_up: bool) -> Result<()> {
+        if let Some(mut c) = self.docker_log_child.take() {
+            c.kill();
+        }
+        self.docker_log_rx = None;
+        let target = format!("compose:{}", args.join(" "));
+        self.current_target = target.clone();
+        self.replace_current_logs(vec![
+            format!("$ docker compose --profile {} {}", self.cfg.compose_profile, args.join(" ")),
+            String::new(),
+        ]);
+        let (child, rx) = docker::compose_raw(&self.docker, &self.cfg.cwd, &self.cfg.compose_profile, &args)?;
+        self.compose_job = Some(ComposeJob { args, target, started: std::time::Instant::now(), fallback_up, recovery: None, rx, _child: child });
+        Ok(())
+    }
+
+    /// Log how the compose job ended, then fall back to `up -d` or refresh.
+    async fn finish_compose_job(&mut self, job: ComposeJob, end: &str) {
+        let code = match end.trim_end() {
+            "==> OK" => 0,
+            other => other.strip_prefix("==> FAIL (exit ")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|c| c.parse().ok())
+                .unwrap_or(1),
+        };
+        let command = format!("docker compose {}", job.args.join(" "));
+        self.push_app_log(&format!("$ {command} → exit {code} ({}s)", job.started.elapsed().as_secs()));
+        if code != 0 && job.fallback_up {
+            self.push_app_log(&format!("Restart failed (exit {code}) → fallback: up -d"));
+            if let Err(e) = self.start_compose_job(vec!["up".to_string(), "-d".to_string()], false) {
+                self.push_app_log(&format!("❌ docker compose failed to start: {e}"));
+            }
+            return;
+        }
+        if code == 0 {
+            self.notify(format!("✅ {command} done"), ratatui::style::Color::Green);
+            if let Some((done, cmd)) = job.recovery {
+                self.show_recovery(&done, Some(cmd));
+            }
+        } else {
+            self.notify(format!("❌ {command} failed (exit {code})"), ratatui::style::Color::Red);
+        }
         let _ = self.refresh_containers().await;
         self.rebuild_items();
-        let _ = self.select(self.selected).await;
     }
 
-    pub async fn open_selected_in_browser(&mut self) {
+    /// URL `o` would open for the selected item. Logs why when there is none.
+    pub fn selected_url(&mut self) -> Option<String> {
         if self.items.is_empty() {
-            return;
+            return None;
         }
         let item = self.items[self.selected].clone();
         if item.kind != SidebarKind::Container && item.kind != SidebarKind::SwarmService {
-            return;
+            return None;
         }
-        let port = docker::pick_best_public_port(&item.ports);
+        let port = docker::pick_best_public_port(&item.ports, &self.cfg.open_preferred_ports);
         if let Some(pubp) = port {
             let host = if self.docker.remote_host.is_empty() { "localhost" } else { &self.docker.remote_host };
-            let url = format!("http://{host}:{pubp}");
-            let _ = open::that(url);
+            Some(format!("http://{host}:{pubp}"))
         } else {
-            self.push_current_log(&format!("No public tcp port for {}", item.name));
+            self.push_app_log(&format!("No public tcp port for {}", item.name));
+            None
+        }
+    }
+
+    pub async fn open_selected_in_browser(&mut self) {
+        if let Some(url) = self.selected_url() {
+            let _ = open::that(url);
+        }
+    }
+
+    /// Open the `slot`-th (0-based) `QUICK_OPEN` entry, whatever is selected.
+    /// The name matches a container name or its compose service; the port may
+    /// be the container port or the published one.
+    pub fn quick_open(&mut self, slot: usize) {
+        let Some((name, port)) = self.cfg.quick_open.get(slot).cloned() else {
+            return;
+        };
+        let found = self.containers.iter()
+            .find(|(c, _)| docker::container_name(&c.names) == name)
+            .or_else(|| self.containers.iter().find(|(c, _)| c.compose_service.as_deref() == Some(name.as_str())));
+        let Some((_, ports)) = found else {
+            self.notify(format!("❌ Quick open: no container named {name}"), ratatui::style::Color::Red);
+            return;
+        };
+        let public = ports.iter()
+            .find(|p| p.private_port == Some(port) && p.public_port.is_some())
+            .and_then(|p| p.public_port)
+            .or_else(|| ports.iter().find_map(|p| p.public_port.filter(|&pp| pp == port)));
+        let Some(public) = public else {
+            self.notify(format!("❌ Quick open: {name} does not publish port {port}"), ratatui::style::Color::Red);
+            return;
+        };
+        let host = if self.docker.remote_host.is_empty() { "localhost" } else { &self.docker.remote_host };
+        let url = format!("http://{host}:{public}");
+        self.push_app_log(&format!("🔗 F{} → {name}: {url}", slot + 1));
+        if open::that(&url).is_err() {
+            self.notify(format!("❌ Cannot open {url}"), ratatui::style::Color::Red);
+        }
+    }
+
+    pub fn copy_selected_url(&mut self) {
+        if let Some(url) = self.selected_url() {
+            self.push_app_log(&format!("🔗 {url}"));
+            self.copy_to_clipboard(url.clone(), &format!("Copied {url}"));
+        }
+    }
+
+    /// Put `text` on the system clipboard and toast `done`. Headless and SSH
+    /// sessions usually have no clipboard: the reason and the alternatives go
+    /// to the app log instead.
+    pub fn copy_to_clipboard(&mut self, text: String, done: &str) -> bool {
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(text)) {
+            Ok(()) => {
+                self.notify(format!("📋 {done}"), ratatui::style::Color::Cyan);
+                true
+            }
+            Err(e) => {
+                self.push_app_log(&format!(
+                    "⚠️ Clipboard unavailable ({e}); without a display (SSH, headless) select with the mouse (m, Ctrl+T) or export the logs with E"
+                ));
+                self.notify("⚠️ Clipboard unavailable (see app log)".to_string(), ratatui::style::Color::Yellow);
+                false
+            }
         }
     }
 
@@ -939,11 +2401,45 @@ impl App {
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
         let filename = format!("logs_{}_{}.txt", self.current_target.replace("/", "_"), timestamp);
         let path = self.cfg.cwd.join(&filename);
-        let content = self.log_lines.iter().map(|s| s.to_owned()).collect::<Vec<String>>().join("\n");
-        std::fs::write(&path, content)?;
+        std::fs::write(&path, self.log_text())?;
         Ok(filename)
     }
 
+    /// Start appending everything the current follower streams to a file,
+    /// or stop if a recording is running.
+    pub fn toggle_log_recording(&mut self) -> Result<()> {
+        if self.log_recording.is_some() {
+            self.stop_log_recording();
+            return Ok(());
+        }
+        if self.docker_log_rx.is_none() || self.shell_active {
+            return Err(anyhow!("no log stream is being followed"));
+        }
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let file_name = format!("logs_{}_{}.rec.txt", self.current_target.replace(['/', ':'], "_"), timestamp);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.cfg.cwd.join(&file_name))?;
+        self.notify(format!("⏺ Recording to {file_name}"), ratatui::style::Color::Red);
+        self.log_recording = Some(LogRecording {
+            target: self.current_target.clone(),
+            file_name,
+            file,
+            bytes: 0,
+        });
+        Ok(())
+    }
+
+    pub fn stop_log_recording(&mut self) {
+        if let Some(rec) = self.log_recording.take() {
+            self.notify(
+                format!("⏹ Saved {} ({} KB)", rec.file_name, rec.bytes / 1024),
+                ratatui::style::Color::Cyan,
+            );
+        }
+    }
+
     pub fn toggle_sort(&mut self, next: SortBy) {
         if self.sort_by == next {
             self.sort_order = match self.sort_order {
@@ -956,3 +2452,37 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica(id: &str, service: &str) -> (docker::ContainerSummary, Vec<docker::Port>) {
+        let mut c: docker::ContainerSummary = serde_json::from_value(serde_json::json!({
+            "ID": id, "Names": format!("app-{id}"), "State": "running", "Status": "Up 1 minute", "Ports": ""
+        }))
+        .unwrap();
+        c.compose_project = Some("app".to_string());
+        c.compose_service = Some(service.to_string());
+        (c, vec![])
+    }
+
+    #[test]
+    fn test_track_restarts_counts_replacements_only() {
+        let mut app = App::new(crate::config::offline_config(std::env::temp_dir()), docker::DockerMeta::offline("docker"));
+        let key = ("app".to_string(), "web".to_string());
+        app.containers = vec![replica("web1", "web"), replica("web2", "web")];
+        app.track_restarts();
+        app.track_restarts();
+        assert_eq!(app.recreations.get(&key), None);
+
+        app.containers.push(replica("web3", "web"));
+        app.track_restarts();
+        assert_eq!(app.recreations.get(&key), None);
+
+        app.containers.retain(|(c, _)| c.id != "web1");
+        app.containers.push(replica("web4", "web"));
+        app.track_restarts();
+        assert_eq!(app.recreations.get(&key), Some(&1));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::ui::app::App;
+use crate::docker;
+use crate::ui::app::{App, NEW_SINCE_MARKER};
+use crate::ui::types::{stream_tag, TaskStream};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
@@ -16,13 +18,26 @@ pub fn draw_logs(f: &mut Frame, app: &mut App, area: Rect) {
         Style::default().fg(color_primary)
     };
 
+    let matches = app.log_matches();
+    let current_match = app.log_match.filter(|_| !matches.is_empty()).map(|i| i.min(matches.len() - 1));
+    let match_counter = match current_match {
+        _ if app.log_query().is_empty() => String::new(),
+        Some(i) => format!(" 🔍 match {}/{} (n/N) |", i + 1, matches.len()),
+        None => format!(" 🔍 {} matches (n/N) |", matches.len()),
+    };
+
     let (title, border_style_logs_actual) = if app.shell_active {
         (
             format!(" 🐚 SHELL — {t} (Esc to exit) ", t = app.current_target),
             Style::default().fg(color_secondary).add_modifier(Modifier::BOLD)
         )
     } else {
-        let follow_status = if app.follow_mode { "[FOLLOWING]" } else { "[PAUSED]" };
+        let follow_status = match (app.follow_mode, app.mouse_capture) {
+            (true, true) => "[FOLLOWING]",
+            (false, true) => "[PAUSED]",
+            (true, false) => "[FOLLOWING] [🖱 OFF]",
+            (false, false) => "[PAUSED] [🖱 OFF]",
+        };
         let t = if app.current_target.is_empty() {
             format!(" 📑 Logs {} ", follow_status)
         } else {
@@ -33,6 +48,19 @@ pub fn draw_logs(f: &mut Frame, app: &mut App, area: Rect) {
             };
             format!(" 📑 Logs {} — {t}{stats_text} ", follow_status, t = app.current_target, stats_text = stats_text)
         };
+        let t = match &app.log_recording {
+            Some(rec) => format!(" ⏺ REC {} ({} KB) |{t}", rec.file_name, rec.bytes / 1024),
+            None => t,
+        };
+        let t = match &app.compose_job {
+            Some(job) => format!(" ⏳ compose {} {}s |{t}", job.args.join(" "), job.started.elapsed().as_secs()),
+            None => t,
+        };
+        let t = match app.active_task_stream() {
+            TaskStream::Both => t,
+            stream => format!(" [{}] (Y) |{t}", stream.label()),
+        };
+        let t = format!("{match_counter}{t}");
         (t, border_style_logs)
     };
 
@@ -52,54 +80,77 @@ pub fn draw_logs(f: &mut Frame, app: &mut App, area: Rect) {
         (None, area, None)
     };
 
-    let query_lower = app.log_filter_query.to_lowercase();
-    let is_active_filter = !query_lower.is_empty();
+    let re = app.log_regex();
+    let is_active_filter = re.is_some();
+    // Searching keeps every line; only the filter hides the others.
+    let hide_misses = is_active_filter && app.log_search.is_none();
+    let current_row = current_match.map(|i| app.log_row(matches[i], i));
 
+    let stream = app.active_task_stream();
     let mut filtered_lines = Vec::new();
-    for l in app.log_lines.iter() {
-        if !is_active_filter || l.to_lowercase().contains(&query_lower) {
+    for l in app.log_lines.iter().filter(|l| stream.shows(l)) {
+        if !hide_misses || re.as_ref().is_some_and(|re| re.is_match(l)) {
             filtered_lines.push(l.clone());
         }
     }
 
+    // `(plain)` tasks keep their tags in the buffer (for `Y`) but not on screen,
+    // and so do containers, whose stderr is only tagged to be drawn in red.
+    let plain = app.tasks.get(&app.current_target).is_some_and(|rt| rt.spec.plain) || app.shows_container_logs();
+
+    // Interleaved views get `docker compose logs`-style coloured prefixes.
+    let aggregate = ["project:", "svc:", "compose:"].iter().any(|p| app.current_target.starts_with(p))
+        || app.current_target == crate::ui::app::STACK_LOGS_ID;
+
     let log_text_lines: Vec<Line> = filtered_lines
         .into_iter()
-        .map(|l| {
+        .enumerate()
+        .map(|(row, l)| {
+            let match_bg = if is_active_filter && current_row == Some(row) { Color::LightRed } else { Color::Yellow };
             let mut base_style = Style::default().fg(Color::White);
+            let tag = stream_tag(&l);
+            let stream = tag.as_ref().map(|(s, _)| *s);
+            let l = match tag {
+                Some((_, range)) if plain => format!("{}{}", &l[..range.start], &l[range.end..]),
+                _ => l,
+            };
             let lower_l = l.to_lowercase();
-            if lower_l.contains("error") || lower_l.contains("fail") || lower_l.starts_with("[err]") || lower_l.contains("exception") {
+            if lower_l.contains("error") || lower_l.contains("fail") || stream == Some(TaskStream::Err) || lower_l.contains("exception") {
                 base_style = base_style.fg(Color::Red);
             } else if lower_l.contains("warn") {
                 base_style = base_style.fg(Color::Yellow);
-            } else if lower_l.contains("success") || lower_l.starts_with("[out]") || lower_l.contains("done") || lower_l.contains("=> ok") {
+            } else if lower_l.contains("success") || lower_l.contains("done") || lower_l.contains("=> ok") {
                 base_style = base_style.fg(Color::Green);
             }
+            let prefix = docker::log_prefix(&l)
+                .filter(|_| aggregate)
+                .map(|(name, end)| (end, Style::default().fg(app.prefix_color(name)).add_modifier(Modifier::BOLD)));
 
-            if l.starts_with('❯') {
+            if l.starts_with(NEW_SINCE_MARKER) {
+                Line::from(Span::styled(l, Style::default().fg(color_secondary).add_modifier(Modifier::BOLD)))
+            } else if l.starts_with('❯') {
                 Line::from(vec![
                     Span::styled(" ❯ ", Style::default().fg(color_secondary).add_modifier(Modifier::BOLD)),
                     Span::styled(l.trim_start_matches('❯').trim().to_string(), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
                 ])
-            } else if is_active_filter {
+            } else if let Some(re) = &re {
                 // Highlight matches
                 let mut spans = Vec::new();
                 let mut last_idx = 0;
-                for (idx, _) in lower_l.match_indices(&query_lower) {
-                    if idx > last_idx {
-                        spans.push(Span::styled(l[last_idx..idx].to_string(), base_style));
-                    }
+                for m in re.find_iter(&l).filter(|m| !m.is_empty()) {
+                    push_plain(&mut spans, &l, last_idx, m.start(), base_style, prefix);
                     spans.push(Span::styled(
-                        l[idx..idx + query_lower.len()].to_string(),
-                        Style::default().bg(Color::Yellow).fg(Color::Black).add_modifier(Modifier::BOLD),
+                        m.as_str().to_string(),
+                        Style::default().bg(match_bg).fg(Color::Black).add_modifier(Modifier::BOLD),
                     ));
-                    last_idx = idx + query_lower.len();
-                }
-                if last_idx < l.len() {
-                    spans.push(Span::styled(l[last_idx..].to_string(), base_style));
+                    last_idx = m.end();
                 }
+                push_plain(&mut spans, &l, last_idx, l.len(), base_style, prefix);
                 Line::from(spans)
             } else {
-                Line::from(Span::styled(l, base_style))
+                let mut spans = Vec::new();
+                push_plain(&mut spans, &l, 0, l.len(), base_style, prefix);
+                Line::from(spans)
             }
         })
         .collect();
@@ -109,7 +160,7 @@ pub fn draw_logs(f: &mut Frame, app: &mut App, area: Rect) {
     let log_height = right_pane_history.height.saturating_sub(2);
     app.last_log_height = log_height;
 
-    let total_lines = app.log_lines.len() as u16;
+    let total_lines = log_text.lines.len() as u16;
     if app.stick_to_bottom {
         app.log_scroll = total_lines.saturating_sub(log_height);
     } else {
@@ -125,24 +176,65 @@ pub fn draw_logs(f: &mut Frame, app: &mut App, area: Rect) {
         .scroll((app.log_scroll, 0));
     f.render_widget(logs, right_pane_history);
 
-    if let (Some(stats_area), Some(stats)) = (stats_area, &app.container_stats) {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-            .split(stats_area);
+    // Match minimap on the right border: where the hits sit in the whole buffer.
+    let gutter_height = right_pane_history.height.saturating_sub(2);
+    if is_active_filter && !matches.is_empty() && gutter_height > 0 && right_pane_history.width > 0 {
+        let total = app.log_lines.len().max(1);
+        let x = right_pane_history.x + right_pane_history.width - 1;
+        let row_of = |idx: usize| (idx * gutter_height as usize / total) as u16;
+        let buf = f.buffer_mut();
+        for &idx in &matches {
+            buf[(x, right_pane_history.y + 1 + row_of(idx))].set_symbol("▐").set_fg(Color::Yellow);
+        }
+        if let Some(cur) = current_match {
+            buf[(x, right_pane_history.y + 1 + row_of(matches[cur]))].set_symbol("█").set_fg(Color::LightRed);
+        }
+    }
 
+    if let (Some(stats_area), Some(stats)) = (stats_area, &app.container_stats) {
         let cpu_gauge = ratatui::widgets::Gauge::default()
             .block(Block::default().borders(Borders::ALL).title(" CPU Usage "))
             .gauge_style(Style::default().fg(Color::Cyan).bg(Color::DarkGray))
             .percent(stats.cpu_percent.min(100.0) as u16);
-        
+
         let mem_gauge = ratatui::widgets::Gauge::default()
             .block(Block::default().borders(Borders::ALL).title(format!(" Memory: {:.1}MB / {:.1}MB ", stats.mem_usage_mb, stats.mem_limit_mb)))
             .gauge_style(Style::default().fg(Color::Magenta).bg(Color::DarkGray))
             .percent(stats.mem_percent.min(100.0) as u16);
 
-        f.render_widget(cpu_gauge, chunks[0]);
-        f.render_widget(mem_gauge, chunks[1]);
+        let net = Paragraph::new(format!(" ↓ {:.1}MB   ↑ {:.1}MB", stats.net_rx_mb, stats.net_tx_mb))
+            .style(Style::default().fg(Color::Green))
+            .block(Block::default().borders(Borders::ALL).title(" Net I/O (rx / tx) "));
+        let io = Paragraph::new(format!(" R {:.1}MB   W {:.1}MB", stats.block_read_mb, stats.block_write_mb))
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title(" Block I/O (read / write) "));
+
+        let count = if app.stats_view == docker::StatsView::All { 4 } else { 2 };
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, count); count as usize])
+            .split(stats_area);
+
+        match app.stats_view {
+            docker::StatsView::CpuMem => {
+                f.render_widget(cpu_gauge, chunks[0]);
+                f.render_widget(mem_gauge, chunks[1]);
+            }
+            docker::StatsView::Net => {
+                f.render_widget(cpu_gauge, chunks[0]);
+                f.render_widget(net, chunks[1]);
+            }
+            docker::StatsView::Io => {
+                f.render_widget(cpu_gauge, chunks[0]);
+                f.render_widget(io, chunks[1]);
+            }
+            docker::StatsView::All => {
+                f.render_widget(cpu_gauge, chunks[0]);
+                f.render_widget(mem_gauge, chunks[1]);
+                f.render_widget(net, chunks[2]);
+                f.render_widget(io, chunks[3]);
+            }
+        }
     }
 
     if let Some(input_area) = right_pane_input {
@@ -159,12 +251,34 @@ pub fn draw_logs(f: &mut Frame, app: &mut App, area: Rect) {
             .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
             .split(area)[1];
             
+        let (label, mode) = match app.log_search {
+            Some(_) => (" 🔍 Search Logs: ", " Search Mode (Tab: filter, Enter: keep, n/N: jump) "),
+            None => (" 🔍 Filter Logs: ", " Filtering Mode, regex (Tab: search, Esc: clear) "),
+        };
         let filter_text = Line::from(vec![
-            Span::styled(" 🔍 Filter Logs: ", Style::default().fg(color_secondary).add_modifier(Modifier::BOLD)),
-            Span::raw(format!("{}█", app.log_filter_query)),
+            Span::styled(label, Style::default().fg(color_secondary).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}█", app.log_query())),
         ]);
         let filter_widget = Paragraph::new(filter_text)
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color_secondary)).title(" Filtering Mode "));
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color_secondary)).title(mode));
         f.render_widget(filter_widget, bar_area);
     }
 }
+
+/// Push `l[from..to]` in `base`, except the part inside the container prefix
+/// (`prefix` is its end offset and style).
+fn push_plain(spans: &mut Vec<Span<'static>>, l: &str, from: usize, to: usize, base: Style, prefix: Option<(usize, Style)>) {
+    if from >= to {
+        return;
+    }
+    match prefix.filter(|(end, _)| from < *end) {
+        Some((end, style)) => {
+            let split = end.min(to);
+            spans.push(Span::styled(l[from..split].to_string(), style));
+            if split < to {
+                spans.push(Span::styled(l[split..to].to_string(), base));
+            }
+        }
+        None => spans.push(Span::styled(l[from..to].to_string(), base)),
+    }
+}
@@ -3,8 +3,9 @@ pub mod popups;
 pub mod sidebar;
 pub mod utils;
 
-use crate::ui::app::App;
-use crate::ui::types::SidebarKind;
+use crate::keybindings::Action;
+use crate::ui::app::{App, RECOVERY_HINT_FOR};
+use crate::ui::types::{Popup, SidebarKind};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::{Paragraph, Block, Borders};
@@ -37,12 +38,30 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
         let bar = Paragraph::new(format!(" 🔍 Filter: {}█ ", app.filter_query))
             .style(Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD));
         f.render_widget(bar, root[1]);
+    } else if let Some(err) = app.log_filter_error.as_ref().filter(|_| app.log_search.is_none()) {
+        let bar = Paragraph::new(format!(" ⚠️ Invalid log filter regex: {err} (the last valid pattern still applies) "))
+            .style(Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD));
+        f.render_widget(bar, root[1]);
+    } else if let Some((hint, _)) = app.recovery_hint.as_ref().filter(|(_, at)| at.elapsed() < RECOVERY_HINT_FOR) {
+        let bar = Paragraph::new(format!(" ↩️ {hint}"))
+            .style(Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD));
+        f.render_widget(bar, root[1]);
     } else {
-        let help = help_for_selected(app);
+        let help = match app.truncated_selection() {
+            Some(full) => format!(" 📛 {full} │{}", help_for_selected(app)),
+            None => help_for_selected(app),
+        };
         let help_bar = Paragraph::new(help).style(Style::default().fg(Color::Black).bg(Color::White));
         f.render_widget(help_bar, root[1]);
     }
 
+    // Keep the help overlay from scrolling past its last line.
+    if let Some(Popup::Help { scroll }) = app.popup {
+        let inner = utils::centered_rect(90, 90, f.area()).height.saturating_sub(2);
+        let max = (popups::help_lines(app).len() as u16).saturating_sub(inner);
+        app.popup = Some(Popup::Help { scroll: scroll.min(max) });
+    }
+
     // Draw active popups
     if let Some(p) = &app.popup {
         popups::draw_popup(f, app, p);
@@ -67,6 +86,8 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
             }
         }
     }
+
+    utils::degrade_colors(f.buffer_mut(), app.cfg.color_support);
 }
 
 pub fn help_for_selected(app: &App) -> String {
@@ -76,31 +97,46 @@ pub fn help_for_selected(app: &App) -> String {
     if app.is_filtering {
         return format!(" 🔍 Filter: {}  [Enter]:Confirm  [Esc]:Clear", app.filter_query);
     }
+    let stopped = if app.show_stopped {
+        "  w:Stopped(shown)".to_string()
+    } else {
+        format!("  w:Stopped({} hidden)", app.containers.iter().filter(|(c, _)| crate::ui::app::is_stopped(c)).count())
+    };
     if app.items.is_empty() {
-        return " /:Filter  C:Context  V:Volumes  N:Networks  q:Quit  ?:Help".to_string();
+        return format!(" /:Filter{stopped}  C:Context  V:Volumes  N:Networks  !:Diagnostics  z:Freeze  q:Quit  ?:Help");
     }
     let item = &app.items[app.selected];
-    let scroll = if !app.focus_on_list { " ↑/↓:Scroll" } else { "" };
+    let scroll = if !app.focus_on_list { " ↑/↓:Scroll  +/-:Tail" } else { "" };
     let filtered_status = if !app.filter_query.is_empty() { " (Filtered)" } else { "" };
+    let linked = app.cfg.post_up_tasks.iter().any(|t| t.service.is_some());
     let v_status = if !app.multi_selected.is_empty() { format!(" ({})", app.multi_selected.len()) } else { "".to_string() };
+    let key = |action| app.cfg.keys.key(action);
+    let (restart, stop, start) = (key(Action::Restart), key(Action::Stop), key(Action::Start));
+    let (kill, inspect, web) = (key(Action::Kill), key(Action::Inspect), key(Action::Web));
     match item.kind {
         SidebarKind::Container => format!(
-            " /:Filter{f}  v:Select{v}  C:Context  V:Volumes  N:Networks  e:Shell  P:Pin  m:CopyMode  y:Copy  r:Restart  s:Stop  t:Start  p:Pause  u:Unpause  k:Kill  d:Rm  i:Inspect  o:Web  tab:Focus  q:Quit  ?:Help{scroll}",
-            f = filtered_status, v = v_status
+            " /:Filter{f}{stopped}  v:Select{v}  C:Context  V:Volumes  N:Networks  e:Shell  P:Pin  m:CopyMode  y:Copy  {restart}:Restart  K:RestartSvc  R:Recreate  {stop}:Stop  {start}:Start  p:Pause  u:Unpause  {kill}:Kill  d:Rm  {inspect}:Inspect  B:RunCmd  M:Stats  {web}:Web  O:CopyURL{link}{custom}  tab:Focus  q:Quit  ?:Help{scroll}{ts}",
+            f = filtered_status, v = v_status,
+            ts = if app.focus_on_list { "" } else { "  t:Timestamps" },
+            link = if linked { "  l:Task" } else { "" },
+            custom = app.cfg.container_actions.iter().map(|a| format!("  {}:{}", a.key, a.name)).collect::<String>()
         ),
         SidebarKind::Task => format!(
-            " /:Filter{f}  v:Select{v}  C:Context  V:Volumes  N:Networks  r:Run  s:Stop  y:Copy  tab:Focus  q:Quit  ?:Help{scroll}",
-            f = filtered_status, v = v_status
+            " /:Filter{f}{stopped}  v:Select{v}  C:Context  V:Volumes  N:Networks  {restart}:Run  {stop}:Stop  {inspect}:Info  Y:Streams{link}  y:Copy  tab:Focus  q:Quit  ?:Help{scroll}",
+            f = filtered_status, v = v_status,
+            link = if app.tasks.get(&item.id).is_some_and(|rt| rt.spec.service.is_some()) { "  l:Container" } else { "" }
         ),
-        SidebarKind::GroupHeader => if item.id == "__pins__" || item.id.starts_with("stack:") {
-                format!(" /:Filter{f}  C:Context  V:Volumes  N:Networks  Spc:Expand/Collapse  ↑/↓:Nav  q:Quit  ?:Help", f = filtered_status)
+        SidebarKind::GroupHeader => if !item.is_compose_project() {
+                format!(" /:Filter{f}{stopped}  C:Context  V:Volumes  N:Networks  Spc:Expand/Collapse  ↑/↓:Nav  q:Quit  ?:Help", f = filtered_status)
             } else {
-                format!(" /:Filter{f}  C:Ctx H:Health V:Vol N:Net  L:Logs  Spc:Collapse  t:StartAll  R:RestartAll  q:Quit  ?:Help", f = filtered_status)
+                format!(" /:Filter{f}{stopped}  C:Ctx H:Health V:Vol N:Net  L:Logs  W:Events  Spc:Collapse  {start}:StartAll  R:RestartAll  D:Down  q:Quit  ?:Help", f = filtered_status)
             }
         SidebarKind::SwarmService => format!(
-            " /:Filter{f}  v:Select{v}  C:Context  V:Volumes  N:Networks  e:Shell  S:Scale  r:Restart  s:Stop  t:Start  d:Rm  i:Inspect  y:Copy  tab:Focus  q:Quit  ?:Help{scroll}",
+            " /:Filter{f}{stopped}  v:Select{v}  C:Context  V:Volumes  N:Networks  e:Shell  S:Scale  {restart}:Restart  {stop}:Stop  {start}:Start  d:Rm  {inspect}:Inspect  y:Copy  tab:Focus  q:Quit  ?:Help{scroll}",
             f = filtered_status, v = v_status
         ),
-        SidebarKind::Separator => format!(" /:Filter{f}  C:Context  V:Volumes  N:Networks  q:Quit  ?:Help", f = filtered_status),
+        SidebarKind::StackLogs => format!(" /:Filter{f}{stopped}  +/-:Tail  y:Copy  T:Record  tab:Focus  q:Quit  ?:Help{scroll}", f = filtered_status),
+        SidebarKind::AppLog => format!(" /:Filter{f}{stopped}  C:Context  V:Volumes  N:Networks  y:Copy  tab:Focus  q:Quit  ?:Help{scroll}", f = filtered_status),
+        SidebarKind::Separator => format!(" /:Filter{f}{stopped}  C:Context  V:Volumes  N:Networks  q:Quit  ?:Help", f = filtered_status),
     }
 }
@@ -1,19 +1,234 @@
+use crate::keybindings::Action;
 use crate::ui::app::App;
-use crate::ui::types::Popup;
+use crate::ui::types::{Popup, SidebarKind, TypedAction};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap, Table, Row, Cell};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap, Table, TableState, Row, Cell};
 use ratatui::Frame;
 
 use super::utils::centered_rect;
 
-pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
+/// "Auto-<choice> in Ns" footer for confirm popups when `CONFIRM_TIMEOUT` is set.
+fn countdown(app: &App, choice: &str) -> String {
+    app.confirm_remaining()
+        .map(|s| format!("\n\n⏱ {choice} automatically in {s}s"))
+        .unwrap_or_default()
+}
+
+/// Lines of one inspect tab, each paired with the value `y` copies
+/// (empty for section headers and spacing).
+pub fn inspect_lines(name: &str, json: &serde_json::Value, tab: usize) -> Vec<(String, String)> {
+    let str_at = |v: &serde_json::Value| v.as_str().unwrap_or("-").to_string();
+    let mut lines = Vec::new();
+    let mut field = |label: &str, value: String| lines.push((format!("{label}: {value}"), value));
+    match tab {
+        0 => {
+            let args = json["Args"].as_array().map(|a| a.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(" ")).unwrap_or_default();
+            field("ID", str_at(&json["Id"]));
+            field("Name", name.to_string());
+            field("Created", str_at(&json["Created"]));
+            field("Restart policy", str_at(&json["HostConfig"]["RestartPolicy"]["Name"]));
+            field("Path", format!("{} {args}", str_at(&json["Path"])).trim_end().to_string());
+            field("Image ID", str_at(&json["Image"]));
+        }
+        1 => {
+            field("Image", str_at(&json["Config"]["Image"]));
+            lines.push((String::new(), String::new()));
+            lines.push(("-- ENV --".to_string(), String::new()));
+            for e in json["Config"]["Env"].as_array().into_iter().flatten().filter_map(|v| v.as_str()) {
+                let value = e.split_once('=').map(|(_, v)| v).unwrap_or(e);
+                lines.push((e.to_string(), value.to_string()));
+            }
+            lines.push((String::new(), String::new()));
+            lines.push(("-- Labels --".to_string(), String::new()));
+            for (k, v) in json["Config"]["Labels"].as_object().into_iter().flatten() {
+                let value = v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string());
+                lines.push((format!("{k}: {value}"), value));
+            }
+            lines.push((String::new(), String::new()));
+            lines.push(("-- Mounts --".to_string(), String::new()));
+            for m in json["Mounts"].as_array().into_iter().flatten() {
+                let source = str_at(&m["Source"]);
+                lines.push((format!("{source} → {}", str_at(&m["Destination"])), source));
+            }
+        }
+        2 => {
+            lines.push(("-- Networks --".to_string(), String::new()));
+            match json["NetworkSettings"]["Networks"].as_object() {
+                Some(nets) => {
+                    for (k, v) in nets {
+                        lines.push((format!("{k}:"), k.clone()));
+                        lines.push((format!("  IP: {}", str_at(&v["IPAddress"])), str_at(&v["IPAddress"])));
+                        lines.push((format!("  Gateway: {}", str_at(&v["Gateway"])), str_at(&v["Gateway"])));
+                    }
+                }
+                None => lines.push(("No network info".to_string(), String::new())),
+            }
+            lines.push((String::new(), String::new()));
+            lines.push(("-- Ports --".to_string(), String::new()));
+            for (port, bindings) in json["NetworkSettings"]["Ports"].as_object().into_iter().flatten() {
+                let published: Vec<String> = bindings.as_array().into_iter().flatten()
+                    .map(|b| format!("{}:{}", str_at(&b["HostIp"]), str_at(&b["HostPort"])))
+                    .collect();
+                if published.is_empty() {
+                    lines.push((port.clone(), port.clone()));
+                }
+                for host in published {
+                    lines.push((format!("{port} → {host}"), host));
+                }
+            }
+        }
+        3 => {
+            // Raw `docker inspect` output; `y` copies the line without its
+            // trailing comma.
+            let pretty = serde_json::to_string_pretty(json).unwrap_or_default();
+            for line in pretty.lines() {
+                let value = line.trim().trim_end_matches(',').to_string();
+                lines.push((line.to_string(), value));
+            }
+        }
+        _ => lines.push(("Unknown tab".to_string(), String::new())),
+    }
+    lines
+}
+
+/// Content of the `?` overlay: one section per kind of item, the one that
+/// applies to the selection first.
+pub fn help_lines(app: &App) -> Vec<Line<'static>> {
+    let k = |key: &str, desc: &str| (key.to_string(), desc.to_string());
+    let key = |action| app.cfg.keys.key(action);
+    let (restart, stop, start) = (key(Action::Restart), key(Action::Stop), key(Action::Start));
+    let (kill, inspect, web) = (key(Action::Kill), key(Action::Inspect), key(Action::Web));
+    let general = vec![
+        k("q / Ctrl+C", "Quitter"),
+        k("?", "Afficher / fermer cette aide"),
+        k("!", "Diagnostics (détection Docker, profil, fichiers .env)"),
+        k("z", "Geler / reprendre le rafraîchissement automatique (les logs continuent, Ctrl+R reste actif)"),
+        k("Espace", "Focus logs : comme z"),
+        k("Ctrl+R", "Rafraîchir maintenant (même en pause)"),
+        k("a", "Afficher / masquer l'âge des conteneurs (2h ago)"),
+        k("w", "Afficher / masquer les conteneurs arrêtés (exited, dead, created)"),
+        k("C", "Changer de contexte Docker"),
+        k("H", "Dashboard de santé / Cleanup (Disk Usage)"),
+        k("V / N / I", "Lister les volumes (Entrée : inspecter, d : supprimer) / réseaux / images (p : prune des images <none>)"),
+        k("h", "Historique des commandes docker"),
+        k("M", "Changer les colonnes de stats (CPU/Mém, Réseau, Disque, Tout)"),
+        k("Ctrl+E", "Éditer le .env dans $EDITOR puis recharger"),
+        k("Ctrl+T", "Activer / désactiver la capture souris (sélection native)"),
+    ];
+    let navigation = vec![
+        k("↑ / ↓ / j / k", "Sélectionner un item (k reste Kill sur un conteneur)"),
+        k("G", "Aller au dernier élément de la liste"),
+        k("Entrée", "Action par défaut (DEFAULT_ACTION, logs sinon)"),
+        k("Espace", "Développer / réduire un groupe"),
+        k("Tab", "Changer le focus (Liste / Logs)"),
+        k("1-9 / g<nombre>", "Aller directement au Nième élément de la liste (gg : le premier)"),
+        k("g<nom>", "Sélectionner en tapant le début (ou des lettres) d'un nom"),
+        k("@", "Aller à un conteneur par nom ou id (`web-1 r` le redémarre)"),
+        k("/", "Filtrer la liste"),
+        k("v", "(Dé)sélectionner pour action groupée"),
+    ];
+    let mut containers = vec![
+        k(&format!("{start} / {stop} / {restart}"), "Démarrer / arrêter / redémarrer"),
+        k(&format!("p / u / {kill}"), "Pause / reprise / kill"),
+        k("R", "Recréer le service compose (--force-recreate --no-deps)"),
+        k("K", "Redémarrer uniquement ce service compose"),
+        k("e", "Shell dans le conteneur (docker exec -it, EXEC_SHELL)"),
+        k("l", "Aller à la tâche liée (service: …)"),
+        k("d", "Supprimer"),
+        k(&inspect.to_string(), "Inspecter (↑/↓ + y : copier une valeur, ←/→ : défiler, w : retour à la ligne, J : JSON brut)"),
+        k("B", "Copier une commande docker run équivalente"),
+        k(&format!("{web} / O"), "Ouvrir dans le navigateur / copier l'URL"),
+        k("F1..F12", "Ouvrir les favoris QUICK_OPEN"),
+        k("P", "Épingler (Pin)"),
+    ];
+    containers.extend(app.cfg.container_actions.iter().map(|a| (a.key.to_string(), format!("{} (CONTAINER_ACTIONS)", a.name))));
+    let tasks = vec![
+        k(&format!("{restart} / {start}"), "Lancer (ou relancer) la tâche"),
+        k(&stop.to_string(), "Arrêter la tâche"),
+        k(&inspect.to_string(), "Sa commande ligne par ligne (y : copier)"),
+        k("Y", "Sortie : stdout + stderr / [ERR] seul / [OUT] seul"),
+        k("l", "Aller au conteneur lié"),
+    ];
+    let swarm = vec![
+        k("S", "Scaler le service"),
+        k("e", "Shell intégré (volet)"),
+        k(&format!("{start} / {stop} / {restart} / d"), "Démarrer / arrêter / redémarrer / supprimer"),
+        k(&inspect.to_string(), "Inspecter"),
+    ];
+    let compose = vec![
+        k("L", "Logs multi-conteneurs du projet"),
+        k("stack logs", "Ligne 📜 sous le journal : logs -f de toute la stack du profil"),
+        k("W", "Événements docker du projet"),
+        k("A", "Vue des services (santé, état, services définis mais absents)"),
+        k(&format!("{start} / R"), "Démarrer / redémarrer tout le projet (sur l'en-tête)"),
+        k("U", "Démarrer uniquement les services arrêtés / absents"),
+        k("D", "docker compose down du projet, après confirmation (v : avec -v)"),
+        k("Z", "Arrêter / relancer toute la stack (sans rien supprimer)"),
+        k(":", "Commande docker compose arbitraire (Ctrl+S : l'enregistrer en favori)"),
+        k(";", "Favoris de commandes (Entrée / 1-9 : lancer, e : éditer, d : supprimer)"),
+    ];
+    let logs = vec![
+        k("/", "Rechercher dans les logs (Tab : recherche / filtre regex)"),
+        k("Esc", "Effacer la recherche / le filtre des logs"),
+        k("n / N", "Occurrence suivante / précédente"),
+        k("PageUp / PageDown", "Défiler"),
+        k("j / k / g / G", "Focus logs : ligne suivante / précédente, début / fin"),
+        k("+ / -", "Plus / moins d'historique (tail) pour la cible, mémorisé"),
+        k("t", "Horodatage des logs de conteneur (on/off, relance le suivi)"),
+        k("m", "Mode Copie"),
+        k("y", "Copier tout le buffer"),
+        k("T", "Enregistrer le flux suivi dans un fichier (on/off)"),
+    ];
+
+    let current = match app.items.get(app.selected) {
+        Some(i) if i.kind == SidebarKind::Container => Some("Conteneurs"),
+        Some(i) if i.kind == SidebarKind::Task => Some("Tâches"),
+        Some(i) if i.kind == SidebarKind::SwarmService => Some("Services Swarm"),
+        Some(i) if i.is_compose_project() => Some("Projet Compose"),
+        _ => None,
+    };
+    let mut sections = vec![
+        ("Conteneurs", containers),
+        ("Tâches", tasks),
+        ("Services Swarm", swarm),
+        ("Projet Compose", compose),
+        ("Logs", logs),
+        ("Navigation", navigation),
+        ("Général", general),
+    ];
+    if let Some(pos) = sections.iter().position(|(title, _)| Some(*title) == current) {
+        let section = sections.remove(pos);
+        sections.insert(0, section);
+    }
+
+    let key_width = sections.iter().flat_map(|(_, keys)| keys.iter().map(|(key, _)| key.chars().count())).max().unwrap_or(0);
+    let mut lines = Vec::new();
+    for (title, keys) in sections {
+        let mut header = vec![Span::styled(title.to_string(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))];
+        if Some(title) == current {
+            header.push(Span::styled("  (sélection)", Style::default().fg(Color::Gray)));
+        }
+        lines.push(Line::from(header));
+        for (key, desc) in keys {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {key:<key_width$}  "), Style::default().fg(Color::Yellow)),
+                Span::raw(desc),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines.pop();
+    lines
+}
+
+pub fn draw_popup(f: &mut Frame, app: &App, p: &Popup) {
     match p {
-        Popup::Inspect { name, json, tab, .. } => {
+        Popup::Inspect { id, name, json, tab, cursor, scroll_x, wrap } => {
             let area = centered_rect(90, 90, f.area());
             f.render_widget(Clear, area);
             
-            let tabs = [" [1] Summary ", " [2] Config ", " [3] Network "];
+            let tabs = [" [1] Summary ", " [2] Config ", " [3] Network ", " [4] Raw JSON "];
             let tab_spans: Vec<Span> = tabs.iter().enumerate().map(|(i, &t)| {
                 if i == *tab {
                     Span::styled(t, Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD))
@@ -23,44 +238,177 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
             }).collect();
             let tab_line = Line::from(tab_spans);
 
-            let content = match tab {
-                0 => {
-                    let id = json["Id"].as_str().unwrap_or("-");
-                    let created = json["Created"].as_str().unwrap_or("-");
-                    let path = json["Path"].as_str().unwrap_or("-");
-                    let args = json["Args"].as_array().map(|a| a.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(" ")).unwrap_or_default();
-                    format!("ID: {id}\nName: {name}\nCreated: {created}\nPath: {path} {args}\n\n[Tab/Arrows] Switch tabs, [Esc] Close")
-                }
-                1 => {
-                    let image = json["Config"]["Image"].as_str().unwrap_or("-");
-                    let env = json["Config"]["Env"].as_array().map(|a| a.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join("\n")).unwrap_or_default();
-                    let labels = json["Config"]["Labels"].as_object().map(|o| o.iter().map(|(k,v)| format!("{k}: {v}")).collect::<Vec<_>>().join("\n")).unwrap_or_default();
-                    format!("Image: {image}\n\n-- ENV --\n{env}\n\n-- Labels --\n{labels}")
-                }
-                2 => {
-                    let nw = json["NetworkSettings"]["Networks"].as_object().map(|o| {
-                        o.iter().map(|(k,v)| format!("{k}:\n  IP: {}\n  Gateway: {}", v["IPAddress"], v["Gateway"])).collect::<Vec<_>>().join("\n")
-                    }).unwrap_or_else(|| "No network info".to_string());
-                    format!("-- Networks --\n{nw}")
-                }
-                _ => "Unknown tab".to_string(),
-            };
+            let width = area.width.saturating_sub(2).max(1) as usize;
+            let mut items: Vec<ListItem> = inspect_lines(name, json, *tab)
+                .into_iter()
+                .map(|(text, value)| {
+                    let style = if value.is_empty() { Style::default().fg(Color::Cyan) } else { Style::default() };
+                    let chars: Vec<char> = text.chars().collect();
+                    let shown: Vec<Line> = if *wrap {
+                        chars.chunks(width).map(|c| Line::from(c.iter().collect::<String>())).collect()
+                    } else {
+                        vec![Line::from(chars.iter().skip(*scroll_x).collect::<String>())]
+                    };
+                    ListItem::new(shown).style(style)
+                })
+                .collect();
+            // Appended after the copyable lines so the cursor indices stay put.
+            let hint = app.containers.iter().find(|(c, _)| c.id == *id).and_then(|(c, _)| app.policy_hint(c));
+            if let (0, Some(hint)) = (*tab, hint) {
+                items.push(ListItem::new(""));
+                items.push(ListItem::new(format!("⚠ {hint}")).style(Style::default().fg(Color::Yellow)));
+            }
 
             let block = Block::default()
                 .borders(Borders::ALL)
-                .title(tab_line);
+                .title(tab_line)
+                .title_bottom(match (*wrap, *scroll_x) {
+                    (true, _) => " ↑/↓:Line  y:Copy value  w:No wrap  Tab:Switch tabs  J:Raw JSON  Esc:Close ".to_string(),
+                    (false, 0) => " ↑/↓:Line  ←/→:Scroll  w:Wrap  y:Copy value  Tab:Switch tabs  J:Raw JSON  Esc:Close ".to_string(),
+                    (false, x) => format!(" ↑/↓:Line  ←/→:Scroll (col {})  Home:Reset  w:Wrap  y:Copy value  Tab:Switch tabs  J:Raw JSON  Esc:Close ", x + 1),
+                });
             
-            let p = Paragraph::new(content)
+            let list = List::new(items)
                 .block(block)
+                .highlight_style(Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD));
+            let mut state = ratatui::widgets::ListState::default().with_selected(Some(*cursor));
+            f.render_stateful_widget(list, area, &mut state);
+        }
+        Popup::ComposeCommand { input } => {
+            let area = centered_rect(70, 20, f.area());
+            f.render_widget(Clear, area);
+            let msg = format!(
+                "docker compose --profile {} {input}█\n\n[Enter]:Run  [Ctrl+S]:Save as bookmark  [Esc]:Cancel   exec/run/attach take over the terminal (add -T to stream instead)",
+                app.cfg.compose_profile
+            );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)).title(" 🐙 Compose Command "))
                 .wrap(Wrap { trim: false });
-            f.render_widget(p, area);
+            f.render_widget(w, area);
         }
-        Popup::ConfirmReset { name, .. } => {
+        Popup::SaveBookmark { command, input } => {
+            let area = centered_rect(70, 20, f.area());
+            f.render_widget(Clear, area);
+            let msg = format!("Name: {input}█\n\n{command}\n\n[Enter]:Save (empty name: the command itself)  [Esc]:Back");
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)).title(" 🔖 Save Bookmark "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
+        Popup::Bookmarks { selected } => {
+            let area = centered_rect(70, 50, f.area());
+            f.render_widget(Clear, area);
+            let width = app.bookmarks.iter().map(|b| b.name.chars().count()).max().unwrap_or(0);
+            let items: Vec<ListItem> = app.bookmarks.iter().enumerate()
+                .map(|(i, b)| {
+                    let key = if i < 9 { format!("{} ", i + 1) } else { "  ".to_string() };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(key, Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!("{:<width$}  ", b.name), Style::default().add_modifier(Modifier::BOLD)),
+                        Span::raw(b.command.clone()),
+                    ]))
+                })
+                .collect();
+            let list = List::new(items)
+                .block(Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(" 🔖 Compose Bookmarks ")
+                    .title_bottom(" Enter/1-9:Run  e:Edit  d:Delete  Esc:Close "))
+                .highlight_style(Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD));
+            let mut state = ratatui::widgets::ListState::default().with_selected(Some(*selected));
+            f.render_stateful_widget(list, area, &mut state);
+        }
+        Popup::RunCommand { name, command } => {
+            let area = centered_rect(80, 60, f.area());
+            f.render_widget(Clear, area);
+            let msg = format!(
+                "{command}\n\nEnv includes variables set by the image; networks beyond the first and labels are not carried over.\n[y]:Copy again  [Esc]:Close"
+            );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)).title(format!(" 🐳 docker run: {name} ")))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
+        Popup::TaskInfo { name } => {
+            let area = centered_rect(80, 60, f.area());
+            f.render_widget(Clear, area);
+            let Some(rt) = app.tasks.get(name) else { return };
+            let key = Style::default().fg(Color::Rgb(255, 170, 0)).add_modifier(Modifier::BOLD);
+            let row = |k: &str, v: String| Line::from(vec![Span::styled(format!("{k:<10}"), key), Span::raw(v)]);
+            let mut lines = vec![
+                row("Status", rt.status.as_str().to_string()),
+                row("Schedule", rt.spec.every.map(|d| format!("every {}s", d.as_secs())).unwrap_or_else(|| "manual".to_string())),
+                row("Service", rt.spec.service.clone().unwrap_or_else(|| "(none)".to_string())),
+                row("Output", if rt.spec.plain { "plain".to_string() } else { "tagged [OUT]/[ERR]".to_string() }),
+                Line::from(""),
+                Line::from(Span::styled("Command", key)),
+            ];
+            lines.extend(crate::env::command_lines(&rt.spec.cmd).into_iter().map(|l| Line::from(format!("  {l}"))));
+            lines.push(Line::from(""));
+            lines.push(Line::from("[y]:Copy command  [Esc]:Close"));
+            let w = Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)).title(format!(" ⚙️ Task: {name} ")))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
+        Popup::GoToContainer { input } => {
+            let area = centered_rect(60, 40, f.area());
+            f.render_widget(Clear, area);
+            let query = match input.trim().rsplit_once(' ') {
+                Some((q, a)) if a.chars().count() == 1 => q.trim(),
+                _ => input.trim(),
+            };
+            let found = app.goto_matches(query);
+            let mut lines = vec![Line::from(format!("Container: {input}█")), Line::from("")];
+            match found.len() {
+                _ if query.is_empty() => {}
+                0 => lines.push(Line::from(Span::styled("No container matches", Style::default().fg(Color::Red)))),
+                n => {
+                    let style = if n == 1 { Style::default().fg(Color::Green) } else { Style::default().fg(Color::Yellow) };
+                    for (id, name) in found.iter().take(8) {
+                        lines.push(Line::from(Span::styled(format!("  {name}  ({})", &id[..id.len().min(12)]), style)));
+                    }
+                    if n > 8 {
+                        lines.push(Line::from(Span::styled(format!("  … {} more", n - 8), Style::default().fg(Color::DarkGray))));
+                    }
+                }
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Name or id prefix; add an action key to run it too (web-1 r).  [Enter]:Go  [Esc]:Cancel",
+                Style::default().fg(Color::Gray),
+            )));
+            let w = Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)).title(" 🎯 Go to container "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
+        Popup::ConfirmCreateEnv { path } => {
             let area = centered_rect(60, 25, f.area());
             f.render_widget(Clear, area);
             let msg = format!(
-                "RESET {name}?\nThis will STOP it, REMOVE it, and DELETE its volumes.\n\n[y/Enter]=Reset, [n/Esc]=Cancel"
+                "{} does not exist.\nCreate it and open it in your editor?\n\n[y/Enter]=Create, [n/Esc]=Cancel{}",
+                path.display(),
+                countdown(app, "Cancel")
             );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)).title(" Edit .env "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
+        Popup::ConfirmReset { name, service, .. } => {
+            let area = centered_rect(60, 25, f.area());
+            f.render_widget(Clear, area);
+            let timer = countdown(app, "Cancel");
+            let msg = match service {
+                Some((_, svc)) => format!(
+                    "RESET {name}?\nThis will STOP it, REMOVE it, and DELETE its volumes.\n\n[u]=Reset + compose up -d {svc}, [y/Enter]=Reset only, [n/Esc]=Cancel{timer}"
+                ),
+                None => format!(
+                    "RESET {name}?\nThis will STOP it, REMOVE it, and DELETE its volumes.\n\n[y/Enter]=Reset, [n/Esc]=Cancel{timer}"
+                ),
+            };
             let w = Paragraph::new(msg)
                 .block(Block::default().borders(Borders::ALL).title(" ⚠️  RESET CONTAINER "))
                 .wrap(Wrap { trim: false });
@@ -70,27 +418,73 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
             let area = centered_rect(60, 25, f.area());
             f.render_widget(Clear, area);
             let msg = format!(
-                "REMOVE {} containers?\nThis will STOP and DELETE the selected containers.\n\n[y/Enter]=Remove, [n/Esc]=Cancel",
-                ids.len()
+                "REMOVE {} containers?\nThis will STOP and DELETE the selected containers.\n\n[y/Enter]=Remove, [n/Esc]=Cancel{}",
+                ids.len(),
+                countdown(app, "Cancel")
             );
             let w = Paragraph::new(msg)
                 .block(Block::default().borders(Borders::ALL).title(" ⚠️  BULK REMOVE "))
                 .wrap(Wrap { trim: false });
             f.render_widget(w, area);
         }
+        Popup::ConfirmStackToggle { project, stop } => {
+            let area = centered_rect(60, 25, f.area());
+            f.render_widget(Clear, area);
+            let (verb, what, color) = if *stop {
+                ("stop", "Stop every container of the stack?\nContainers, networks and volumes are kept; Z again resumes it.", Color::Yellow)
+            } else {
+                ("start", "Start the stopped containers of the stack again?\nNothing is recreated.", Color::Green)
+            };
+            let msg = format!(
+                "{what}\n\ndocker compose --profile {} {verb}\n\n[y/Enter]={}, [n/Esc]=Cancel{}",
+                app.cfg.compose_profile,
+                if *stop { "Stop" } else { "Start" },
+                countdown(app, "Cancel")
+            );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color)).title(format!(" ⏯ {project} ")))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
+        Popup::ConfirmUpMissing { services } => {
+            let area = centered_rect(60, 40, f.area());
+            f.render_widget(Clear, area);
+            let list: String = services.iter().map(|s| format!("  • {s}\n")).collect();
+            let msg = format!(
+                "These services have no running container:\n\n{list}\ndocker compose --profile {} up -d <these only>\n\n[y/Enter]=Start, [n/Esc]=Cancel{}",
+                app.cfg.compose_profile,
+                countdown(app, "Cancel")
+            );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Green)).title(" 🚀 Up Missing Services "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
         Popup::ConfirmComposeRestart { infra_running } => {
             let area = centered_rect(60, 25, f.area());
             f.render_widget(Clear, area);
             let msg = if *infra_running {
-                "Detected running stack containers.\n\n[r/Enter]=Restart services, [k]=Keep, [Esc]=Cancel".to_string()
+                format!("Detected running stack containers.\n\n[r/Enter]=Restart services, [k]=Keep, [Esc]=Cancel{}", countdown(app, "Keep"))
             } else {
-                "Start services now?\n\n[r/Enter]=docker compose up -d, [Esc]=Cancel".to_string()
+                format!("Start services now?\n\n[r/Enter]=docker compose up -d, [Esc]=Cancel{}", countdown(app, "Cancel"))
             };
             let w = Paragraph::new(msg)
                 .block(Block::default().borders(Borders::ALL).title(" Docker compose "))
                 .wrap(Wrap { trim: false });
             f.render_widget(w, area);
         }
+        Popup::ConfirmComposeDown { project } => {
+            let area = centered_rect(60, 30, f.area());
+            f.render_widget(Clear, area);
+            let msg = format!(
+                "Take the compose project {project} down?\nIts containers and networks are removed; named volumes are kept unless you pick -v.\n\ndocker compose -p {project} down\n\n[y/Enter]=Down, [v]=Down -v (delete volumes), [n/Esc]=Cancel{}",
+                countdown(app, "Cancel")
+            );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Red)).title(" 🛑 Compose down "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
         Popup::ScaleService { name, input, .. } => {
             let area = centered_rect(50, 20, f.area());
             f.render_widget(Clear, area);
@@ -105,7 +499,7 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
             f.render_widget(Clear, area);
             let title = " 📂 Volumes Explorer ";
 
-            let header_cells = ["Name", "Driver", "Size"]
+            let header_cells = ["Name", "Driver", "Size", "Used by"]
                 .iter()
                 .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
             let header = Row::new(header_cells).height(1).bottom_margin(1);
@@ -120,6 +514,10 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
                     Cell::from(vol.name.clone()),
                     Cell::from(vol.driver.clone()),
                     Cell::from(vol.size.clone().unwrap_or_else(|| "-".to_string())),
+                    Cell::from(match crate::docker::volume_used_by(&app.containers, &vol.name) {
+                        users if users.is_empty() => "-".to_string(),
+                        users => users.join(", "),
+                    }),
                 ];
                 Row::new(cells).style(style)
             });
@@ -130,6 +528,7 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
                     ratatui::layout::Constraint::Min(40),
                     ratatui::layout::Constraint::Length(15),
                     ratatui::layout::Constraint::Length(15),
+                    ratatui::layout::Constraint::Min(20),
                 ],
             )
             .header(header)
@@ -143,9 +542,40 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
                 width: area.width,
                 height: 1,
             };
-            let help_text = Paragraph::new(" ↑/↓:Nav  d:Rm  D:ForceRm  Esc/Enter:Close ").style(Style::default().fg(Color::Gray));
+            let help_text = Paragraph::new(" ↑/↓:Nav  Enter/i:Inspect in logs  d:Rm  D:ForceRm  Esc:Close ").style(Style::default().fg(Color::Gray));
             f.render_widget(help_text, help_area);
         }
+        Popup::ConfirmImagePrune { dangling } => {
+            let area = centered_rect(60, 25, f.area());
+            f.render_widget(Clear, area);
+            let msg = format!(
+                "Remove the {dangling} dangling image(s) (<none>:<none>)?\nTagged images are kept.\n\ndocker image prune -f\n\n[y/Enter]=Prune, [n/Esc]=Back{}",
+                countdown(app, "Cancel")
+            );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)).title(" 🧹 Prune Images "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
+        Popup::ConfirmVolumeRemove { name, force, .. } => {
+            let area = centered_rect(60, 25, f.area());
+            f.render_widget(Clear, area);
+            let users = crate::docker::volume_used_by(&app.containers, name);
+            let used = match (users.is_empty(), *force) {
+                (true, _) => String::new(),
+                (false, false) => format!("\nUsed by {}: docker refuses while they exist.", users.join(", ")),
+                (false, true) => format!("\nUsed by {}.", users.join(", ")),
+            };
+            let msg = format!(
+                "Remove volume {name}{}?\nIts data cannot be recovered.{used}\n\n[y/Enter]=Remove, [n/Esc]=Back{}",
+                if *force { " (force)" } else { "" },
+                countdown(app, "Cancel")
+            );
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Red)).title(" 🗑️ Remove Volume "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
         Popup::Networks { networks, selected } => {
             let area = centered_rect(80, 70, f.area());
             f.render_widget(Clear, area);
@@ -194,6 +624,77 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
             let help_text = Paragraph::new(" ↑/↓:Nav  d:Rm  Esc/Enter:Close ").style(Style::default().fg(Color::Gray));
             f.render_widget(help_text, help_area);
         }
+        Popup::ComposePs { rows, selected } => {
+            let area = centered_rect(85, 70, f.area());
+            f.render_widget(Clear, area);
+            let title = format!(" 🐙 Compose services (profile: {}) ", app.cfg.compose_profile);
+
+            let header_cells = ["Service", "Container", "State", "Health", "Status"]
+                .iter()
+                .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+            let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+            let table_rows = rows.iter().enumerate().map(|(i, r)| {
+                let state_color = match r.state.to_lowercase().as_str() {
+                    "running" => Color::Green,
+                    "" => Color::DarkGray,
+                    "restarting" | "paused" | "created" => Color::Yellow,
+                    _ => Color::Red,
+                };
+                let health_color = match r.health.as_str() {
+                    "healthy" => Color::Green,
+                    "unhealthy" => Color::Red,
+                    _ => Color::Yellow,
+                };
+                let cells = if r.name.is_empty() {
+                    vec![
+                        Cell::from(r.service.clone()),
+                        Cell::from("—"),
+                        Cell::from("not created").style(Style::default().fg(state_color)),
+                        Cell::from(""),
+                        Cell::from(""),
+                    ]
+                } else {
+                    vec![
+                        Cell::from(r.service.clone()),
+                        Cell::from(r.name.clone()),
+                        Cell::from(r.state.clone()).style(Style::default().fg(state_color)),
+                        Cell::from(r.health.clone()).style(Style::default().fg(health_color)),
+                        Cell::from(r.status.clone()),
+                    ]
+                };
+                let row = Row::new(cells);
+                if i == *selected {
+                    row.style(Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD))
+                } else {
+                    row
+                }
+            });
+
+            let t = Table::new(
+                table_rows,
+                [
+                    ratatui::layout::Constraint::Length(18),
+                    ratatui::layout::Constraint::Min(24),
+                    ratatui::layout::Constraint::Length(12),
+                    ratatui::layout::Constraint::Length(10),
+                    ratatui::layout::Constraint::Min(20),
+                ],
+            )
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+            f.render_widget(t, area);
+
+            let help_area = ratatui::layout::Rect {
+                x: area.x,
+                y: area.y + area.height,
+                width: area.width,
+                height: 1,
+            };
+            let help_text = Paragraph::new(" ↑/↓:Nav  Enter:Select container  U:Start missing  r:Refresh  Esc:Close ").style(Style::default().fg(Color::Gray));
+            f.render_widget(help_text, help_area);
+        }
         Popup::ContextSwitch { contexts, selected } => {
             let area = centered_rect(60, 40, f.area());
             f.render_widget(Clear, area);
@@ -283,12 +784,15 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
             let rows = images.iter().enumerate().map(|(i, img)| {
                 let style = if i == *selected {
                     Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+                } else if img.is_dangling() {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::DIM)
                 } else {
                     Style::default()
                 };
+                let repository = if img.is_dangling() { "⚠ <none> (dangling)".to_string() } else { img.repository.clone() };
                 let cells = vec![
                     Cell::from(img.id.clone()),
-                    Cell::from(img.repository.clone()),
+                    Cell::from(repository),
                     Cell::from(img.tag.clone()),
                     Cell::from(img.size.clone()),
                     Cell::from(img.created_since.clone()),
@@ -318,7 +822,62 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
                 width: area.width,
                 height: 1,
             };
-            let help_text = Paragraph::new(" ↑/↓:Nav  d:Rm  D:ForceRm  Esc/Enter:Close ").style(Style::default().fg(Color::Gray));
+            let help_text = Paragraph::new(" ↑/↓:Nav  Enter/i:Inspect in logs  d:Rm  D:ForceRm  p:Prune dangling  Esc:Close ").style(Style::default().fg(Color::Gray));
+            f.render_widget(help_text, help_area);
+        }
+        Popup::CommandLog { entries, selected } => {
+            let area = centered_rect(90, 70, f.area());
+            f.render_widget(Clear, area);
+            let title = format!(" 🧾 Recent docker commands ({}) ", entries.len());
+
+            let header_cells = ["Time", "Exit", "Took", "Command"]
+                .iter()
+                .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+            let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+            let rows = entries.iter().enumerate().map(|(i, rec)| {
+                let (exit, exit_style) = match rec.exit_code {
+                    Some(0) => ("0".to_string(), Style::default().fg(Color::Green)),
+                    Some(c) => (c.to_string(), Style::default().fg(Color::Red)),
+                    None => ("…".to_string(), Style::default().fg(Color::DarkGray)),
+                };
+                let style = if i == *selected {
+                    Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let took = if rec.exit_code.is_some() { format!("{}ms", rec.duration.as_millis()) } else { "stream".to_string() };
+                let cells = vec![
+                    Cell::from(rec.at.format("%H:%M:%S").to_string()),
+                    Cell::from(exit).style(if i == *selected { style } else { exit_style }),
+                    Cell::from(took),
+                    Cell::from(rec.command.clone()),
+                ];
+                Row::new(cells).style(style)
+            });
+
+            let t = Table::new(
+                rows,
+                [
+                    ratatui::layout::Constraint::Length(10),
+                    ratatui::layout::Constraint::Length(5),
+                    ratatui::layout::Constraint::Length(8),
+                    ratatui::layout::Constraint::Min(40),
+                ],
+            )
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+            let mut state = TableState::default().with_selected(Some(*selected));
+            f.render_stateful_widget(t, area, &mut state);
+
+            let help_area = ratatui::layout::Rect {
+                x: area.x,
+                y: area.y + area.height,
+                width: area.width,
+                height: 1,
+            };
+            let help_text = Paragraph::new(" ↑/↓:Nav  y:Copy command  Esc/Enter:Close ").style(Style::default().fg(Color::Gray));
             f.render_widget(help_text, help_area);
         }
         Popup::FileExplorer { name, path, files, selected, .. } => {
@@ -356,10 +915,41 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
             let help_text = Paragraph::new(" ↑/↓:Nav  Enter/→:Open/Enter  Backspace/←:Back  Esc:Close ").style(Style::default().fg(Color::Gray));
             f.render_widget(help_text, help_area);
         }
+        Popup::ConfirmTyped { expected, input, action } => {
+            let area = centered_rect(55, 30, f.area());
+            f.render_widget(Clear, area);
+            let what = match action {
+                TypedAction::Prune => "System prune removes stopped containers, unused networks, dangling images and build cache.".to_string(),
+                TypedAction::Reset { name, .. } => format!("Reset deletes {name} and its volumes; their data cannot be recovered."),
+                TypedAction::ComposeDown { project, .. } => format!("Down -v removes the containers, networks and volumes of {project}; volume data cannot be recovered."),
+            };
+            let input_style = if input == expected { Color::Green } else { Color::White };
+            let mut msg = vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  {what}"), Style::default().fg(Color::LightRed))),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  Type "),
+                    Span::styled(expected.clone(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    Span::raw(" to confirm:"),
+                ]),
+                Line::from(Span::styled(format!("  > {input}█"), Style::default().fg(input_style).add_modifier(Modifier::BOLD))),
+                Line::from(""),
+                Line::from("  [Enter]:Confirm  [Esc]:Cancel"),
+            ];
+            if let Some(left) = app.confirm_remaining() {
+                msg.push(Line::from(""));
+                msg.push(Line::from(format!("  ⏱ Cancel automatically in {left}s")));
+            }
+            let w = Paragraph::new(msg)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Red)).title(" Dangerous Action "))
+                .wrap(Wrap { trim: false });
+            f.render_widget(w, area);
+        }
         Popup::ConfirmPrune => {
             let area = centered_rect(50, 30, f.area());
             f.render_widget(Clear, area);
-            let msg = vec![
+            let mut msg = vec![
                 Line::from(""),
                 Line::from(vec![Span::styled("  ⚠️  SYSTEM PRUNE  ⚠️", Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD))]),
                 Line::from(""),
@@ -376,49 +966,118 @@ pub fn draw_popup(f: &mut Frame, _app: &App, p: &Popup) {
                     Span::styled("Cancel", Style::default().fg(Color::White)),
                 ]),
             ];
+            if let Some(left) = app.confirm_remaining() {
+                msg.push(Line::from(""));
+                msg.push(Line::from(format!("  ⏱ Cancel automatically in {left}s")));
+            }
             let w = Paragraph::new(msg)
                 .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)).title(" Dangerous Action "))
                 .wrap(Wrap { trim: false });
             f.render_widget(w, area);
         }
-        Popup::Help => {
-            let area = centered_rect(70, 70, f.area());
+        Popup::Diagnostics => {
+            let area = centered_rect(75, 75, f.area());
             f.render_widget(Clear, area);
-            let msg = "Raccourcis clavier :\n\n\
-                Global :\n\
-                - q / Ctrl+C : Quitter\n\
-                - Tab : Changer le focus (Liste / Logs)\n\
-                - ? : Afficher cette aide\n\
-                - C : Changer de contexte Docker\n\
-                - H : Dashboard de santé globale (Disk Usage)\n\
-                - V : Lister les volumes\n\
-                - N : Lister les réseaux\n\
-                - / : Filtrer la liste\n\n\
-                Navigation :\n\
-                - Haut/Bas : Sélectionner un item\n\
-                - Espace : Développer/Réduire un groupe\n\
-                - v : (Dés)électionner pour action groupée\n\n\
-                Actions sur Containers/Services :\n\
-                - t : Démarrer\n\
-                - s : Arrêter\n\
-                - r : Redémarrer\n\
-                - e : Shell interactif\n\
-                - L : Logs multi-conteneurs (Compose)\n\
-                - d : Supprimer\n\
-                - i : Inspecter (JSON)\n\
-                - S : Scaler le service Swarm\n\
-                - o : Ouvrir dans le navigateur\n\
-                - P : Épingler (Pin)\n\n\
-                Logs & Maintenance :\n\
-                - H : Dashboard de santé / Cleanup\n\
-                - I : Explorateur d'images (Image Explorer)\n\
-                - m : Mode Copie\n\
-                - y : Copier tout le buffer\n\
-                - PageUp/PageDown : Défiler";
-            let w = Paragraph::new(msg)
-                .block(Block::default().borders(Borders::ALL).title(" Aide des raccourcis "))
+            let key = Style::default().fg(Color::Rgb(255, 170, 0)).add_modifier(Modifier::BOLD);
+            let section = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+            let row = |k: &str, v: String| Line::from(vec![
+                Span::styled(format!("  {k:<16}"), key),
+                Span::raw(v),
+            ]);
+            let or_none = |v: &str| if v.is_empty() { "(none)".to_string() } else { v.to_string() };
+            let d = &app.docker;
+            let (log_targets, log_lines, log_bytes) = app.log_buffer_usage();
+
+            let program = crate::docker::split_args(&d.docker_bin).into_iter().next().unwrap_or_default();
+            let bin_path = crate::config::locate_binary(&program)
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "not found in PATH".to_string());
+            let mut lines = vec![
+                Line::from(Span::styled("Docker", section)),
+                row("Binary", format!("{} → {bin_path}", d.docker_bin)),
+                row("Available", if d.available { "yes".to_string() } else { "no".to_string() }),
+                row("Backend", d.backend.clone()),
+                row("Context", d.context_name.clone()),
+                row("Socket", or_none(&d.socket_path)),
+                row("Remote host", d.remote_host.clone()),
+                row("Engine API", if d.client.is_some() { "connected".to_string() } else { "CLI fallback".to_string() }),
+                row("Compose", d.compose.label().to_string()),
+                Line::from(vec![
+                    Span::styled(format!("  {:<16}", "Failure reason"), key),
+                    match &d.error {
+                        Some(e) => Span::styled(e.clone(), Style::default().fg(Color::Red)),
+                        None => Span::styled("(none)", Style::default().fg(Color::Green)),
+                    },
+                ]),
+                Line::from(""),
+                Line::from(Span::styled("Project", section)),
+                row("Root", app.cfg.cwd.display().to_string()),
+                row("Profile", app.cfg.profile.clone()),
+                row("Compose profile", or_none(&app.cfg.compose_profile)),
+                row("Refresh", match (&app.refresh_events, app.cfg.refresh_on_events) {
+                    (Some(_), _) => format!("on docker events, safety poll every {}s", app.cfg.events_poll_secs),
+                    (None, true) => format!("every {}ms (docker events unavailable)", app.cfg.refresh_ms),
+                    (None, false) => format!("every {}ms", app.cfg.refresh_ms),
+                }),
+                row("Compose files", if app.cfg.compose_files.is_empty() {
+                    "(compose default)".to_string()
+                } else {
+                    app.cfg.compose_files.iter()
+                        .map(|f| {
+                            let shown = f.strip_prefix(&app.cfg.cwd).unwrap_or(f).display().to_string();
+                            if f.exists() { shown } else { format!("{shown} (missing)") }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }),
+                row("Env files", if app.env_files.is_empty() { "(none)".to_string() } else { app.env_files.join(", ") }),
+                Line::from(""),
+                Line::from(Span::styled("Log buffers", section)),
+                row("Buffered", format!("{log_lines} lines, {} KB across {log_targets} targets", log_bytes / 1024)),
+                row("Budget", match app.cfg.log_mem_budget {
+                    0 => "unlimited (LOG_MEM_BUDGET_MB=0)".to_string(),
+                    b => format!("{} MB, {:.0}% used", b / (1024 * 1024), log_bytes as f64 * 100.0 / b as f64),
+                }),
+                row("Evicted", format!(
+                    "{} lines ({} from tasks, {} from the app log)",
+                    app.evicted_log_lines,
+                    app.evicted_log_lines - app.evicted_app_log_lines,
+                    app.evicted_app_log_lines
+                )),
+                Line::from(""),
+                Line::from(Span::styled(format!("Post-up tasks ({})", app.cfg.post_up_tasks.len()), section)),
+                row("Tasks file", format!(
+                    "{}{}",
+                    app.cfg.tasks_file.display(),
+                    if app.cfg.tasks_file.exists() { "" } else { " (missing)" }
+                )),
+            ];
+            if app.cfg.post_up_tasks.is_empty() {
+                lines.push(Line::from("  (none)"));
+            }
+            for t in &app.cfg.post_up_tasks {
+                let status = app.tasks.get(&t.name).map(|rt| rt.status.as_str()).unwrap_or("-");
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<16}", t.name), key),
+                    Span::styled(format!("[{status}] "), Style::default().fg(Color::DarkGray)),
+                    Span::raw(t.cmd.clone()),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("  [Esc]:Close", Style::default().fg(Color::Gray))));
+
+            let w = Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)).title(" 🩺 Diagnostics "))
                 .wrap(Wrap { trim: false });
             f.render_widget(w, area);
         }
+        Popup::Help { scroll } => {
+            let area = centered_rect(90, 90, f.area());
+            f.render_widget(Clear, area);
+            let w = Paragraph::new(help_lines(app))
+                .block(Block::default().borders(Borders::ALL).title(" Aide des raccourcis — ↑/↓ PgUp/PgDn : défiler, Esc/q/? : fermer "))
+                .scroll((*scroll, 0));
+            f.render_widget(w, area);
+        }
     }
 }
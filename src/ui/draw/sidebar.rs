@@ -1,8 +1,9 @@
-use crate::ui::app::App;
+use crate::ui::app::{App, QUICK_JUMP_TIMEOUT, RESTART_FLASH};
 use crate::ui::types::SidebarKind;
 use chrono::Local;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem};
 use ratatui::Frame;
 
@@ -17,10 +18,18 @@ pub fn draw_sidebar(f: &mut Frame, app: &mut App, area: Rect) {
         Style::default().fg(color_dim)
     };
 
+    // Labels are laid out for the pane width; redo them when it changes.
+    let inner_width = area.width.saturating_sub(2);
+    if inner_width != app.sidebar_width {
+        app.sidebar_width = inner_width;
+        app.rebuild_items();
+    }
+
     let items: Vec<ListItem> = if app.docker.available {
         app.items.iter().map(|it| {
             let (raw_label, style) = match it.kind {
                 SidebarKind::GroupHeader => (it.label.clone(), Style::default().fg(color_secondary).add_modifier(Modifier::BOLD)),
+                SidebarKind::AppLog | SidebarKind::StackLogs => (it.label.clone(), Style::default().fg(Color::Rgb(180, 180, 255))),
                 SidebarKind::Separator => (it.label.clone(), Style::default().fg(color_dim).add_modifier(Modifier::DIM)),
                 SidebarKind::SwarmService => {
                     let prefix = if it.selected { "● " } else { "○ " };
@@ -32,7 +41,9 @@ pub fn draw_sidebar(f: &mut Frame, app: &mut App, area: Rect) {
                 }
                 SidebarKind::Container => {
                     let prefix = if it.selected { "● " } else { "○ " };
-                    (format!("{prefix}{}", it.label), Style::default())
+                    let unhealthy = app.containers.iter().any(|(c, _)| c.id == it.id && c.health == "unhealthy");
+                    let style = if unhealthy { Style::default().fg(Color::LightRed) } else { Style::default() };
+                    (format!("{prefix}{}", it.label), style)
                 }
             };
             
@@ -43,6 +54,11 @@ pub fn draw_sidebar(f: &mut Frame, app: &mut App, area: Rect) {
             };
 
             let mut final_style = style;
+            let flashing = app.restart_flash.get(&it.id)
+                .is_some_and(|at| at.elapsed() < RESTART_FLASH && at.elapsed().as_millis() / 500 % 2 == 0);
+            if flashing {
+                final_style = final_style.fg(Color::White).bg(Color::Red);
+            }
             if it.selected && it.kind != SidebarKind::GroupHeader && it.kind != SidebarKind::Separator {
                 final_style = final_style.add_modifier(Modifier::BOLD);
             }
@@ -52,11 +68,27 @@ pub fn draw_sidebar(f: &mut Frame, app: &mut App, area: Rect) {
         vec![ListItem::new("(docker not available)")]
     };
 
-    let updated = Local::now().format("%H:%M:%S").to_string();
+    let brand = if app.cfg.title.is_empty() { String::new() } else { format!("{} — ", app.cfg.title) };
+    // While paused the list is a frozen snapshot, so there is no update time to show.
+    let status = if app.refresh_requested {
+        "[⟳ refreshing…]".to_string()
+    } else if app.refresh_paused {
+        "[⏸ PAUSED]".to_string()
+    } else {
+        format!("[upd: {}]", Local::now().format("%H:%M:%S"))
+    };
+    let jump = match &app.jump_input {
+        Some((digits, at)) if at.elapsed() < QUICK_JUMP_TIMEOUT => format!(" [go: {digits}_]"),
+        _ => String::new(),
+    };
+    let badge = Span::styled(
+        format!(" {} ", app.cfg.profile.to_uppercase()),
+        Style::default().fg(Color::Black).bg(app.profile_color()).add_modifier(Modifier::BOLD),
+    );
     let left_block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style_list)
-        .title(format!(" 🐳 Containers + Tasks [upd: {updated}] "));
+        .title(Line::from(vec![badge, Span::raw(format!(" 🐳 {brand}Containers + Tasks {status}{jump} "))]));
 
     let list = List::new(items)
         .block(left_block)
@@ -1,4 +1,7 @@
+use crate::config::ColorSupport;
+use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier};
 
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -25,3 +28,97 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         )
         .split(popup_layout[1])[1]
 }
+
+/// Fit `name` in `width` characters, padding short names and cutting long
+/// ones in the middle (`supabase-…-worker-1`) so names that share a prefix
+/// stay distinguishable.
+pub fn fit_name(name: &str, width: usize) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    if chars.len() <= width {
+        return format!("{name:<width$}");
+    }
+    if width < 3 {
+        return chars[..width].iter().collect();
+    }
+    let tail = (width - 1) / 2;
+    let head = width - 1 - tail;
+    let mut out: String = chars[..head].iter().collect();
+    out.push('…');
+    out.extend(&chars[chars.len() - tail..]);
+    out
+}
+
+/// Bring a drawn frame down to what the terminal supports: true-colour
+/// values become palette entries, and without colours at all every cell
+/// falls back to the default colours, with backgrounds (selection bars,
+/// banners) shown reversed so they stay visible.
+pub fn degrade_colors(buf: &mut Buffer, support: ColorSupport) {
+    if support == ColorSupport::TrueColor {
+        return;
+    }
+    for cell in buf.content.iter_mut() {
+        if support == ColorSupport::None {
+            if !matches!(cell.bg, Color::Reset | Color::Black) {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+            continue;
+        }
+        cell.fg = degrade_color(cell.fg, support);
+        cell.bg = degrade_color(cell.bg, support);
+    }
+}
+
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn degrade_color(color: Color, support: ColorSupport) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) if support == ColorSupport::Basic => indexed_rgb(i),
+        _ => return color,
+    };
+    if support == ColorSupport::Ansi256 {
+        // Nearest step of the 6x6x6 cube (16..=231).
+        let step = |v: u8| if v < 48 { 0 } else { ((v as u16 - 35) / 40) as u8 };
+        return Color::Indexed(16 + 36 * step(rgb.0) + 6 * step(rgb.1) + step(rgb.2));
+    }
+    let dist = |(r, g, b): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, rgb.0) + d(g, rgb.1) + d(b, rgb.2)
+    };
+    ANSI_16.iter().min_by_key(|(_, c)| dist(*c)).map(|(c, _)| *c).unwrap_or(color)
+}
+
+/// RGB of an xterm 256-colour palette entry.
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => ANSI_16[i as usize].1,
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
@@ -1,15 +1,17 @@
 use crate::ui::app::App;
-use crate::ui::types::{SidebarKind, Popup};
+use crate::ui::types::{SidebarKind, Popup, ForegroundJob};
 use crate::docker;
 use crate::pins;
 use anyhow::Result;
-use arboard::Clipboard;
 
 pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
     match c {
         'o' => {
             app.open_selected_in_browser().await;
         }
+        'O' => {
+            app.copy_selected_url();
+        }
         'c' => {
             if app.docker.available {
                 app.popup = Some(Popup::ConfirmComposeRestart { infra_running: app.infra_already_up() });
@@ -21,22 +23,42 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
             match item.kind {
                 SidebarKind::Container if app.docker.available => {
                     if let Ok(v) = docker::container_inspect(&app.docker, &app.cfg.cwd, &item.id).await {
-                        app.popup = Some(Popup::Inspect { id: item.id.clone(), name: item.name.clone(), json: v, tab: 0 });
+                        app.popup = Some(Popup::Inspect { id: item.id.clone(), name: item.name.clone(), json: v, tab: 0, cursor: 0, scroll_x: 0, wrap: false });
                     }
                 }
                 SidebarKind::SwarmService if app.docker.available => {
                     let out = docker::cmd_inspect_service(&app.docker, &app.cfg.cwd, &item.id).await
                         .unwrap_or_else(|e| format!("Error: {e}"));
-                    app.popup = Some(Popup::Inspect { id: item.id.clone(), name: item.name.clone(), json: serde_json::from_str(&out).unwrap_or(serde_json::Value::String(out)), tab: 0 });
+                    app.popup = Some(Popup::Inspect { id: item.id.clone(), name: item.name.clone(), json: serde_json::from_str(&out).unwrap_or(serde_json::Value::String(out)), tab: 0, cursor: 0, scroll_x: 0, wrap: false });
+                }
+                SidebarKind::Task if app.tasks.contains_key(&item.id) => {
+                    app.popup = Some(Popup::TaskInfo { name: item.id });
                 }
                 _ => {}
             }
         }
+        'B' => {
+            if app.items.is_empty() { return Ok(()); }
+            let item = app.items[app.selected].clone();
+            if item.kind == SidebarKind::Container && app.docker.available {
+                match docker::container_inspect(&app.docker, &app.cfg.cwd, &item.id).await {
+                    Ok(v) => {
+                        let command = docker::docker_run_command(&v);
+                        app.copy_to_clipboard(command.clone(), "docker run command copied");
+                        app.popup = Some(Popup::RunCommand { name: item.name, command });
+                    }
+                    Err(e) => app.push_app_log(&format!("❌ Inspect {} failed: {e}", item.name)),
+                }
+            }
+        }
         'x' => {
             if app.items.is_empty() { return Ok(()); }
             let item = app.items[app.selected].clone();
             if item.kind == SidebarKind::Container && app.docker.available {
-                app.popup = Some(Popup::ConfirmReset { id: item.id, name: item.name });
+                let service = app.containers.iter()
+                    .find(|(c, _)| c.id == item.id)
+                    .and_then(|(c, _)| Some((c.compose_project.clone()?, c.compose_service.clone()?)));
+                app.popup = Some(Popup::ConfirmReset { id: item.id, name: item.name, service });
             }
         }
         'r' => {
@@ -54,7 +76,7 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                             let _ = app.run_task(&item.id).await;
                         }
                         SidebarKind::Container if app.docker.available => {
-                            app.push_current_log(&format!("Restarting container {}...", item.name));
+                            app.push_app_log(&format!("Restarting container {}...", item.name));
                             if let Err(e) = docker::container_action(&app.docker, &app.cfg.cwd, "restart", &item.id).await {
                                 app.notify(format!("❌ Restart failed: {e}"), ratatui::style::Color::Red);
                             } else {
@@ -62,7 +84,7 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                             }
                         }
                         SidebarKind::SwarmService if app.docker.available => {
-                            app.push_current_log(&format!("Rolling restart service {}...", item.name));
+                            app.push_app_log(&format!("Rolling restart service {}...", item.name));
                             if let Err(e) = docker::service_rolling_restart(&app.docker, &app.cfg.cwd, &item.id).await {
                                 app.notify(format!("❌ Rolling restart failed: {e}"), ratatui::style::Color::Red);
                             } else {
@@ -87,6 +109,8 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                 vec![app.items[app.selected].id.clone()]
             };
 
+            let stopped = container_ids(app, &ids);
+            let recovery = app.recovery_command(&stopped, false);
             for id in ids {
                 if let Some(item) = app.items.iter().find(|i| i.id == id).cloned() {
                     match item.kind {
@@ -94,7 +118,7 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                             app.stop_task(&item.id).await;
                         }
                         SidebarKind::Container if app.docker.available => {
-                            app.push_current_log(&format!("Stopping container {}...", item.name));
+                            app.push_app_log(&format!("Stopping container {}...", item.name));
                             if let Err(e) = docker::container_action(&app.docker, &app.cfg.cwd, "stop", &item.id).await {
                                 app.notify(format!("❌ Stop failed: {e}"), ratatui::style::Color::Red);
                             } else {
@@ -102,7 +126,7 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                             }
                         }
                         SidebarKind::SwarmService if app.docker.available => {
-                            app.push_current_log(&format!("Stopping Swarm service {} (scaling to 0)...", item.name));
+                            app.push_app_log(&format!("Stopping Swarm service {} (scaling to 0)...", item.name));
                             if let Err(e) = docker::service_scale(&app.docker, &app.cfg.cwd, &item.id, 0).await {
                                 app.notify(format!("❌ Stop failed: {e}"), ratatui::style::Color::Red);
                             } else {
@@ -113,6 +137,9 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                     }
                 }
             }
+            if !stopped.is_empty() && app.docker.available {
+                app.show_recovery(&format!("🛑 Stopped {}", container_names(app, &stopped)), recovery);
+            }
             app.multi_selected.clear();
             let _ = app.refresh_containers().await;
             let _ = app.refresh_swarm().await;
@@ -134,7 +161,7 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                             let _ = app.run_task(&item.id).await;
                         }
                         SidebarKind::Container if app.docker.available => {
-                            app.push_current_log(&format!("Starting container {}...", item.name));
+                            app.push_app_log(&format!("Starting container {}...", item.name));
                             if let Err(e) = docker::container_action(&app.docker, &app.cfg.cwd, "start", &item.id).await {
                                 app.notify(format!("❌ Start failed: {e}"), ratatui::style::Color::Red);
                             } else {
@@ -142,19 +169,19 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                             }
                         }
                         SidebarKind::SwarmService if app.docker.available => {
-                            app.push_current_log(&format!("Starting Swarm service {} (scaling to 1)...", item.name));
+                            app.push_app_log(&format!("Starting Swarm service {} (scaling to 1)...", item.name));
                             if let Err(e) = docker::service_scale(&app.docker, &app.cfg.cwd, &item.id, 1).await {
                                 app.notify(format!("❌ Start failed: {e}"), ratatui::style::Color::Red);
                             } else {
                                 app.notify(format!("🚀 Started service {}", item.name), ratatui::style::Color::Green);
                             }
                         }
-                        SidebarKind::GroupHeader if item.id != "__pins__" && !item.id.starts_with("stack:") && app.docker.available => {
+                        SidebarKind::GroupHeader if item.is_compose_project() && app.docker.available => {
                             let project = item.id.clone();
-                            app.push_current_log(&format!("🚀 Starting compose project {}...", project));
+                            app.push_app_log(&format!("🚀 Starting compose project {}...", project));
                             match docker::compose_group_up(&app.docker, &app.cfg.cwd, &project).await {
                                 Ok(lines) => {
-                                    for l in lines { app.push_current_log(&l); }
+                                    for l in lines { app.push_app_log(&l); }
                                     app.notify(format!("✅ Compose up done: {}", project), ratatui::style::Color::Green);
                                 }
                                 Err(e) => app.notify(format!("❌ Start failed: {e}"), ratatui::style::Color::Red),
@@ -192,9 +219,9 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
             for id in ids {
                 if let Some(item) = app.items.iter().find(|i| i.id == id).cloned() {
                     if item.kind == SidebarKind::Container && app.docker.available {
-                        app.push_current_log(&format!("Pausing container {}...", item.name));
+                        app.push_app_log(&format!("Pausing container {}...", item.name));
                         if let Err(e) = docker::container_action(&app.docker, &app.cfg.cwd, "pause", &item.id).await {
-                            app.push_current_log(&format!("❌ Pause failed: {e}"));
+                            app.push_app_log(&format!("❌ Pause failed: {e}"));
                         }
                     }
                 }
@@ -213,9 +240,9 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
             for id in ids {
                 if let Some(item) = app.items.iter().find(|i| i.id == id).cloned() {
                     if item.kind == SidebarKind::Container && app.docker.available {
-                        app.push_current_log(&format!("Unpausing container {}...", item.name));
+                        app.push_app_log(&format!("Unpausing container {}...", item.name));
                         if let Err(e) = docker::container_action(&app.docker, &app.cfg.cwd, "unpause", &item.id).await {
-                            app.push_current_log(&format!("❌ Unpause failed: {e}"));
+                            app.push_app_log(&format!("❌ Unpause failed: {e}"));
                         }
                     }
                 }
@@ -231,16 +258,21 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
             } else {
                 vec![app.items[app.selected].id.clone()]
             };
+            let killed = container_ids(app, &ids);
+            let recovery = app.recovery_command(&killed, false);
             for id in ids {
                 if let Some(item) = app.items.iter().find(|i| i.id == id).cloned() {
                     if item.kind == SidebarKind::Container && app.docker.available {
-                        app.push_current_log(&format!("Killing container {}...", item.name));
+                        app.push_app_log(&format!("Killing container {}...", item.name));
                         if let Err(e) = docker::container_action(&app.docker, &app.cfg.cwd, "kill", &item.id).await {
-                            app.push_current_log(&format!("❌ Kill failed: {e}"));
+                            app.push_app_log(&format!("❌ Kill failed: {e}"));
                         }
                     }
                 }
             }
+            if !killed.is_empty() && app.docker.available {
+                app.show_recovery(&format!("💀 Killed {}", container_names(app, &killed)), recovery);
+            }
             app.multi_selected.clear();
             let _ = app.refresh_containers().await;
             app.rebuild_items();
@@ -253,12 +285,15 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                 vec![app.items[app.selected].id.clone()]
             };
 
+            let removed = container_ids(app, &ids);
+            let recovery = app.recovery_command(&removed, true);
+            let removed_names = container_names(app, &removed);
             for id in ids {
                 if let Some(item) = app.items.iter().find(|i| i.id == id).cloned() {
                     if app.docker.available {
                         match item.kind {
                             SidebarKind::Container => {
-                                app.push_current_log(&format!("Removing container {} (force)...", item.name));
+                                app.push_app_log(&format!("Removing container {} (force)...", item.name));
                                 if let Err(e) = docker::container_rm_force(&app.docker, &app.cfg.cwd, &item.id).await {
                                     app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red);
                                 } else {
@@ -266,7 +301,7 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                                 }
                             }
                             SidebarKind::SwarmService => {
-                                app.push_current_log(&format!("Removing Swarm service {}...", item.name));
+                                app.push_app_log(&format!("Removing Swarm service {}...", item.name));
                                 if let Err(e) = docker::service_rm(&app.docker, &app.cfg.cwd, &item.id).await {
                                     app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red);
                                 } else {
@@ -278,6 +313,9 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
                     }
                 }
             }
+            if !removed.is_empty() && app.docker.available {
+                app.show_recovery(&format!("🗑️ Removed {removed_names}"), recovery);
+            }
             app.multi_selected.clear();
             let _ = app.refresh_containers().await;
             let _ = app.refresh_swarm().await;
@@ -285,17 +323,23 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
             let _ = app.select(app.selected).await;
         }
         'y' => {
-            let text = app.log_lines.iter().cloned().collect::<Vec<_>>().join("\n");
-            match Clipboard::new().and_then(|mut cb| { cb.set_text(text)?; Ok(()) }) {
-                Ok(()) => app.push_current_log("📋 Logs copied to clipboard."),
-                Err(_)  => app.push_current_log("⚠️  Clipboard unavailable."),
+            let text = app.log_text();
+            let done = format!("{} log lines copied", app.log_lines.len());
+            if app.copy_to_clipboard(text, &done) {
+                app.push_app_log(&format!("📋 {done} ({})", app.current_target));
             }
         }
         'e' => {
             if app.items.is_empty() { return Ok(()); }
             let item = app.items[app.selected].clone();
             if (item.kind == SidebarKind::Container || item.kind == SidebarKind::SwarmService) && app.docker.available {
-                app.start_shell(&item.id, item.kind).await?;
+                if item.kind == SidebarKind::SwarmService || app.cfg.exec_in_pane {
+                    app.start_shell(&item.id, item.kind).await?;
+                } else if app.containers.iter().any(|(c, _)| c.id == item.id && c.state.eq_ignore_ascii_case("running")) {
+                    app.pending_foreground = Some(ForegroundJob::Exec { id: item.id.clone(), name: item.name.clone() });
+                } else {
+                    app.notify(format!("⚠️ {} is not running", item.name), ratatui::style::Color::Yellow);
+                }
             }
         }
         'P' => {
@@ -304,10 +348,10 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
             if item.kind == SidebarKind::Container {
                 if app.pins.contains(&item.name) {
                     app.pins.remove(&item.name);
-                    app.push_current_log(&format!("📌 Unpinned {}.", item.name));
+                    app.push_app_log(&format!("📌 Unpinned {}.", item.name));
                 } else {
                     app.pins.insert(item.name.clone());
-                    app.push_current_log(&format!("📌 Pinned {}.", item.name));
+                    app.push_app_log(&format!("📌 Pinned {}.", item.name));
                 }
                 pins::save_pins(&app.pins);
                 app.rebuild_items();
@@ -316,34 +360,85 @@ pub async fn handle_action(app: &mut App, c: char) -> Result<()> {
         'R' => {
             if app.items.is_empty() { return Ok(()); }
             let item = app.items[app.selected].clone();
-            if item.kind == SidebarKind::GroupHeader && item.id != "__pins__" && !item.id.starts_with("stack:") && app.docker.available {
+            if item.is_compose_project() && app.docker.available {
                 let project = item.id.clone();
-                app.push_current_log(&format!("🔄 Restarting compose project {}...", project));
+                app.push_app_log(&format!("🔄 Restarting compose project {}...", project));
                 match docker::compose_group_restart(&app.docker, &app.cfg.cwd, &project).await {
                     Ok(lines) => {
-                        for l in lines { app.push_current_log(&l); }
-                        app.push_current_log("✅ Compose restart done.");
+                        for l in lines { app.push_app_log(&l); }
+                        app.push_app_log("✅ Compose restart done.");
+                    }
+                    Err(e) => app.push_app_log(&format!("❌ Restart failed: {e}")),
+                }
+                return Ok(());
+            }
+            // On a compose container (or a scaled service header): recreate just that service.
+            if let (Some(service), true) = (selected_compose_service(app), app.docker.available) {
+                let profile = app.cfg.compose_profile.clone();
+                app.push_app_log(&format!("♻️ Recreating service {service} (profile: {profile}, --force-recreate --no-deps)..."));
+                let args = ["up", "-d", "--force-recreate", "--no-deps", service.as_str()].map(String::from).to_vec();
+                if let Err(e) = app.start_compose_job(args, false) {
+                    app.push_app_log(&format!("❌ docker compose failed to start: {e}"));
+                }
+            }
+        }
+        'l' => {
+            app.jump_to_linked().await;
+        }
+        'K' => {
+            if app.items.is_empty() || !app.docker.available { return Ok(()); }
+            match selected_compose_service(app) {
+                Some(service) => {
+                    app.push_app_log(&format!("🔄 Restarting service {service} (profile: {})...", app.cfg.compose_profile));
+                    if let Err(e) = app.restart_compose_service(&service) {
+                        app.notify(format!("❌ Restart of {service} failed: {e}"), ratatui::style::Color::Red);
                     }
-                    Err(e) => app.push_current_log(&format!("❌ Restart failed: {e}")),
                 }
+                None => app.notify("⚠️ Not a compose service".to_string(), ratatui::style::Color::Yellow),
             }
         }
         'D' => {
             if app.items.is_empty() { return Ok(()); }
             let item = app.items[app.selected].clone();
-            if item.kind == SidebarKind::GroupHeader && item.id != "__pins__" && !item.id.starts_with("stack:") && app.docker.available {
-                let project = item.id.clone();
-                app.push_current_log(&format!("🛑 Stopping compose project {}...", project));
-                match docker::compose_group_down(&app.docker, &app.cfg.cwd, &project).await {
-                    Ok(lines) => {
-                        for l in lines { app.push_current_log(&l); }
-                        app.push_current_log("✅ Compose down done.");
-                    }
-                    Err(e) => app.push_current_log(&format!("❌ Down failed: {e}")),
-                }
+            if item.is_compose_project() && app.docker.available {
+                app.popup = Some(Popup::ConfirmComposeDown { project: item.id.clone() });
+            }
+        }
+        other => {
+            if let Some(action) = app.cfg.container_actions.iter().find(|a| a.key == other).cloned() {
+                app.run_container_action(&action).await;
             }
         }
-        _ => {}
     }
     Ok(())
 }
+
+/// Compose service of the selected container, or of a scaled service header.
+fn selected_compose_service(app: &App) -> Option<String> {
+    let item = app.items.get(app.selected)?;
+    match item.kind {
+        SidebarKind::Container => app.containers.iter()
+            .find(|(c, _)| c.id == item.id)
+            .and_then(|(c, _)| c.compose_service.clone()),
+        SidebarKind::GroupHeader => item.id.strip_prefix("svc:")
+            .and_then(|s| s.split_once('/'))
+            .map(|(_, svc)| svc.to_string()),
+        _ => None,
+    }
+}
+
+/// The container ids among `ids` (multi-select may mix in tasks and services).
+fn container_ids(app: &App, ids: &[String]) -> Vec<String> {
+    ids.iter()
+        .filter(|id| app.items.iter().any(|i| i.kind == SidebarKind::Container && &i.id == *id))
+        .cloned()
+        .collect()
+}
+
+fn container_names(app: &App, ids: &[String]) -> String {
+    app.items.iter()
+        .filter(|i| i.kind == SidebarKind::Container && ids.contains(&i.id))
+        .map(|i| i.name.clone())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
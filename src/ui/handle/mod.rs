@@ -8,6 +8,15 @@ use crossterm::event::{Event, KeyCode};
 
 pub async fn handle_event(app: &mut App, ev: Event) -> Result<bool> {
     if let Event::Key(k) = ev {
+        // 0. Digits / `g<number>` jump straight to a list item
+        if navigation::handle_quick_jump(app, k).await {
+            return Ok(false);
+        }
+        if let Some(code) = navigation::vim_navigation(app, k) {
+            navigation::handle_navigation(app, code).await;
+            return Ok(false);
+        }
+
         // 1. Check if it's a general global shortcut
         let should_quit = shortcuts::handle_shortcut(app, k.code, k.modifiers).await;
         if should_quit {
@@ -38,8 +47,11 @@ pub async fn handle_event(app: &mut App, ev: Event) -> Result<bool> {
                 // it might have changed app state (like opening a popup) but return false. 
                 // We shouldn't process it as an action if it was meant to be a shortcut.
                 // However, our action list ('t', 's', 'p', 'd', 'e', etc.) doesn't overlap with shortcuts.
-                if !matches!(c, 'C' | 'V' | 'N' | 'm' | 'v' | ' ' | 'L' | 'H' | '?' | '/') {
-                    actions::handle_action(app, c).await?;
+                if !matches!(c, 'C' | 'V' | 'N' | 'n' | 'm' | 'v' | ' ' | 'L' | 'W' | 'H' | 'h' | 'T' | '?' | '!' | ':' | 'M' | 'z' | 'Z' | 'a' | 'Y' | 'U' | '/' | '+' | '-' | 'G' | 'A' | ';' | 'w')
+                    && (c != 't' || app.focus_on_list) {
+                    if let Some(c) = app.cfg.keys.dispatch(c) {
+                        actions::handle_action(app, c).await?;
+                    }
                 }
             }
         }
@@ -1,6 +1,118 @@
-use crate::ui::app::App;
+use crate::config::EnterAction;
+use crate::keybindings::Action;
+use crate::ui::app::{App, QUICK_JUMP_TIMEOUT};
 use crate::ui::types::SidebarKind;
-use crossterm::event::{KeyCode, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEventKind};
+
+/// Quick jump in the list: `1`-`9` select the Nth item, `g` followed by
+/// digits reaches further down, `gg` goes back to the top, and `g` followed
+/// by letters selects by name (type-to-select). Returns true when the key
+/// was consumed. The pending input is dropped on timeout or on any other key.
+pub async fn handle_quick_jump(app: &mut App, k: KeyEvent) -> bool {
+    if !app.focus_on_list || app.popup.is_some() || app.is_filtering || app.shell_active
+        || k.modifiers.contains(KeyModifiers::CONTROL)
+    {
+        app.jump_input = None;
+        return false;
+    }
+    let pending = app.jump_input.take().filter(|(_, at)| at.elapsed() < QUICK_JUMP_TIMEOUT);
+    match (k.code, pending) {
+        (KeyCode::Char('g'), None) => {
+            app.jump_input = Some((String::new(), std::time::Instant::now()));
+            true
+        }
+        (KeyCode::Char('g'), Some((typed, _))) if typed.is_empty() => {
+            jump_to(app, 1).await;
+            true
+        }
+        (KeyCode::Char(c), Some((mut digits, _))) if c.is_ascii_digit() && digits.chars().all(|d| d.is_ascii_digit()) => {
+            digits.push(c);
+            let n = digits.parse().unwrap_or(0);
+            app.jump_input = Some((digits, std::time::Instant::now()));
+            jump_to(app, n).await;
+            true
+        }
+        (KeyCode::Char(c), Some((mut typed, _))) if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') => {
+            typed.push(c);
+            jump_to_name(app, &typed).await;
+            app.jump_input = Some((typed, std::time::Instant::now()));
+            true
+        }
+        (KeyCode::Backspace, Some((mut typed, _))) => {
+            typed.pop();
+            if !typed.is_empty() && !typed.chars().all(|d| d.is_ascii_digit()) {
+                jump_to_name(app, &typed).await;
+            }
+            app.jump_input = Some((typed, std::time::Instant::now()));
+            true
+        }
+        (KeyCode::Char(c @ '1'..='9'), None) => {
+            jump_to(app, c as usize - '0' as usize).await;
+            true
+        }
+        (KeyCode::Esc, Some(_)) => true,
+        _ => false,
+    }
+}
+
+/// Vim keys, as the arrow key they stand for. In the logs pane `j`/`k`/`g`/`G`
+/// scroll like Down/Up/Home/End. In the list `j`/`k` move and `G` goes to the
+/// last item (`g` is the jump prefix, `gg` the top), but `k` stays Kill while
+/// it is the kill key and a container is selected or marked.
+pub fn vim_navigation(app: &App, k: KeyEvent) -> Option<KeyCode> {
+    if app.popup.is_some() || app.is_filtering || app.shell_active || k.modifiers.contains(KeyModifiers::CONTROL) {
+        return None;
+    }
+    let kills = app.cfg.keys.key(Action::Kill) == 'k'
+        && (!app.multi_selected.is_empty()
+            || app.items.get(app.selected).is_some_and(|i| i.kind == SidebarKind::Container));
+    match (k.code, app.focus_on_list) {
+        (KeyCode::Char('j'), _) => Some(KeyCode::Down),
+        (KeyCode::Char('k'), true) if kills => None,
+        (KeyCode::Char('k'), _) => Some(KeyCode::Up),
+        (KeyCode::Char('g'), false) => Some(KeyCode::Home),
+        (KeyCode::Char('G'), _) => Some(KeyCode::End),
+        _ => None,
+    }
+}
+
+/// Select the first item whose name starts with `typed`, else contains it,
+/// else contains its letters in order (case-insensitive).
+async fn jump_to_name(app: &mut App, typed: &str) {
+    let query = typed.to_lowercase();
+    let subsequence = |name: &str| {
+        let mut chars = name.chars();
+        query.chars().all(|q| chars.any(|c| c == q))
+    };
+    let names: Vec<(usize, String)> = app.items.iter()
+        .enumerate()
+        .filter(|(_, i)| !matches!(i.kind, SidebarKind::Separator | SidebarKind::AppLog | SidebarKind::StackLogs))
+        .map(|(idx, i)| (idx, i.name.to_lowercase()))
+        .collect();
+    let found = names.iter().find(|(_, n)| n.starts_with(&query))
+        .or_else(|| names.iter().find(|(_, n)| n.contains(&query)))
+        .or_else(|| names.iter().find(|(_, n)| subsequence(n)));
+    if let Some(&(idx, _)) = found {
+        if idx != app.selected {
+            let _ = app.select(idx).await;
+        }
+    }
+}
+
+/// Select the Nth (1-based) selectable item; separators are not counted.
+async fn jump_to(app: &mut App, n: usize) {
+    let Some(idx) = app.items.iter()
+        .enumerate()
+        .filter(|(_, i)| i.kind != SidebarKind::Separator)
+        .nth(n.saturating_sub(1))
+        .map(|(idx, _)| idx)
+    else {
+        return;
+    };
+    if n > 0 && idx != app.selected {
+        let _ = app.select(idx).await;
+    }
+}
 
 pub async fn handle_navigation(app: &mut App, k: KeyCode) {
     if app.focus_on_list {
@@ -29,8 +141,24 @@ pub async fn handle_navigation(app: &mut App, k: KeyCode) {
                 }
                 let _ = app.select(app.selected).await;
             }
+            KeyCode::End => {
+                if let Some(last) = app.items.iter().rposition(|i| i.kind != SidebarKind::Separator) {
+                    app.selected = last;
+                    let _ = app.select(last).await;
+                }
+            }
             KeyCode::Enter => {
-                let _ = app.select(app.selected).await;
+                let defaults = app.cfg.default_action;
+                let action = match app.items.get(app.selected).map(|i| &i.kind) {
+                    Some(SidebarKind::Container) => defaults.container,
+                    Some(SidebarKind::SwarmService) => defaults.service,
+                    Some(SidebarKind::Task) => defaults.task,
+                    _ => EnterAction::Logs,
+                };
+                match action.key() {
+                    Some(c) => { let _ = super::actions::handle_action(app, c).await; }
+                    None => { let _ = app.select(app.selected).await; }
+                }
             }
             _ => {}
         }
@@ -64,6 +192,7 @@ pub async fn handle_navigation(app: &mut App, k: KeyCode) {
             }
             KeyCode::End => {
                 app.stick_to_bottom = true;
+                app.log_match = None;
             }
             _ => {}
         }
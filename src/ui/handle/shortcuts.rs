@@ -1,5 +1,5 @@
 use crate::ui::app::App;
-use crate::ui::types::{SidebarKind, Popup};
+use crate::ui::types::{SidebarKind, Popup, ForegroundJob, TypedAction};
 use crate::docker;
 use crossterm::event::{KeyCode, KeyModifiers, EnableMouseCapture, DisableMouseCapture};
 use tokio::io::AsyncWriteExt;
@@ -47,9 +47,10 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
         return false;
     }
 
-    // Global quit
+    // Global quit ('q' is plain text inside the compose prompt)
+    let typing = matches!(app.popup, Some(Popup::ComposeCommand { .. } | Popup::SaveBookmark { .. } | Popup::GoToContainer { .. } | Popup::ConfirmTyped { .. }));
     if (k == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL))
-        || k == KeyCode::Char('q')
+        || (k == KeyCode::Char('q') && !typing)
     {
         return true;
     }
@@ -61,7 +62,13 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
 
     // Help popup
     if k == KeyCode::Char('?') && app.popup.is_none() && !app.is_filtering {
-        app.popup = Some(Popup::Help);
+        app.popup = Some(Popup::Help { scroll: 0 });
+        return false;
+    }
+
+    // Startup diagnostics
+    if k == KeyCode::Char('!') && app.popup.is_none() && !app.is_filtering {
+        app.popup = Some(Popup::Diagnostics);
         return false;
     }
 
@@ -91,20 +98,43 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
     // ── Log Filtering Mode ──
     if app.is_filtering_logs {
         match k {
-            KeyCode::Enter | KeyCode::Esc => {
+            KeyCode::Enter => {
                 app.is_filtering_logs = false;
-                if k == KeyCode::Esc {
-                    app.log_filter_query.clear();
-                }
             }
-            KeyCode::Char(c) => {
-                app.log_filter_query.push(c);
+            KeyCode::Esc => {
+                app.is_filtering_logs = false;
+                app.clear_log_query();
             }
+            // Tab: switch between search (all lines) and filter (matches only).
+            KeyCode::Tab => match app.log_search.take() {
+                Some(query) => app.log_filter_query = query,
+                None => app.log_search = Some(std::mem::take(&mut app.log_filter_query)),
+            },
+            KeyCode::Char(c) => match app.log_search.as_mut() {
+                Some(query) => query.push(c),
+                None => app.log_filter_query.push(c),
+            },
             KeyCode::Backspace => {
-                app.log_filter_query.pop();
+                match app.log_search.as_mut() {
+                    Some(query) => query.pop(),
+                    None => app.log_filter_query.pop(),
+                };
             }
             _ => {}
         }
+        app.update_log_filter();
+        app.log_match = None;
+        return false;
+    }
+
+    // n / N: next / previous log search match (logs focus, query set)
+    if matches!(k, KeyCode::Char('n') | KeyCode::Char('N'))
+        && !app.focus_on_list
+        && !app.log_query().is_empty()
+        && app.popup.is_none()
+        && !app.shell_active
+    {
+        app.jump_log_match(k == KeyCode::Char('n'));
         return false;
     }
 
@@ -114,6 +144,9 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
             app.is_filtering = true;
         } else {
             app.is_filtering_logs = true;
+            if app.log_filter_query.is_empty() && app.log_search.is_none() {
+                app.log_search = Some(String::new());
+            }
         }
         return false;
     }
@@ -122,23 +155,77 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
     // ── Copy mode ──
     if app.copy_mode {
         app.copy_mode = false;
-        crossterm::execute!(io::stdout(), EnableMouseCapture).ok();
+        if app.mouse_capture {
+            crossterm::execute!(io::stdout(), EnableMouseCapture).ok();
+        }
+        return false;
+    }
+
+    // Esc: drop the kept log search / filter (logs focus)
+    if k == KeyCode::Esc && !app.focus_on_list && app.popup.is_none() && !app.log_query().is_empty() {
+        app.clear_log_query();
         return false;
     }
 
     // popup mode
     if let Some(p) = app.popup.clone() {
         match p {
-            Popup::Inspect { id, name, json, tab } => {
+            Popup::Inspect { id, name, json, mut tab, mut cursor, mut scroll_x, mut wrap } => {
+                let lines = crate::ui::draw::popups::inspect_lines(&name, &json, tab);
+                let last = lines.len().saturating_sub(1);
                 match k {
                     KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('?') => {
                         app.popup = None;
+                        return false;
                     }
-                    KeyCode::Tab | KeyCode::Right => {
-                        app.popup = Some(Popup::Inspect { id, name, json, tab: (tab + 1) % 3 });
+                    KeyCode::Tab => { tab = (tab + 1) % 4; cursor = 0; scroll_x = 0; }
+                    KeyCode::BackTab => { tab = (tab + 3) % 4; cursor = 0; scroll_x = 0; }
+                    // Raw JSON and back to the summary
+                    KeyCode::Char('J') => { tab = if tab == 3 { 0 } else { 3 }; cursor = 0; scroll_x = 0; }
+                    KeyCode::Right if !wrap => scroll_x += 8,
+                    KeyCode::Left => scroll_x = scroll_x.saturating_sub(8),
+                    KeyCode::Home => scroll_x = 0,
+                    KeyCode::Char('w') => { wrap = !wrap; scroll_x = 0; }
+                    KeyCode::Up => cursor = cursor.saturating_sub(1),
+                    KeyCode::Down => cursor = (cursor + 1).min(last),
+                    KeyCode::PageUp => cursor = cursor.saturating_sub(10),
+                    KeyCode::PageDown => cursor = (cursor + 10).min(last),
+                    KeyCode::Char('y') => {
+                        match lines.get(cursor).map(|(_, v)| v.clone()).filter(|v| !v.is_empty()) {
+                            Some(value) => {
+                                app.copy_to_clipboard(value.clone(), &format!("Copied: {value}"));
+                            }
+                            None => app.notify("Nothing to copy on this line".to_string(), ratatui::style::Color::Yellow),
+                        }
                     }
-                    KeyCode::Left => {
-                        app.popup = Some(Popup::Inspect { id, name, json, tab: (tab + 2) % 3 });
+                    _ => {}
+                }
+                // Never scroll past the widest line of the tab.
+                let widest = lines.iter().map(|(text, _)| text.chars().count()).max().unwrap_or(0);
+                scroll_x = scroll_x.min(widest.saturating_sub(1));
+                app.popup = Some(Popup::Inspect { id, name, json, tab, cursor, scroll_x, wrap });
+                return false;
+            }
+            Popup::RunCommand { command, .. } => {
+                match k {
+                    KeyCode::Char('y') => {
+                        app.copy_to_clipboard(command, "docker run command copied");
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::TaskInfo { name } => {
+                match k {
+                    KeyCode::Char('y') => {
+                        let cmd = app.tasks.get(&name).map(|rt| rt.spec.cmd.clone()).unwrap_or_default();
+                        app.copy_to_clipboard(cmd, "Task command copied");
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') | KeyCode::Enter => {
+                        app.popup = None;
                     }
                     _ => {}
                 }
@@ -160,7 +247,9 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                 match k {
                     KeyCode::Char('y') | KeyCode::Enter => {
                         app.popup = None;
-                        app.push_current_log(&format!("🗑️ Removing {} containers...", ids.len()));
+                        app.push_app_log(&format!("🗑️ Removing {} containers...", ids.len()));
+                        let recovery = app.recovery_command(&ids, true);
+                        app.show_recovery(&format!("🗑️ Removed {} containers", ids.len()), recovery);
                         for id in ids {
                             let _ = crate::docker::container_rm_force(&app.docker, &app.cfg.cwd, &id).await;
                         }
@@ -176,11 +265,13 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
             }
             Popup::ConfirmPrune => {
                 match k {
+                    KeyCode::Char('y') | KeyCode::Enter if app.cfg.typed_confirm => {
+                        app.popup = Some(Popup::ConfirmTyped { expected: "prune".to_string(), input: String::new(), action: TypedAction::Prune });
+                        app.confirm_opened = None;
+                    }
                     KeyCode::Char('y') | KeyCode::Enter => {
                         app.popup = None;
-                        app.push_current_log("🧹 Pruning system...");
-                        app.stats_refreshing = true;
-                        let _ = app.trigger_prune().await;
+                        run_typed_action(app, TypedAction::Prune).await;
                     }
                     KeyCode::Esc | KeyCode::Char('n') => {
                         app.popup = None;
@@ -189,12 +280,25 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                 }
                 return false;
             }
-            Popup::Help => {
-                if matches!(k, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('?')) {
+            Popup::Diagnostics => {
+                if matches!(k, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('!')) {
                     app.popup = None;
                 }
                 return false;
             }
+            Popup::Help { scroll } => {
+                app.popup = match k {
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('?') => None,
+                    KeyCode::Up => Some(Popup::Help { scroll: scroll.saturating_sub(1) }),
+                    KeyCode::Down => Some(Popup::Help { scroll: scroll.saturating_add(1) }),
+                    KeyCode::PageUp => Some(Popup::Help { scroll: scroll.saturating_sub(10) }),
+                    KeyCode::PageDown => Some(Popup::Help { scroll: scroll.saturating_add(10) }),
+                    KeyCode::Home => Some(Popup::Help { scroll: 0 }),
+                    KeyCode::End => Some(Popup::Help { scroll: u16::MAX }),
+                    _ => Some(Popup::Help { scroll }),
+                };
+                return false;
+            }
             Popup::FileExplorer { id, name, path, files, selected } => {
                 match k {
                     KeyCode::Up => {
@@ -215,13 +319,11 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                             }
                         }
                     }
-                    KeyCode::Backspace | KeyCode::Left => {
-                        if path != "/" {
-                            let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
-                            let new_path = if parts.len() <= 1 { "/".to_string() } else { format!("/{}", parts[..parts.len()-1].join("/")) };
-                            if let Ok(new_files) = docker::list_container_files(&app.docker, &id, &new_path).await {
-                                app.popup = Some(Popup::FileExplorer { id, name, path: new_path, files: new_files, selected: 0 });
-                            }
+                    KeyCode::Backspace | KeyCode::Left if path != "/" => {
+                        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+                        let new_path = if parts.len() <= 1 { "/".to_string() } else { format!("/{}", parts[..parts.len()-1].join("/")) };
+                        if let Ok(new_files) = docker::list_container_files(&app.docker, &id, &new_path).await {
+                            app.popup = Some(Popup::FileExplorer { id, name, path: new_path, files: new_files, selected: 0 });
                         }
                     }
                     KeyCode::Esc | KeyCode::Char('q') => {
@@ -241,38 +343,15 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                         let new_sel = if selected + 1 >= volumes.len() { 0 } else { selected + 1 };
                         app.popup = Some(Popup::Volumes { volumes, selected: new_sel });
                     }
-                    KeyCode::Char('d') => {
-                        if !volumes.is_empty() {
-                            let vol = &volumes[selected];
-                            app.push_current_log(&format!("🗑️ Removing volume {}...", vol.name));
-                            if let Err(e) = docker::rm_volume(&app.docker, &app.cfg.cwd, &vol.name, false).await {
-                                app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red);
-                            } else {
-                                app.notify(format!("✅ Removed volume {}", vol.name), ratatui::style::Color::Green);
-                                if let Ok(new_vols) = docker::get_volumes(&app.docker, &app.cfg.cwd).await {
-                                    let new_sel = if selected >= new_vols.len() { new_vols.len().saturating_sub(1) } else { selected };
-                                    app.popup = Some(Popup::Volumes { volumes: new_vols, selected: new_sel });
-                                } else {
-                                    app.popup = None;
-                                }
-                            }
-                        }
+                    KeyCode::Char(c @ ('d' | 'D')) if !volumes.is_empty() => {
+                        let name = volumes[selected].name.clone();
+                        app.popup = Some(Popup::ConfirmVolumeRemove { name, force: c == 'D', selected });
                     }
-                    KeyCode::Char('D') => {
-                        if !volumes.is_empty() {
-                            let vol = &volumes[selected];
-                            app.push_current_log(&format!("🗑️ Force removing volume {}...", vol.name));
-                            if let Err(e) = docker::rm_volume(&app.docker, &app.cfg.cwd, &vol.name, true).await {
-                                app.notify(format!("❌ Force remove failed: {e}"), ratatui::style::Color::Red);
-                            } else {
-                                app.notify(format!("✅ Force removed volume {}", vol.name), ratatui::style::Color::Green);
-                                if let Ok(new_vols) = docker::get_volumes(&app.docker, &app.cfg.cwd).await {
-                                    let new_sel = if selected >= new_vols.len() { new_vols.len().saturating_sub(1) } else { selected };
-                                    app.popup = Some(Popup::Volumes { volumes: new_vols, selected: new_sel });
-                                } else {
-                                    app.popup = None;
-                                }
-                            }
+                    KeyCode::Enter | KeyCode::Char('i') if !volumes.is_empty() => {
+                        let name = volumes[selected].name.clone();
+                        app.popup = None;
+                        if let Err(e) = app.show_volume(&name).await {
+                            app.notify(format!("❌ Inspect failed: {e}"), ratatui::style::Color::Red);
                         }
                     }
                     KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
@@ -282,6 +361,50 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                 }
                 return false;
             }
+            Popup::ConfirmImagePrune { .. } => {
+                match k {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        app.push_app_log("🧹 docker image prune -f...");
+                        match docker::prune_images(&app.docker, &app.cfg.cwd).await {
+                            Ok(out) => {
+                                for line in out.lines().filter(|l| !l.trim().is_empty()) {
+                                    app.push_app_log(&format!("  {line}"));
+                                }
+                                app.notify("🧹 Dangling images pruned".to_string(), ratatui::style::Color::Green);
+                            }
+                            Err(e) => app.notify(format!("❌ Prune failed: {e}"), ratatui::style::Color::Red),
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('n') => {}
+                    _ => return false,
+                }
+                app.popup = docker::get_images(&app.docker, &app.cfg.cwd).await
+                    .ok()
+                    .map(|images| Popup::ImageExplorer { images, selected: 0 });
+                return false;
+            }
+            Popup::ConfirmVolumeRemove { name, force, selected } => {
+                match k {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        let flag = if force { " -f" } else { "" };
+                        app.push_app_log(&format!("🗑️ docker volume rm{flag} {name}..."));
+                        match docker::rm_volume(&app.docker, &app.cfg.cwd, &name, force).await {
+                            Ok(()) => app.notify(format!("✅ Removed volume {name}"), ratatui::style::Color::Green),
+                            Err(e) => app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red),
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('n') => {}
+                    _ => return false,
+                }
+                app.popup = match docker::get_volumes(&app.docker, &app.cfg.cwd).await {
+                    Ok(volumes) => {
+                        let selected = selected.min(volumes.len().saturating_sub(1));
+                        Some(Popup::Volumes { volumes, selected })
+                    }
+                    Err(_) => None,
+                };
+                return false;
+            }
             Popup::Networks { networks, selected } => {
                 match k {
                     KeyCode::Up => {
@@ -292,20 +415,18 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                         let new_sel = if selected + 1 >= networks.len() { 0 } else { selected + 1 };
                         app.popup = Some(Popup::Networks { networks, selected: new_sel });
                     }
-                    KeyCode::Char('d') => {
-                        if !networks.is_empty() {
-                            let net = &networks[selected];
-                            app.push_current_log(&format!("🗑️ Removing network {}...", net.name));
-                            if let Err(e) = docker::rm_network(&app.docker, &app.cfg.cwd, &net.id).await {
-                                app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red);
+                    KeyCode::Char('d') if !networks.is_empty() => {
+                        let net = &networks[selected];
+                        app.push_app_log(&format!("🗑️ Removing network {}...", net.name));
+                        if let Err(e) = docker::rm_network(&app.docker, &app.cfg.cwd, &net.id).await {
+                            app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red);
+                        } else {
+                            app.notify(format!("✅ Removed network {}", net.name), ratatui::style::Color::Green);
+                            if let Ok(new_nets) = docker::get_networks(&app.docker, &app.cfg.cwd).await {
+                                let new_sel = if selected >= new_nets.len() { new_nets.len().saturating_sub(1) } else { selected };
+                                app.popup = Some(Popup::Networks { networks: new_nets, selected: new_sel });
                             } else {
-                                app.notify(format!("✅ Removed network {}", net.name), ratatui::style::Color::Green);
-                                if let Ok(new_nets) = docker::get_networks(&app.docker, &app.cfg.cwd).await {
-                                    let new_sel = if selected >= new_nets.len() { new_nets.len().saturating_sub(1) } else { selected };
-                                    app.popup = Some(Popup::Networks { networks: new_nets, selected: new_sel });
-                                } else {
-                                    app.popup = None;
-                                }
+                                app.popup = None;
                             }
                         }
                     }
@@ -316,19 +437,303 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                 }
                 return false;
             }
-            Popup::ConfirmReset { id, name } => {
+            Popup::ComposePs { rows, selected } => {
+                match k {
+                    KeyCode::Up => {
+                        let new_sel = if selected == 0 { rows.len().saturating_sub(1) } else { selected - 1 };
+                        app.popup = Some(Popup::ComposePs { rows, selected: new_sel });
+                    }
+                    KeyCode::Down => {
+                        let new_sel = if selected + 1 >= rows.len() { 0 } else { selected + 1 };
+                        app.popup = Some(Popup::ComposePs { rows, selected: new_sel });
+                    }
+                    KeyCode::Enter if !rows.is_empty() => {
+                        let row = &rows[selected];
+                        let id = app.containers.iter()
+                            .find(|(c, _)| !row.name.is_empty() && docker::container_name(&c.names) == row.name)
+                            .map(|(c, _)| c.id.clone());
+                        match id {
+                            Some(id) => {
+                                app.popup = None;
+                                app.reveal_container(&id).await;
+                            }
+                            None => app.notify(format!("⚠️ {} has no container (U starts it)", row.service), ratatui::style::Color::Yellow),
+                        }
+                    }
+                    KeyCode::Char('U') => {
+                        let mut services: Vec<String> = rows.iter()
+                            .filter(|r| !rows.iter().any(|o| o.service == r.service && o.state.eq_ignore_ascii_case("running")))
+                            .map(|r| r.service.clone())
+                            .collect();
+                        services.dedup();
+                        if services.is_empty() {
+                            app.notify("✅ Every compose service is already running".to_string(), ratatui::style::Color::Green);
+                        } else {
+                            app.popup = Some(Popup::ConfirmUpMissing { services });
+                        }
+                    }
+                    KeyCode::Char('r') => {
+                        if let Err(e) = app.show_compose_ps().await {
+                            app.notify(format!("❌ compose ps failed: {e}"), ratatui::style::Color::Red);
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::ComposeCommand { mut input } => {
+                match k {
+                    KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.popup = Some(match input.trim().is_empty() {
+                            true => Popup::ComposeCommand { input },
+                            false => Popup::SaveBookmark { command: input, input: String::new() },
+                        });
+                    }
+                    KeyCode::Enter => {
+                        app.popup = None;
+                        if let Err(e) = app.run_compose_command(&input) {
+                            app.notify(format!("❌ docker compose failed: {e}"), ratatui::style::Color::Red);
+                        }
+                    }
+                    KeyCode::Char(c) => {
+                        input.push(c);
+                        app.popup = Some(Popup::ComposeCommand { input });
+                    }
+                    KeyCode::Backspace => {
+                        input.pop();
+                        app.popup = Some(Popup::ComposeCommand { input });
+                    }
+                    KeyCode::Esc => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::SaveBookmark { command, mut input } => {
+                match k {
+                    KeyCode::Enter => {
+                        app.save_bookmark(&input, &command);
+                        app.notify(format!("🔖 Saved: {}", if input.trim().is_empty() { command.trim() } else { input.trim() }), ratatui::style::Color::Cyan);
+                        app.popup = None;
+                    }
+                    KeyCode::Char(c) => {
+                        input.push(c);
+                        app.popup = Some(Popup::SaveBookmark { command, input });
+                    }
+                    KeyCode::Backspace => {
+                        input.pop();
+                        app.popup = Some(Popup::SaveBookmark { command, input });
+                    }
+                    KeyCode::Esc => {
+                        app.popup = Some(Popup::ComposeCommand { input: command });
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::Bookmarks { selected } => {
+                let count = app.bookmarks.len();
+                // 1-9 run the Nth bookmark straight away.
+                let run = match k {
+                    KeyCode::Enter => Some(selected),
+                    KeyCode::Char(c @ '1'..='9') => Some(c as usize - '1' as usize),
+                    _ => None,
+                };
+                match k {
+                    _ if run.is_some() => {
+                        if let Some(b) = run.and_then(|i| app.bookmarks.get(i)).cloned() {
+                            app.popup = None;
+                            if let Err(e) = app.run_compose_command(&b.command) {
+                                app.notify(format!("❌ docker compose failed: {e}"), ratatui::style::Color::Red);
+                            }
+                        }
+                    }
+                    KeyCode::Up if count > 0 => {
+                        app.popup = Some(Popup::Bookmarks { selected: if selected == 0 { count - 1 } else { selected - 1 } });
+                    }
+                    KeyCode::Down if count > 0 => {
+                        app.popup = Some(Popup::Bookmarks { selected: (selected + 1) % count });
+                    }
+                    KeyCode::Char('e') => {
+                        if let Some(b) = app.bookmarks.get(selected) {
+                            app.popup = Some(Popup::ComposeCommand { input: b.command.clone() });
+                        }
+                    }
+                    KeyCode::Char('d') if count > 0 => {
+                        app.delete_bookmark(selected);
+                        app.popup = Some(Popup::Bookmarks { selected: selected.min(count.saturating_sub(2)) });
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::GoToContainer { mut input } => {
+                match k {
+                    KeyCode::Enter => {
+                        // `web-1 r`: a trailing single character is an action key.
+                        let (query, action) = match input.trim().rsplit_once(' ') {
+                            Some((q, a)) if a.chars().count() == 1 => (q.trim(), a.chars().next()),
+                            _ => (input.trim(), None),
+                        };
+                        let found = app.goto_matches(query);
+                        match found.as_slice() {
+                            [] => app.notify(format!("❌ No container matches \"{query}\""), ratatui::style::Color::Red),
+                            [(id, name)] => {
+                                app.popup = None;
+                                if !app.reveal_container(id).await {
+                                    app.notify(format!("❌ {name} is not in the list"), ratatui::style::Color::Red);
+                                } else if let Some(c) = action.and_then(|c| app.cfg.keys.dispatch(c)) {
+                                    let _ = crate::ui::handle::actions::handle_action(app, c).await;
+                                }
+                                return false;
+                            }
+                            many => app.notify(
+                                format!("⚠️ {} containers match \"{query}\"; type more of the name or id", many.len()),
+                                ratatui::style::Color::Yellow,
+                            ),
+                        }
+                        app.popup = Some(Popup::GoToContainer { input });
+                    }
+                    KeyCode::Char(c) => {
+                        input.push(c);
+                        app.popup = Some(Popup::GoToContainer { input });
+                    }
+                    KeyCode::Backspace => {
+                        input.pop();
+                        app.popup = Some(Popup::GoToContainer { input });
+                    }
+                    KeyCode::Esc => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::ConfirmCreateEnv { path } => {
+                match k {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        app.popup = None;
+                        match std::fs::write(&path, "") {
+                            Ok(()) => app.pending_foreground = Some(ForegroundJob::EditFile(path)),
+                            Err(e) => app.notify(format!("❌ Cannot create {}: {e}", path.display()), ratatui::style::Color::Red),
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('n') => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::ConfirmComposeDown { project } => {
+                let volumes = match k {
+                    KeyCode::Char('y') | KeyCode::Enter => false,
+                    KeyCode::Char('v') => true,
+                    KeyCode::Esc | KeyCode::Char('n') => {
+                        app.popup = None;
+                        return false;
+                    }
+                    _ => return false,
+                };
+                let action = TypedAction::ComposeDown { project: project.clone(), volumes };
+                if volumes && app.cfg.typed_confirm {
+                    app.popup = Some(Popup::ConfirmTyped { expected: project, input: String::new(), action });
+                    app.confirm_opened = None;
+                } else {
+                    app.popup = None;
+                    run_typed_action(app, action).await;
+                }
+                return false;
+            }
+            Popup::ConfirmReset { id, name, service } => {
+                let recreate = match k {
+                    KeyCode::Char('y') | KeyCode::Enter => false,
+                    KeyCode::Char('u') if service.is_some() => true,
+                    KeyCode::Esc | KeyCode::Char('n') => {
+                        app.popup = None;
+                        return false;
+                    }
+                    _ => return false,
+                };
+                let action = TypedAction::Reset { id, name: name.clone(), service, recreate };
+                if app.cfg.typed_confirm {
+                    app.popup = Some(Popup::ConfirmTyped { expected: name, input: String::new(), action });
+                    app.confirm_opened = None;
+                } else {
+                    app.popup = None;
+                    run_typed_action(app, action).await;
+                }
+                return false;
+            }
+            Popup::ConfirmTyped { expected, mut input, action } => {
+                match k {
+                    KeyCode::Enter if input == expected => {
+                        app.popup = None;
+                        run_typed_action(app, action).await;
+                    }
+                    KeyCode::Enter => {
+                        app.notify(format!("⚠️ Type \"{expected}\" exactly to confirm"), ratatui::style::Color::Yellow);
+                        app.popup = Some(Popup::ConfirmTyped { expected, input, action });
+                    }
+                    KeyCode::Char(c) => {
+                        input.push(c);
+                        app.popup = Some(Popup::ConfirmTyped { expected, input, action });
+                    }
+                    KeyCode::Backspace => {
+                        input.pop();
+                        app.popup = Some(Popup::ConfirmTyped { expected, input, action });
+                    }
+                    KeyCode::Esc => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::ConfirmStackToggle { project, stop } => {
                 match k {
                     KeyCode::Char('y') | KeyCode::Enter => {
                         app.popup = None;
-                        app.push_current_log(&format!("🔥 RESETTING {name} (Stop+Rm+VolRm)..."));
-                        let _ = docker::reset_container(&app.docker, &app.cfg.cwd, &id).await.map(|msgs| {
-                            for m in msgs {
-                                app.push_current_log(&m);
+                        let verb = if stop { "stop" } else { "start" };
+                        app.push_app_log(&format!("⏯ docker compose {verb} ({project})"));
+                        if let Err(e) = app.toggle_stack(stop) {
+                            app.notify(format!("❌ compose failed: {e}"), ratatui::style::Color::Red);
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('n') => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+            Popup::ConfirmUpMissing { services } => {
+                match k {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        app.popup = None;
+                        let profile = app.cfg.compose_profile.clone();
+                        app.push_app_log(&format!("🚀 Starting missing services (profile: {profile}): {}", services.join(", ")));
+                        match docker::compose_up_services(&app.docker, &app.cfg.cwd, &profile, &services).await {
+                            Ok(lines) => {
+                                for l in lines {
+                                    app.push_app_log(&l);
+                                }
+                                app.notify(format!("🚀 Started {} service(s)", services.len()), ratatui::style::Color::Green);
                             }
-                        });
+                            Err(e) => {
+                                app.push_app_log(&format!("❌ Compose up failed: {e}"));
+                                app.notify("❌ Compose up failed (see app log)".to_string(), ratatui::style::Color::Red);
+                            }
+                        }
                         let _ = app.refresh_containers().await;
                         app.rebuild_items();
-                        let _ = app.select(app.selected).await;
                     }
                     KeyCode::Esc | KeyCode::Char('n') => {
                         app.popup = None;
@@ -341,7 +746,7 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                 match k {
                     KeyCode::Char('r') | KeyCode::Enter => {
                         app.popup = None;
-                        app.compose_up_or_restart(infra_running).await;
+                        app.compose_up_or_restart(infra_running);
                     }
                     KeyCode::Char('k') => {
                         app.popup = None;
@@ -358,14 +763,14 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                     KeyCode::Enter => {
                         let replicas: u64 = input.parse().unwrap_or(current);
                         app.popup = None;
-                        app.push_current_log(&format!("⚖️ Scaling service {name} to {replicas}..."));
+                        app.push_app_log(&format!("⚖️ Scaling service {name} to {replicas}..."));
                         if let Err(e) = docker::service_scale(&app.docker, &app.cfg.cwd, &id, replicas as usize).await {
-                            app.push_current_log(&format!("❌ Scaling failed: {e}"));
+                            app.push_app_log(&format!("❌ Scaling failed: {e}"));
                         }
                         let _ = app.refresh_swarm().await;
                         app.rebuild_items();
                     }
-                    KeyCode::Char(c) if c.is_digit(10) => {
+                    KeyCode::Char(c) if c.is_ascii_digit() => {
                         input.push(c);
                         app.popup = Some(Popup::ScaleService { id, name, current, input });
                     }
@@ -391,10 +796,14 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                         app.popup = Some(Popup::ContextSwitch { contexts, selected: new_sel });
                     }
                     KeyCode::Enter => {
-                        let ctx_name = contexts[selected].name.clone();
                         app.popup = None;
-                        app.push_current_log(&format!("🔌 Switching to Docker context: {}...", ctx_name));
-                        let _ = app.switch_context_and_refresh(ctx_name).await;
+                        if let Some(ctx) = contexts.get(selected) {
+                            let ctx_name = ctx.name.clone();
+                            app.push_app_log(&format!("🔌 Switching to Docker context: {}...", ctx_name));
+                            if let Err(e) = app.switch_context_and_refresh(ctx_name).await {
+                                app.notify(format!("❌ Context switch failed: {e}"), ratatui::style::Color::Red);
+                            }
+                        }
                     }
                     KeyCode::Esc => {
                         app.popup = None;
@@ -403,6 +812,29 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                 }
                 return false;
             }
+            Popup::CommandLog { entries, selected } => {
+                match k {
+                    KeyCode::Up => {
+                        let new_sel = selected.saturating_sub(1);
+                        app.popup = Some(Popup::CommandLog { entries, selected: new_sel });
+                    }
+                    KeyCode::Down => {
+                        let new_sel = (selected + 1).min(entries.len().saturating_sub(1));
+                        app.popup = Some(Popup::CommandLog { entries, selected: new_sel });
+                    }
+                    KeyCode::Char('y') => {
+                        if let Some(rec) = entries.get(selected) {
+                            let cmd = rec.command.trim_start_matches("(api) ").to_string();
+                            app.copy_to_clipboard(cmd, "Command copied");
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Enter => {
+                        app.popup = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
             Popup::ImageExplorer { images, selected } => {
                 match k {
                     KeyCode::Up => {
@@ -413,40 +845,47 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
                         let new_sel = if selected + 1 >= images.len() { 0 } else { selected + 1 };
                         app.popup = Some(Popup::ImageExplorer { images, selected: new_sel });
                     }
-                    KeyCode::Char('d') => {
-                        if !images.is_empty() {
-                            let img = &images[selected];
-                            app.push_current_log(&format!("🗑️ Removing image {}...", img.repository));
-                            if let Err(e) = docker::rm_image(&app.docker, &app.cfg.cwd, &img.id, false).await {
-                                app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red);
+                    KeyCode::Char('d') if !images.is_empty() => {
+                        let img = &images[selected];
+                        app.push_app_log(&format!("🗑️ Removing image {}...", img.repository));
+                        if let Err(e) = docker::rm_image(&app.docker, &app.cfg.cwd, &img.id, false).await {
+                            app.notify(format!("❌ Remove failed: {e}"), ratatui::style::Color::Red);
+                        } else {
+                            app.notify(format!("✅ Removed image {}", img.repository), ratatui::style::Color::Green);
+                            if let Ok(new_images) = docker::get_images(&app.docker, &app.cfg.cwd).await {
+                                let new_sel = if selected >= new_images.len() { new_images.len().saturating_sub(1) } else { selected };
+                                app.popup = Some(Popup::ImageExplorer { images: new_images, selected: new_sel });
                             } else {
-                                app.notify(format!("✅ Removed image {}", img.repository), ratatui::style::Color::Green);
-                                if let Ok(new_images) = docker::get_images(&app.docker, &app.cfg.cwd).await {
-                                    let new_sel = if selected >= new_images.len() { new_images.len().saturating_sub(1) } else { selected };
-                                    app.popup = Some(Popup::ImageExplorer { images: new_images, selected: new_sel });
-                                } else {
-                                    app.popup = None;
-                                }
+                                app.popup = None;
                             }
                         }
                     }
-                    KeyCode::Char('D') => {
-                        if !images.is_empty() {
-                            let img = &images[selected];
-                            app.push_current_log(&format!("🗑️ Force removing image {}...", img.repository));
-                            if let Err(e) = docker::rm_image(&app.docker, &app.cfg.cwd, &img.id, true).await {
-                                app.notify(format!("❌ Force remove failed: {e}"), ratatui::style::Color::Red);
+                    KeyCode::Char('D') if !images.is_empty() => {
+                        let img = &images[selected];
+                        app.push_app_log(&format!("🗑️ Force removing image {}...", img.repository));
+                        if let Err(e) = docker::rm_image(&app.docker, &app.cfg.cwd, &img.id, true).await {
+                            app.notify(format!("❌ Force remove failed: {e}"), ratatui::style::Color::Red);
+                        } else {
+                            app.notify(format!("✅ Force removed image {}", img.repository), ratatui::style::Color::Green);
+                            if let Ok(new_images) = docker::get_images(&app.docker, &app.cfg.cwd).await {
+                                let new_sel = if selected >= new_images.len() { new_images.len().saturating_sub(1) } else { selected };
+                                app.popup = Some(Popup::ImageExplorer { images: new_images, selected: new_sel });
                             } else {
-                                app.notify(format!("✅ Force removed image {}", img.repository), ratatui::style::Color::Green);
-                                if let Ok(new_images) = docker::get_images(&app.docker, &app.cfg.cwd).await {
-                                    let new_sel = if selected >= new_images.len() { new_images.len().saturating_sub(1) } else { selected };
-                                    app.popup = Some(Popup::ImageExplorer { images: new_images, selected: new_sel });
-                                } else {
-                                    app.popup = None;
-                                }
+                                app.popup = None;
                             }
                         }
                     }
+                    KeyCode::Char('p') => {
+                        let dangling = images.iter().filter(|i| i.is_dangling()).count();
+                        app.popup = Some(Popup::ConfirmImagePrune { dangling });
+                    }
+                    KeyCode::Enter | KeyCode::Char('i') if !images.is_empty() => {
+                        let id = images[selected].id.clone();
+                        app.popup = None;
+                        if let Err(e) = app.show_image(&id).await {
+                            app.notify(format!("❌ Inspect failed: {e}"), ratatui::style::Color::Red);
+                        }
+                    }
                     KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
                         app.popup = None;
                     }
@@ -467,6 +906,14 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
         return false;
     }
 
+    // h: Recent docker commands
+    if k == KeyCode::Char('h') && app.popup.is_none() && !app.is_filtering {
+        let entries = app.docker.history.snapshot();
+        let selected = entries.len().saturating_sub(1);
+        app.popup = Some(Popup::CommandLog { entries, selected });
+        return false;
+    }
+
     // V: Volumes Explorer
     if k == KeyCode::Char('V') && modifiers.contains(KeyModifiers::SHIFT) && app.popup.is_none() && !app.is_filtering {
         if let Ok(volumes) = docker::get_volumes(&app.docker, &app.cfg.cwd).await {
@@ -593,6 +1040,7 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
         app.follow_mode = !app.follow_mode;
         if app.follow_mode {
             app.stick_to_bottom = true;
+            app.log_match = None;
             app.notify("▶️ Follow mode: ON".to_string(), ratatui::style::Color::Green);
         } else {
             app.stick_to_bottom = false;
@@ -601,28 +1049,206 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
         return false;
     }
 
+    // t: follow container logs with or without timestamps (logs focus; Start in the list)
+    if k == KeyCode::Char('t') && !modifiers.contains(KeyModifiers::CONTROL) && !app.focus_on_list && app.popup.is_none() && !app.is_filtering_logs {
+        if let Err(e) = app.toggle_log_timestamps().await {
+            app.notify(format!("❌ Failed to restart logs: {e}"), ratatui::style::Color::Red);
+        }
+        return false;
+    }
+
+    // +/-: grow or shrink the tail of the followed logs (logs focus)
+    if matches!(k, KeyCode::Char('+') | KeyCode::Char('-'))
+        && !app.focus_on_list && app.popup.is_none() && !app.is_filtering_logs {
+        if let Err(e) = app.adjust_log_tail(k == KeyCode::Char('+')).await {
+            app.notify(format!("❌ Failed to restart logs: {e}"), ratatui::style::Color::Red);
+        }
+        return false;
+    }
+
     // Space: toggle group collapse
-    if k == KeyCode::Char(' ') && app.focus_on_list {
-        if !app.items.is_empty() {
-            let item = app.items[app.selected].clone();
-            if item.kind == SidebarKind::GroupHeader {
-                let project = item.id.clone();
-                app.toggle_group_collapse(&project);
-                app.rebuild_items();
-                return false;
-            }
+    if k == KeyCode::Char(' ') && app.focus_on_list && !app.items.is_empty() {
+        let item = app.items[app.selected].clone();
+        if item.kind == SidebarKind::GroupHeader {
+            let project = item.id.clone();
+            app.toggle_group_collapse(&project);
+            app.rebuild_items();
+            return false;
         }
     }
 
+    // Space in logs focus: same as z, since it has nothing else to do there
+    if k == KeyCode::Char(' ') && !app.focus_on_list && app.popup.is_none() && !app.is_filtering_logs {
+        app.toggle_refresh_pause();
+        return false;
+    }
+
     // L: Multi-container log streaming
-    if k == KeyCode::Char('L') && app.focus_on_list {
-            if !app.items.is_empty() {
-            let item = app.items[app.selected].clone();
-            if item.kind == SidebarKind::GroupHeader && !item.id.starts_with("stack:") && item.id != "__pins__" {
-                let _ = app.start_compose_logs(item.id.clone());
-                return false;
+    if k == KeyCode::Char('L') && app.focus_on_list && !app.items.is_empty() {
+        let item = app.items[app.selected].clone();
+        if item.is_compose_project() {
+            let _ = app.start_compose_logs(item.id.clone());
+            return false;
+        }
+    }
+
+    // W: Watch daemon events for the compose project under the cursor
+    if k == KeyCode::Char('W') && app.focus_on_list && app.popup.is_none() && !app.items.is_empty() {
+        let item = app.items[app.selected].clone();
+        let project = match item.kind {
+            SidebarKind::GroupHeader if item.is_compose_project() && item.id != crate::ui::app::STANDALONE_GROUP => Some(item.id.clone()),
+            SidebarKind::Container => app.containers.iter()
+                .find(|(c, _)| c.id == item.id)
+                .and_then(|(c, _)| c.compose_project.clone()),
+            _ => None,
+        };
+        match project {
+            Some(p) => {
+                if let Err(e) = app.start_compose_events(p) {
+                    app.notify(format!("❌ Events failed: {e}"), ratatui::style::Color::Red);
+                }
             }
+            None => app.notify("⚠️ Not part of a compose project".to_string(), ratatui::style::Color::Yellow),
+        }
+        return false;
+    }
+
+    // M: cycle the stat columns (CPU/mem, net, block IO, all)
+    if k == KeyCode::Char('M') && app.popup.is_none() && !app.is_filtering {
+        app.stats_view = app.stats_view.next();
+        app.rebuild_items();
+        app.notify(format!("📊 Stats: {}", app.stats_view.label()), ratatui::style::Color::Cyan);
+        return false;
+    }
+
+    // @ — go to a container by name or id, even if filtered out or collapsed
+    if k == KeyCode::Char('@') && app.popup.is_none() && !app.is_filtering && !app.containers.is_empty() {
+        app.popup = Some(Popup::GoToContainer { input: String::new() });
+        return false;
+    }
+
+    // ':' — run any docker compose subcommand
+    if k == KeyCode::Char(':') && app.popup.is_none() && !app.is_filtering && app.docker.available {
+        app.popup = Some(Popup::ComposeCommand { input: String::new() });
+        return false;
+    }
+
+    // ';' — saved compose command lines
+    if k == KeyCode::Char(';') && app.popup.is_none() && !app.is_filtering && app.docker.available {
+        if app.bookmarks.is_empty() {
+            app.notify("🔖 No bookmarks yet: type a command after ':' and press Ctrl+S".to_string(), ratatui::style::Color::Yellow);
+        } else {
+            app.popup = Some(Popup::Bookmarks { selected: 0 });
         }
+        return false;
+    }
+
+    // T: tee the followed logs into a file
+    if k == KeyCode::Char('T') && app.popup.is_none() && !app.is_filtering {
+        if let Err(e) = app.toggle_log_recording() {
+            app.notify(format!("❌ Cannot record: {e}"), ratatui::style::Color::Red);
+        }
+        return false;
+    }
+
+    // F1..F12: QUICK_OPEN favourites
+    if let KeyCode::F(n @ 1..=12) = k {
+        if app.popup.is_none() && !app.is_filtering && !app.shell_active {
+            app.quick_open(n as usize - 1);
+            return false;
+        }
+    }
+
+    // A: compose's view of every service, including those with no container
+    if k == KeyCode::Char('A') && app.popup.is_none() && !app.is_filtering && app.docker.available {
+        if let Err(e) = app.show_compose_ps().await {
+            app.notify(format!("❌ compose ps failed: {e}"), ratatui::style::Color::Red);
+        }
+        return false;
+    }
+
+    // U: compose up only the services that have no running container
+    if k == KeyCode::Char('U') && app.popup.is_none() && !app.is_filtering && app.docker.available {
+        match app.missing_compose_services().await {
+            Ok(services) if services.is_empty() => {
+                app.notify("✅ Every compose service is already running".to_string(), ratatui::style::Color::Green);
+            }
+            Ok(services) => app.popup = Some(Popup::ConfirmUpMissing { services }),
+            Err(e) => app.notify(format!("❌ Cannot read compose config: {e}"), ratatui::style::Color::Red),
+        }
+        return false;
+    }
+
+    // Z: stop / start the whole stack, keeping containers and volumes
+    if k == KeyCode::Char('Z') && app.popup.is_none() && !app.is_filtering && app.docker.available {
+        match app.stack_running().await {
+            Ok((project, running)) => app.popup = Some(Popup::ConfirmStackToggle { project, stop: running }),
+            Err(e) => app.notify(format!("❌ Cannot read compose config: {e}"), ratatui::style::Color::Red),
+        }
+        return false;
+    }
+
+    // z: freeze / resume the auto-refresh
+    if k == KeyCode::Char('z') && app.popup.is_none() && !app.is_filtering {
+        app.toggle_refresh_pause();
+        return false;
+    }
+
+    // Y: cycle the task log between both streams, stderr only and stdout only
+    if k == KeyCode::Char('Y') && app.popup.is_none() && !app.is_filtering {
+        app.task_stream = app.task_stream.next();
+        app.log_match = None;
+        app.notify(format!("🔀 Task output: {}", app.task_stream.label()), ratatui::style::Color::Cyan);
+        return false;
+    }
+
+    // a: show / hide the container age column
+    if k == KeyCode::Char('a') && app.popup.is_none() && !app.is_filtering {
+        app.show_age = !app.show_age;
+        app.rebuild_items();
+        return false;
+    }
+
+    // w: show / hide stopped containers
+    if k == KeyCode::Char('w') && app.popup.is_none() && !app.is_filtering {
+        app.toggle_show_stopped().await;
+        let hidden = app.containers.iter().filter(|(c, _)| crate::ui::app::is_stopped(c)).count();
+        if app.show_stopped {
+            app.notify("👁 Showing stopped containers".to_string(), ratatui::style::Color::Cyan);
+        } else {
+            app.notify(format!("🙈 Hiding {hidden} stopped container(s)"), ratatui::style::Color::Cyan);
+        }
+        return false;
+    }
+
+    // Ctrl+R: refresh now, even while paused
+    if k == KeyCode::Char('r') && modifiers.contains(KeyModifiers::CONTROL) && app.popup.is_none() && !app.is_filtering && app.docker.available {
+        app.refresh_requested = true;
+        return false;
+    }
+
+    // Ctrl+T: release the mouse to the terminal (native selection) or take it back
+    if k == KeyCode::Char('t') && modifiers.contains(KeyModifiers::CONTROL) && app.popup.is_none() && !app.is_filtering {
+        app.mouse_capture = !app.mouse_capture;
+        if app.mouse_capture {
+            crossterm::execute!(io::stdout(), EnableMouseCapture).ok();
+            app.notify("🖱 Mouse capture on".to_string(), ratatui::style::Color::Cyan);
+        } else {
+            crossterm::execute!(io::stdout(), DisableMouseCapture).ok();
+            app.notify("🖱 Mouse capture off — select text with the mouse, Ctrl+T to restore".to_string(), ratatui::style::Color::Cyan);
+        }
+        return false;
+    }
+
+    // Ctrl+E: edit the project's .env in $EDITOR, then reload it
+    if k == KeyCode::Char('e') && modifiers.contains(KeyModifiers::CONTROL) && app.popup.is_none() && !app.is_filtering {
+        let path = crate::env::editable_env_file(&app.cfg.cwd, &app.cfg.profile);
+        if path.exists() {
+            app.pending_foreground = Some(ForegroundJob::EditFile(path));
+        } else {
+            app.popup = Some(Popup::ConfirmCreateEnv { path });
+        }
+        return false;
     }
 
     // E: Export logs
@@ -636,3 +1262,76 @@ pub async fn handle_shortcut(app: &mut App, k: KeyCode, modifiers: KeyModifiers)
 
     false
 }
+
+/// Run a destructive action once it is confirmed (typed or not).
+async fn run_typed_action(app: &mut App, action: TypedAction) {
+    match action {
+        TypedAction::Prune => {
+            app.push_app_log("🧹 Pruning system...");
+            app.stats_refreshing = true;
+            let _ = app.trigger_prune().await;
+        }
+        TypedAction::Reset { id, name, service, recreate } => {
+            app.push_app_log(&format!("🔥 RESETTING {name} (Stop+Rm+VolRm)..."));
+            let _ = docker::reset_container(&app.docker, &app.cfg.cwd, &id).await.map(|msgs| {
+                for m in msgs {
+                    app.push_app_log(&m);
+                }
+            });
+            if !recreate {
+                let recovery = app.recovery_command(std::slice::from_ref(&id), true)
+                    .map(|cmd| format!("{cmd} (volumes are gone: it starts empty)"));
+                app.show_recovery(&format!("🔥 Reset {name}"), recovery);
+            }
+            if let (true, Some((project, service))) = (recreate, service) {
+                app.push_app_log(&format!("🚀 docker compose -p {project} up -d {service}..."));
+                match docker::compose_service_up(&app.docker, &app.cfg.cwd, &project, &service).await {
+                    Ok(lines) => {
+                        for l in lines {
+                            app.push_app_log(&l);
+                        }
+                    }
+                    Err(e) => app.push_app_log(&format!("❌ compose up failed: {e}")),
+                }
+            }
+            let _ = app.refresh_containers().await;
+            app.rebuild_items();
+            let _ = app.select(app.selected).await;
+        }
+        TypedAction::ComposeDown { project, volumes } => {
+            let flag = if volumes { " -v" } else { "" };
+            app.push_app_log(&format!("🛑 docker compose -p {project} down{flag}..."));
+            let mut args = ["-p", project.as_str(), "down"].map(String::from).to_vec();
+            if volumes {
+                args.push("-v".to_string());
+            }
+            if let Err(e) = app.start_compose_job(args, false) {
+                app.push_app_log(&format!("❌ docker compose failed to start: {e}"));
+            } else if let Some(job) = app.compose_job.as_mut() {
+                let recovery = format!("docker compose -p {project} up -d");
+                let recovery = if volumes { format!("{recovery} (volumes are gone: it starts empty)") } else { recovery };
+                job.recovery = Some((format!("🛑 {project} is down"), recovery));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_refresh_and_quick_open_keys() {
+        let mut cfg = crate::config::offline_config(std::env::temp_dir().join("docker-cli-shortcuts"));
+        cfg.quick_open = (1..=12).map(|n| (format!("svc{n}"), 80)).collect();
+        let mut app = App::new(cfg, docker::DockerMeta::offline("docker"));
+
+        assert!(!handle_shortcut(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL).await);
+        assert!(app.refresh_requested);
+
+        app.refresh_requested = false;
+        handle_shortcut(&mut app, KeyCode::F(5), KeyModifiers::NONE).await;
+        assert!(!app.refresh_requested);
+        assert_eq!(app.toast.as_ref().map(|t| t.0.as_str()), Some("❌ Quick open: no container named svc5"));
+    }
+}
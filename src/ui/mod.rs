@@ -7,7 +7,7 @@ pub use app::{App, RunOpts};
 
 use crate::docker;
 use crate::env;
-use crate::config::{get_config};
+use crate::config::{default_profile, discover_profiles, get_config};
 use crate::ui::draw::draw_ui;
 use crate::ui::handle::handle_event;
 use anyhow::Result;
@@ -31,17 +31,57 @@ pub async fn run(opts: RunOpts) -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let chosen = std::env::var("DOCKER_PROFILE")
+    let explicit = std::env::var("DOCKER_PROFILE")
         .ok()
-        .or_else(|| std::env::var("COMPOSE_PROFILE").ok())
-        .unwrap_or_else(|| "local".to_string());
+        .or_else(|| std::env::var("COMPOSE_PROFILE").ok());
+    let mut auto_note = None;
+    let chosen = match explicit {
+        Some(p) => p,
+        None => {
+            let available = docker::list_compose_profiles(&opts.docker_meta, &opts.root).await;
+            let found = discover_profiles(&opts.root, &available);
+            match found.as_slice() {
+                [_, _, ..] => pick_profile(&mut terminal, &found, &default_profile(&available))?,
+                [(only, _)] => only.clone(),
+                [] => {
+                    let picked = default_profile(&available);
+                    if picked != "local" {
+                        auto_note = Some(format!(
+                            "Profile 'local' not declared in compose file (available: {}) → using '{picked}'",
+                            available.join(", ")
+                        ));
+                    }
+                    picked
+                }
+            }
+        }
+    };
 
     std::env::set_var("DOCKER_PROFILE", &chosen);
     std::env::set_var("COMPOSE_PROFILES", &chosen);
-    env::load_env(&opts.root, Some(&chosen))?;
+    let loaded = env::load_env(&opts.root, Some(&chosen))?;
 
     let cfg = get_config(&chosen);
-    let mut app = App::new(cfg, opts.docker_meta);
+    let docker_meta = if cfg.docker_bin != opts.docker_meta.docker_bin {
+        docker::DockerMeta::detect_context(&opts.root, &cfg.docker_bin, None, opts.docker_meta.history.clone()).await
+    } else {
+        opts.docker_meta
+    };
+    let mut app = App::new(cfg, docker_meta);
+    app.env_files = loaded.files;
+    app.apply_compose_files();
+    app.report_key_conflicts();
+    if !app.mouse_capture {
+        io::stdout().execute(DisableMouseCapture)?;
+    }
+    if let Some(note) = auto_note {
+        app.push_app_log(&note);
+    }
+    // Both loads report a broken docker-cli.toml.
+    let mut seen = std::collections::HashSet::new();
+    for w in opts.env_warnings.iter().chain(&loaded.warnings).filter(|w| seen.insert(*w)) {
+        app.push_app_log(&format!("⚠️ env: {w}"));
+    }
 
     if app.docker.available {
         let _ = app.refresh_containers().await;
@@ -52,7 +92,7 @@ pub async fn run(opts: RunOpts) -> Result<()> {
         let screenshot_file = std::fs::read_to_string("screenshot_backdoor.txt").is_ok();
         let screenshot_env = std::env::var("SCREENSHOT_VIEW").is_ok();
         
-        if app.cfg.auto_compose_up && !screenshot_file && !screenshot_env {
+        if app.cfg.auto_compose_up && app.cfg.compose_prompt && !screenshot_file && !screenshot_env {
             let infra_running = app.infra_already_up();
             app.popup = Some(types::Popup::ConfirmComposeRestart { infra_running });
         }
@@ -92,25 +132,39 @@ pub async fn run(opts: RunOpts) -> Result<()> {
 
 
     let mut stats_ticker = time::interval(Duration::from_secs(2));
+    let mut list_stats_ticker = time::interval(Duration::from_secs(5));
     let (tx_refresh, mut rx_refresh) = mpsc::unbounded_channel();
     let (tx_swarm, mut rx_swarm) = mpsc::unbounded_channel::<Vec<docker::SwarmService>>();
     let (tx_stats, mut rx_stats) = mpsc::unbounded_channel::<Option<docker::ContainerStats>>();
+    let (tx_list_stats, mut rx_list_stats) = mpsc::unbounded_channel::<std::collections::HashMap<String, docker::StatsSample>>();
 
     loop {
         terminal.draw(|f| draw_ui(f, &mut app))?;
 
         app.pump_background().await;
 
+        if std::mem::take(&mut app.refresh_requested) {
+            app.stale_refresh = app.refreshing;
+            match app.refresh_now().await {
+                Ok(()) => app.notify("🔄 Refreshed".to_string(), ratatui::style::Color::Cyan),
+                Err(e) => app.notify(format!("❌ Refresh failed: {e}"), ratatui::style::Color::Red),
+            }
+            continue;
+        }
+
         tokio::select! {
             _ = ticker.tick() => {
-                if app.docker.available && app.popup.is_none() {
+                app.run_due_tasks().await;
+                app.expire_confirm();
+                if app.docker.available && app.popup.is_none() && !app.refresh_paused && app.refresh_due() {
                     if !app.refreshing {
                         app.refreshing = true;
                         let tx = tx_refresh.clone();
                         let docker = app.docker.clone();
                         let cwd = app.cfg.cwd.clone();
+                        let previous = app.containers.clone();
                         tokio::spawn(async move {
-                            let res = docker::list_containers_all(&docker, &cwd).await;
+                            let res = docker::list_containers_all(&docker, &cwd, &previous).await;
                             let _ = tx.send(res);
                         });
                     }
@@ -127,7 +181,7 @@ pub async fn run(opts: RunOpts) -> Result<()> {
                 }
             }
             _ = stats_ticker.tick() => {
-                if app.docker.available && !app.items.is_empty() && !app.stats_refreshing {
+                if app.docker.available && !app.items.is_empty() && !app.stats_refreshing && !app.refresh_paused {
                     let item = app.items[app.selected].clone();
                     if item.kind == types::SidebarKind::Container {
                         app.stats_refreshing = true;
@@ -142,10 +196,40 @@ pub async fn run(opts: RunOpts) -> Result<()> {
                     }
                 }
             }
+            _ = list_stats_ticker.tick() => {
+                if !app.cfg.list_stats && !app.list_stats.is_empty() {
+                    app.list_stats.clear();
+                    app.rebuild_items();
+                }
+                if app.cfg.list_stats && app.docker.available && !app.list_stats_refreshing && !app.refresh_paused {
+                    let running: Vec<String> = app.containers.iter()
+                        .filter(|(c, _)| c.state.eq_ignore_ascii_case("running"))
+                        .map(|(c, _)| c.id.clone())
+                        .collect();
+                    if !running.is_empty() {
+                        app.list_stats_refreshing = true;
+                        let tx = tx_list_stats.clone();
+                        let docker = app.docker.clone();
+                        let cwd = app.cfg.cwd.clone();
+                        tokio::spawn(async move {
+                            let _ = tx.send(docker::fetch_all_stats(&docker, &cwd, &running).await);
+                        });
+                    }
+                }
+            }
+            Some(stats) = rx_list_stats.recv() => {
+                app.list_stats_refreshing = false;
+                app.list_stats = stats;
+                app.rebuild_items();
+            }
             Some(res) = rx_refresh.recv() => {
                 app.refreshing = false;
+                if std::mem::take(&mut app.stale_refresh) {
+                    continue;
+                }
                 if let Ok(containers) = res {
                     app.containers = containers;
+                    app.track_restarts();
                     app.rebuild_items();
                 }
             }
@@ -172,9 +256,57 @@ pub async fn run(opts: RunOpts) -> Result<()> {
                 app.rebuild_items();
             }
             ev = read_event() => {
+                if let Some(Event::Resize(..)) = ev {
+                    // Snap to the new size right away: the draw at the top of the
+                    // loop recomputes `last_log_height` and clamps the log scroll.
+                    terminal.autoresize()?;
+                    continue;
+                }
                 if let Some(ev) = ev {
                     let should_quit = handle_event(&mut app, ev).await?;
                     if should_quit { break; }
+                    match app.pending_foreground.take() {
+                        Some(types::ForegroundJob::EditFile(path)) => {
+                            match edit_file(&mut terminal, &path, app.mouse_capture) {
+                                Ok(()) => {
+                                    if let Err(e) = app.reload_env().await {
+                                        app.notify(format!("❌ Reload failed: {e}"), ratatui::style::Color::Red);
+                                    }
+                                }
+                                Err(e) => app.notify(format!("❌ Editor failed: {e}"), ratatui::style::Color::Red),
+                            }
+                        }
+                        Some(types::ForegroundJob::Compose(args)) => {
+                            suspend_tui()?;
+                            let argv: Vec<&str> = args.iter().map(String::as_str).collect();
+                            let res = docker::docker_compose(&app.docker, &app.cfg.cwd, &app.cfg.compose_profile, &argv).await;
+                            wait_for_enter(&res);
+                            resume_tui(&mut terminal, app.mouse_capture)?;
+                            match res {
+                                Ok(code) => app.push_app_log(&format!("$ docker compose {} → exit {code}", args.join(" "))),
+                                Err(e) => app.notify(format!("❌ docker compose failed: {e}"), ratatui::style::Color::Red),
+                            }
+                        }
+                        Some(types::ForegroundJob::Exec { id, name }) => {
+                            suspend_tui()?;
+                            let args = docker::exec_shell_args(&id, &app.cfg.exec_shell);
+                            let res = docker::docker_foreground(&app.docker, &app.cfg.cwd, &args).await;
+                            // A clean `exit` goes straight back; anything else stays on
+                            // screen until Enter so the error can be read.
+                            if !matches!(res, Ok(0)) {
+                                wait_for_enter(&res);
+                            }
+                            resume_tui(&mut terminal, app.mouse_capture)?;
+                            match res {
+                                Ok(code) => app.push_app_log(&format!("$ docker exec {name} → exit {code}")),
+                                Err(e) => app.notify(format!("❌ docker exec failed: {e}"), ratatui::style::Color::Red),
+                            }
+                            if let Err(e) = app.refresh_now().await {
+                                app.push_app_log(&format!("❌ Refresh failed: {e}"));
+                            }
+                        }
+                        None => {}
+                    }
                 }
             }
         }
@@ -187,6 +319,102 @@ pub async fn run(opts: RunOpts) -> Result<()> {
     Ok(())
 }
 
+/// Hand the terminal to `$VISUAL`/`$EDITOR` (falling back to `vi`, or
+/// `notepad` on Windows) and take it back once the editor exits.
+fn edit_file(terminal: &mut Terminal<CrosstermBackend<Stdout>>, path: &std::path::Path, mouse_capture: bool) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    let mut parts = editor.split_whitespace();
+    let bin = parts.next().unwrap_or("vi");
+
+    suspend_tui()?;
+    let status = tokio::task::block_in_place(|| {
+        std::process::Command::new(bin).args(parts).arg(path).status()
+    });
+    resume_tui(terminal, mouse_capture)?;
+
+    let status = status.map_err(|e| anyhow::anyhow!("{bin}: {e}"))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("{bin} exited with {status}"));
+    }
+    Ok(())
+}
+
+/// Give the terminal back to a foreground program.
+fn suspend_tui() -> Result<()> {
+    disable_raw_mode()?;
+    io::stdout().execute(DisableMouseCapture)?;
+    io::stdout().execute(LeaveAlternateScreen)?;
+    Ok(())
+}
+
+fn resume_tui(terminal: &mut Terminal<CrosstermBackend<Stdout>>, mouse_capture: bool) -> Result<()> {
+    io::stdout().execute(EnterAlternateScreen)?;
+    if mouse_capture {
+        io::stdout().execute(EnableMouseCapture)?;
+    }
+    enable_raw_mode()?;
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Startup list of the discovered profiles, before the main UI exists.
+/// Enter picks the highlighted one, Esc the default; Ctrl+C quits.
+fn pick_profile(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    profiles: &[(String, Vec<String>)],
+    default: &str,
+) -> Result<String> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+
+    let support = crate::config::ColorSupport::detect();
+    let mut state = ListState::default();
+    state.select(Some(profiles.iter().position(|(p, _)| p == default).unwrap_or(0)));
+    loop {
+        terminal.draw(|f| {
+            let area = draw::utils::centered_rect(60, 50, f.area());
+            let items: Vec<ListItem> = profiles.iter()
+                .map(|(p, sources)| {
+                    let mark = if p == default { "  (default)" } else { "" };
+                    ListItem::new(format!("{p}{mark}  ·  {}", sources.join(", ")))
+                })
+                .collect();
+            let list = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(" Profile  [↑/↓] Select  [Enter] Use  [Esc] Default "))
+                .highlight_style(Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD));
+            f.render_stateful_widget(list, area, &mut state);
+            draw::utils::degrade_colors(f.buffer_mut(), support);
+        })?;
+        let Event::Key(key) = event::read()? else { continue };
+        let selected = state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down | KeyCode::Char('j') => state.select(Some((selected + 1).min(profiles.len() - 1))),
+            KeyCode::Enter => return Ok(profiles[selected].0.clone()),
+            KeyCode::Esc => return Ok(default.to_string()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                anyhow::bail!("no profile chosen")
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Keep a finished foreground command's output on screen until Enter.
+fn wait_for_enter(res: &Result<i32>) {
+    match res {
+        Ok(code) => println!("\n[exit {code}] Press Enter to return…"),
+        Err(e) => println!("\n[error: {e}] Press Enter to return…"),
+    }
+    let mut line = String::new();
+    let _ = tokio::task::block_in_place(|| io::stdin().read_line(&mut line));
+}
+
 async fn read_event() -> Option<Event> {
     if event::poll(Duration::from_millis(50)).ok()? {
         event::read().ok()
@@ -7,6 +7,10 @@ use tokio::sync::mpsc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SidebarKind {
+    /// The tool's own message log, pinned at the top of the list.
+    AppLog,
+    /// `docker compose logs -f` of the whole stack, under the app log.
+    StackLogs,
     Task,
     GroupHeader,
     Container,
@@ -25,27 +29,276 @@ pub struct UiItem {
     pub depth: usize,
 }
 
+impl UiItem {
+    /// Header of a compose project group, as opposed to pins, swarm stacks or
+    /// the per-service headers nested inside a project.
+    pub fn is_compose_project(&self) -> bool {
+        self.kind == SidebarKind::GroupHeader
+            && self.id != "__pins__"
+            && !self.id.starts_with("stack:")
+            && !self.id.starts_with("svc:")
+    }
+}
+
+/// Work that needs the real terminal; the main loop suspends the TUI for it.
+pub enum ForegroundJob {
+    EditFile(std::path::PathBuf),
+    Compose(Vec<String>),
+    /// `docker exec -it` into a running container.
+    Exec { id: String, name: String },
+}
+
+/// Which of a task's streams the log pane shows; cycled with `Y`. Lines
+/// with neither tag (run headers, exit status) are always shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaskStream {
+    #[default]
+    Both,
+    Err,
+    Out,
+}
+
+impl TaskStream {
+    pub fn next(self) -> Self {
+        match self {
+            TaskStream::Both => TaskStream::Err,
+            TaskStream::Err => TaskStream::Out,
+            TaskStream::Out => TaskStream::Both,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskStream::Both => "stdout + stderr",
+            TaskStream::Err => "stderr only",
+            TaskStream::Out => "stdout only",
+        }
+    }
+
+    pub fn shows(self, line: &str) -> bool {
+        match stream_tag(line) {
+            Some((stream, _)) => self == TaskStream::Both || self == stream,
+            None => true,
+        }
+    }
+}
+
+/// The `[OUT] ` / `[ERR] ` tag of a task line, at its start or right after
+/// the `[task] ` prefix: the stream it names and its byte range.
+pub fn stream_tag(line: &str) -> Option<(TaskStream, std::ops::Range<usize>)> {
+    let tag_at = |start: usize| {
+        let rest = line.get(start..)?;
+        let stream = if rest.starts_with("[OUT] ") {
+            TaskStream::Out
+        } else if rest.starts_with("[ERR] ") {
+            TaskStream::Err
+        } else {
+            return None;
+        };
+        Some((stream, start..start + 6))
+    };
+    tag_at(0).or_else(|| line.starts_with('[').then(|| line.find("] ")).flatten().and_then(|end| tag_at(end + 2)))
+}
+
+/// `text` with the stream tag of each line removed: container logs as
+/// Docker printed them, for copies, exports and recordings.
+pub fn strip_stream_tags(text: &str) -> std::borrow::Cow<'_, str> {
+    if !text.contains("[OUT] ") && !text.contains("[ERR] ") {
+        return text.into();
+    }
+    text.split_inclusive('\n')
+        .map(|line| match stream_tag(line) {
+            Some((_, range)) => format!("{}{}", &line[..range.start], &line[range.end..]),
+            None => line.to_string(),
+        })
+        .collect::<String>()
+        .into()
+}
+
+/// `compose up -d`, `restart` or `down` running in the background. Its output
+/// reaches the log pane while `target` is on screen; the last line
+/// (`==> OK`, `==> FAIL (exit N)`) ends the job.
+pub struct ComposeJob {
+    pub args: Vec<String>,
+    pub target: String,
+    pub started: std::time::Instant,
+    /// Run `up -d` when this one fails (a `restart` with nothing to restart).
+    pub fallback_up: bool,
+    /// Undo hint (`show_recovery` title and command) once the job succeeds.
+    pub recovery: Option<(String, String)>,
+    pub rx: tokio::sync::mpsc::UnboundedReceiver<String>,
+    pub _child: crate::docker::LogStream,
+}
+
+/// Live "tee" of the followed stream into a file, toggled with `T`.
+pub struct LogRecording {
+    pub target: String,
+    pub file_name: String,
+    pub file: std::fs::File,
+    pub bytes: usize,
+}
+
 pub struct TaskRuntime {
     pub spec: TaskSpec,
     pub status: TaskStatus,
     pub lines: VecDeque<String>,
+    /// Bytes held by `lines`, kept in step by [`TaskRuntime::push_line`].
+    pub line_bytes: usize,
     pub child: Option<Child>,
     pub rx: Option<mpsc::UnboundedReceiver<String>>,
+    /// Next scheduled run for tasks with `every`; `None` when the schedule is
+    /// off (never started, or stopped by the user).
+    pub next_run: Option<std::time::Instant>,
+    /// Lines received per pump over the last `RATE_WINDOW`.
+    pub recent_output: VecDeque<(std::time::Instant, usize)>,
+    /// Last line received, or the start of the run before any output.
+    pub last_output: Option<std::time::Instant>,
+    /// Last time the log pane showed this task; the stalest tasks lose their
+    /// oldest lines first when `LOG_MEM_BUDGET_MB` is exceeded.
+    pub last_viewed: Option<std::time::Instant>,
+}
+
+/// Window over which a task's output rate is averaged.
+pub const RATE_WINDOW: std::time::Duration = std::time::Duration::from_secs(5);
+/// A running task silent for this long is flagged as idle in the sidebar.
+pub const IDLE_AFTER: std::time::Duration = std::time::Duration::from_secs(60);
+
+impl TaskRuntime {
+    pub fn new(spec: TaskSpec) -> Self {
+        Self {
+            spec,
+            status: TaskStatus::Pending,
+            lines: VecDeque::new(),
+            line_bytes: 0,
+            child: None,
+            rx: None,
+            next_run: None,
+            recent_output: VecDeque::new(),
+            last_output: None,
+            last_viewed: None,
+        }
+    }
+
+    /// Append `line`, then drop the oldest lines until at most `max_lines`
+    /// and `max_bytes` (0 for no byte limit) are held. The newest line is
+    /// always kept.
+    pub fn push_line(&mut self, line: String, max_lines: usize, max_bytes: usize) {
+        self.line_bytes += line.len();
+        self.lines.push_back(line);
+        while self.lines.len() > max_lines || (max_bytes > 0 && self.line_bytes > max_bytes && self.lines.len() > 1) {
+            if self.pop_line().is_none() {
+                break;
+            }
+        }
+    }
+
+    pub fn pop_line(&mut self) -> Option<String> {
+        let line = self.lines.pop_front()?;
+        self.line_bytes -= line.len();
+        Some(line)
+    }
+
+    pub fn clear_lines(&mut self) {
+        self.lines.clear();
+        self.line_bytes = 0;
+    }
+
+    pub fn note_output(&mut self, at: std::time::Instant, lines: usize) {
+        self.recent_output.push_back((at, lines));
+        self.last_output = Some(at);
+        while self.recent_output.front().is_some_and(|(t, _)| at.duration_since(*t) > RATE_WINDOW) {
+            self.recent_output.pop_front();
+        }
+    }
+
+    /// Lines per second over the last `RATE_WINDOW`.
+    pub fn line_rate(&self) -> f64 {
+        let n: usize = self.recent_output.iter()
+            .filter(|(t, _)| t.elapsed() <= RATE_WINDOW)
+            .map(|(_, n)| n)
+            .sum();
+        n as f64 / RATE_WINDOW.as_secs_f64()
+    }
 }
 
 #[derive(Clone)]
 pub enum Popup {
-    Inspect { id: String, name: String, json: serde_json::Value, tab: usize },
-    ConfirmReset { id: String, name: String },
+    /// `scroll_x` is the horizontal offset in characters, ignored while `wrap` is on.
+    Inspect { id: String, name: String, json: serde_json::Value, tab: usize, cursor: usize, scroll_x: usize, wrap: bool },
+    /// `service` is `(project, service)` from the compose labels, when the
+    /// container can be brought back with `compose up`.
+    ConfirmReset { id: String, name: String, service: Option<(String, String)> },
     ConfirmBulkRemove { ids: Vec<String> },
     ConfirmComposeRestart { infra_running: bool },
+    /// `D` on a compose project header: `down`, with or without `-v`.
+    ConfirmComposeDown { project: String },
+    /// Compose services with no running container, about to be started.
+    ConfirmUpMissing { services: Vec<String> },
+    /// Stop (`stop: true`) or start the whole compose stack, keeping containers.
+    ConfirmStackToggle { project: String, stop: bool },
     ScaleService { id: String, name: String, current: u64, input: String },
     Volumes { volumes: Vec<crate::docker::DockerVolume>, selected: usize },
+    /// `d` / `D` (`force`) in the volumes explorer, which reopens afterwards.
+    ConfirmVolumeRemove { name: String, force: bool, selected: usize },
     Networks { networks: Vec<crate::docker::DockerNetwork>, selected: usize },
+    /// `compose ps` for the profile, including defined services with no container.
+    ComposePs { rows: Vec<crate::docker::ComposePsRow>, selected: usize },
     ContextSwitch { contexts: Vec<crate::docker::DockerContext>, selected: usize },
     SystemHealth { data: Vec<docker::SystemDfRow> },
     ImageExplorer { images: Vec<crate::docker::DockerImage>, selected: usize },
+    /// `p` in the image explorer: `docker image prune -f`.
+    ConfirmImagePrune { dangling: usize },
     FileExplorer { id: String, name: String, path: String, files: Vec<(String, bool)>, selected: usize },
+    CommandLog { entries: Vec<crate::docker::CommandRecord>, selected: usize },
     ConfirmPrune,
-    Help,
+    /// Second step of a destructive action under `TYPED_CONFIRM`: it only
+    /// runs once `input` equals `expected`.
+    ConfirmTyped { expected: String, input: String, action: TypedAction },
+    ConfirmCreateEnv { path: std::path::PathBuf },
+    ComposeCommand { input: String },
+    /// Name prompt for saving the `:` command line as a bookmark.
+    SaveBookmark { command: String, input: String },
+    /// `;` menu of saved compose command lines.
+    Bookmarks { selected: usize },
+    /// `G` prompt: a container name or id prefix, optionally followed by an
+    /// action key to run once it is selected.
+    GoToContainer { input: String },
+    /// A `docker run` rebuilt from inspect, already copied to the clipboard.
+    RunCommand { name: String, command: String },
+    /// `i` on a task: its settings and the command laid out line by line.
+    TaskInfo { name: String },
+    Diagnostics,
+    /// `?`: every key by category; `scroll` is clamped when drawn.
+    Help { scroll: u16 },
+}
+
+/// Action waiting behind a [`Popup::ConfirmTyped`].
+#[derive(Clone)]
+pub enum TypedAction {
+    Prune,
+    /// Remove the container and its volumes, then `compose up` the service
+    /// again when `recreate` is set.
+    Reset { id: String, name: String, service: Option<(String, String)>, recreate: bool },
+    /// `docker compose -p <project> down`, with `-v` when `volumes` is set.
+    ComposeDown { project: String, volumes: bool },
+}
+
+impl Popup {
+    /// Yes/no prompts, which `CONFIRM_TIMEOUT` may answer with their safe choice.
+    pub fn is_confirm(&self) -> bool {
+        matches!(
+            self,
+            Popup::ConfirmReset { .. }
+                | Popup::ConfirmBulkRemove { .. }
+                | Popup::ConfirmComposeRestart { .. }
+                | Popup::ConfirmComposeDown { .. }
+                | Popup::ConfirmUpMissing { .. }
+                | Popup::ConfirmStackToggle { .. }
+                | Popup::ConfirmPrune
+                | Popup::ConfirmVolumeRemove { .. }
+                | Popup::ConfirmImagePrune { .. }
+                | Popup::ConfirmTyped { .. }
+                | Popup::ConfirmCreateEnv { .. }
+        )
+    }
 }